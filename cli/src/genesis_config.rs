@@ -260,13 +260,22 @@ pub fn genesis(genesis_spec: GenesisSpec) -> GenesisConfig {
             claims: sdot_claims,
         }),
         xbridge_features: Some(XBridgeFeaturesConfig {
-            trustee_info_config: vec![(
-                Chain::Bitcoin,
-                TrusteeInfoConfig {
-                    min_trustee_count: 3,
-                    max_trustee_count: 15,
-                },
-            )],
+            trustee_info_config: vec![
+                (
+                    Chain::Bitcoin,
+                    TrusteeInfoConfig {
+                        min_trustee_count: 3,
+                        max_trustee_count: 15,
+                    },
+                ),
+                (
+                    Chain::Ethereum,
+                    TrusteeInfoConfig {
+                        min_trustee_count: 3,
+                        max_trustee_count: 15,
+                    },
+                ),
+            ],
             _genesis_phantom_data: Default::default(),
         }),
         xcontracts: contracts_config,
@@ -283,6 +292,13 @@ pub fn genesis(genesis_spec: GenesisSpec) -> GenesisConfig {
                 (btc_asset.clone(), true, true),
                 (sdot_asset.clone(), true, true),
             ],
+            // (who, token, value, start, cliff, period)
+            vesting: vec![],
+            // the initial PCX of the team is unlocked linearly in a year on the dev chain
+            team_vesting: match genesis_spec {
+                GenesisSpec::Dev => Some((0, 0, 365 * 24 * 60 * 30)),
+                _ => None,
+            },
             // xstaking
             intentions: genesis_node_info
                 .iter()
@@ -7,7 +7,7 @@ use serde_json::{json, Value};
 use std::iter::FromIterator;
 
 use parity_codec::{Decode, Encode};
-use rustc_hex::FromHex;
+use rustc_hex::ToHex;
 // substrate
 use primitives::{Blake2Hasher, H160, H256};
 use runtime_primitives::generic::SignedBlock;
@@ -800,7 +800,21 @@ where
         if let Some((number, info)) =
             self.trustee_session_info_for(self.block_id_by_hash(hash)?, chain, number)?
         {
-            return Ok(parse_trustee_session_info(chain, number, info));
+            let migration = match chain {
+                Chain::Bitcoin => {
+                    let state = self.state_at(hash)?;
+                    let key = <xbitcoin::TrusteeMigrationOf<Runtime>>::key_for(&number);
+                    Self::pickout::<xbitcoin::TrusteeMigration>(&state, &key, Hasher::BLAKE2256)?
+                        .map(TrusteeMigrationInfo::from)
+                }
+                _ => None,
+            };
+            return Ok(
+                parse_trustee_session_info(chain, number, info).map(|mut session_info| {
+                    session_info["migration"] = json!(migration);
+                    session_info
+                }),
+            );
         } else {
             return Ok(None);
         }
@@ -822,14 +836,7 @@ where
         tx_length: u64,
         hash: Option<<Block as BlockT>::Hash>,
     ) -> Result<Option<u64>> {
-        if !call_params.starts_with("0x") {
-            return Err(Error::BinaryStartErr.into());
-        }
-        let call_params: Vec<u8> = if let Ok(hex_call) = call_params[2..].from_hex() {
-            hex_call
-        } else {
-            return Err(Error::HexDecodeErr.into());
-        };
+        let call_params = decode_hex_params(&call_params)?;
         let call: Call = if let Some(call) = Decode::decode(&mut call_params.as_slice()) {
             call
         } else {
@@ -857,15 +864,156 @@ where
         let key = <xfee_manager::TransactionByteFee<Runtime>>::key();
         let transaction_byte_fee =
             Self::pickout::<Balance>(&state, &key, Hasher::TWOX128)?.unwrap_or(100);
+        let key = <xfee_manager::FeeMultiplier<Runtime>>::key();
+        let fee_multiplier = Self::pickout::<u32>(&state, &key, Hasher::TWOX128)?
+            .unwrap_or(xfee_manager::FEE_MULTIPLIER_BASE as u32);
         Ok(json!(
             {
                 "transactionBaseFee": transaction_base_fee,
                 "transactionByteFee": transaction_byte_fee,
                 "feeWeight": fee_weight,
+                "feeMultiplier": fee_multiplier,
+            }
+        ))
+    }
+
+    fn fee_multiplier(&self, hash: Option<<Block as BlockT>::Hash>) -> Result<Value> {
+        let state = self.state_at(hash)?;
+        let key = <xfee_manager::FeeMultiplier<Runtime>>::key();
+        let fee_multiplier = Self::pickout::<u32>(&state, &key, Hasher::TWOX128)?
+            .unwrap_or(xfee_manager::FEE_MULTIPLIER_BASE as u32);
+        Ok(json!(
+            {
+                "feeMultiplier": fee_multiplier,
+                "base": xfee_manager::FEE_MULTIPLIER_BASE,
+            }
+        ))
+    }
+
+    fn burned_fee(&self, hash: Option<<Block as BlockT>::Hash>) -> Result<Value> {
+        let state = self.state_at(hash)?;
+        let key = <xfee_manager::BurnedFee<Runtime>>::key();
+        let burned_fee = Self::pickout::<Balance>(&state, &key, Hasher::TWOX128)?.unwrap_or(0);
+        let key = <xfee_manager::FeeBurnProportion<Runtime>>::key();
+        let burn_proportion =
+            Self::pickout::<(u32, u32)>(&state, &key, Hasher::TWOX128)?.unwrap_or((0, 1));
+        Ok(json!(
+            {
+                "burnedFee": burned_fee,
+                "burnProportion": burn_proportion,
+            }
+        ))
+    }
+
+    fn estimate_extrinsic_fee(
+        &self,
+        extrinsic: String,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Value> {
+        let extrinsic = decode_hex_params(&extrinsic)?;
+        let (payer, fee_power, fee, token_fee) = self
+            .estimate_fee(self.block_id_by_hash(hash)?, extrinsic)?
+            .map_err(|e| Error::RuntimeErr(e, None))?;
+        let payer: AccountIdForRpc = payer.into();
+        let (fee_token, token_fee) = match token_fee {
+            Some((token, token_fee)) => (Some(to_string!(&token)), Some(token_fee)),
+            None => (None, None),
+        };
+        Ok(json!(
+            {
+                "payer": payer,
+                "feePower": fee_power,
+                "fee": fee,
+                "feeToken": fee_token,
+                "tokenFee": token_fee,
+            }
+        ))
+    }
+
+    fn dry_run_extrinsic(
+        &self,
+        extrinsic: String,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Value> {
+        let extrinsic = decode_hex_params(&extrinsic)?;
+        let (result, events) = self.dry_run(self.block_id_by_hash(hash)?, extrinsic)?;
+        let events = events
+            .into_iter()
+            .map(
+                |event| match <chainx_runtime::Event as Decode>::decode(&mut event.as_slice()) {
+                    Some(event) => format!("{:?}", event),
+                    None => format!("0x{:}", event.to_hex::<String>()),
+                },
+            )
+            .collect::<Vec<_>>();
+        Ok(json!(
+            {
+                "success": result.is_ok(),
+                "error": result.err().map(|e| to_string!(&e)),
+                "events": events,
             }
         ))
     }
 
+    fn allowance(
+        &self,
+        owner: AccountIdForRpc,
+        spender: AccountIdForRpc,
+        token: String,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Balance> {
+        let state = self.state_at(hash)?;
+        let owner: AccountId = owner.unchecked_into();
+        let spender: AccountId = spender.unchecked_into();
+        let key = <xassets::Allowance<Runtime>>::key_for(&(owner, spender, token.into_bytes()));
+        Ok(Self::pickout::<Balance>(&state, &key, Hasher::BLAKE2256)?.unwrap_or(0))
+    }
+
+    fn allowances_of(
+        &self,
+        owner: AccountIdForRpc,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Value> {
+        let state = self.state_at(hash)?;
+        let owner: AccountId = owner.unchecked_into();
+        let key = <xassets::AllowancesOf<Runtime>>::key_for(&owner);
+        let list =
+            Self::pickout::<Vec<(AccountId, xassets::Token)>>(&state, &key, Hasher::BLAKE2256)?
+                .unwrap_or_default();
+
+        let mut allowances = Vec::new();
+        for (spender, token) in list {
+            let key = <xassets::Allowance<Runtime>>::key_for(&(
+                owner.clone(),
+                spender.clone(),
+                token.clone(),
+            ));
+            let allowance = Self::pickout::<Balance>(&state, &key, Hasher::BLAKE2256)?.unwrap_or(0);
+            let spender: AccountIdForRpc = spender.into();
+            allowances.push(json!(
+                {
+                    "spender": spender,
+                    "token": to_string!(&token),
+                    "allowance": allowance,
+                }
+            ));
+        }
+        Ok(Value::Array(allowances))
+    }
+
+    fn frozen_assets_of(
+        &self,
+        who: AccountIdForRpc,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Vec<String>> {
+        let state = self.state_at(hash)?;
+        let who: AccountId = who.unchecked_into();
+        let key = <xassets::FrozenTokensOf<Runtime>>::key_for(&who);
+        let tokens = Self::pickout::<Vec<xassets::Token>>(&state, &key, Hasher::BLAKE2256)?
+            .unwrap_or_default();
+        Ok(tokens.iter().map(|token| to_string!(token)).collect())
+    }
+
     fn withdraw_tx(
         &self,
         chain: Chain,
@@ -888,6 +1036,207 @@ where
         }
     }
 
+    fn lockups_of(
+        &self,
+        who: AccountIdForRpc,
+        page_index: u32,
+        page_size: u32,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<PageData<LockupInfo>>> {
+        if page_size > MAX_PAGE_SIZE || page_size < 1 {
+            return Err(Error::PageSizeErr(page_size).into());
+        }
+
+        let state = self.state_at(hash)?;
+        let who: AccountId = who.unchecked_into();
+
+        let key = <xbitcoin::lockup::AccountLockedUTXO<Runtime>>::key_for(&who);
+        let utxo_list =
+            Self::pickout::<Vec<(xbitcoin::H256, u32)>>(&state, &key, Hasher::BLAKE2256)?
+                .unwrap_or_default();
+
+        let mut lockups = Vec::new();
+        for utxo in utxo_list.into_iter().rev() {
+            let key = <xbitcoin::lockup::LockedUpBTC<Runtime>>::key_for(&utxo);
+            if let Some((_, value, addr)) =
+                Self::pickout::<(AccountId, u64, BitcoinAddress)>(&state, &key, Hasher::BLAKE2256)?
+            {
+                let key = <xbitcoin::lockup::LockedUpBTCTimeLock<Runtime>>::key_for(&utxo);
+                let timelock = Self::pickout::<LockupTimeLock>(&state, &key, Hasher::BLAKE2256)?;
+                lockups.push(LockupInfo::new(utxo, value, addr.to_string(), timelock));
+            }
+        }
+
+        into_pagedata(lockups, page_index, page_size)
+    }
+
+    fn lockup_stats(
+        &self,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<LockupStatistics>> {
+        let state = self.state_at(hash)?;
+        let key = <xbitcoin::lockup::LockupStats<Runtime>>::key();
+        Ok(Some(
+            Self::pickout::<LockupStatistics>(&state, &key, Hasher::TWOX128)?.unwrap_or_default(),
+        ))
+    }
+
+    fn unclaimed_sdot(
+        &self,
+        page_index: u32,
+        page_size: u32,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<PageData<UnclaimedSdotInfo>>> {
+        if page_size > MAX_PAGE_SIZE || page_size < 1 {
+            return Err(Error::PageSizeErr(page_size).into());
+        }
+
+        let state = self.state_at(hash)?;
+
+        let key = <xsdot::ClaimAddresses<Runtime>>::key();
+        let addresses = Self::pickout::<Vec<EthereumAddress>>(&state, &key, Hasher::TWOX128)?
+            .unwrap_or_default();
+
+        let mut unclaimed = Vec::new();
+        for address in addresses {
+            let key = <xsdot::Claims<Runtime>>::key_for(&address);
+            if let Some(balance) = Self::pickout::<Balance>(&state, &key, Hasher::BLAKE2256)? {
+                unclaimed.push(UnclaimedSdotInfo::new(address, balance));
+            }
+        }
+
+        into_pagedata(unclaimed, page_index, page_size)
+    }
+
+    fn trustee_sign_stats(
+        &self,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Vec<TrusteeSignStatsInfo>>> {
+        let trustees = match self.trustee_session_info_for(
+            self.block_id_by_hash(hash)?,
+            Chain::Bitcoin,
+            None,
+        )? {
+            Some((_, info)) => info.trustees_info,
+            None => return Ok(None),
+        };
+
+        let state = self.state_at(hash)?;
+        let mut stats = Vec::new();
+        for (who, _) in trustees {
+            let key = <xbitcoin::TrusteeSignStatsOf<Runtime>>::key_for(&who);
+            let sign_stats =
+                Self::pickout::<xbitcoin::TrusteeSignStats>(&state, &key, Hasher::BLAKE2256)?
+                    .unwrap_or_default();
+            stats.push(TrusteeSignStatsInfo::new(who, sign_stats));
+        }
+
+        Ok(Some(stats))
+    }
+
+    fn multisig_list(
+        &self,
+        who: AccountIdForRpc,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Vec<MultiSigInfo>>> {
+        let who: AccountId = who.unchecked_into();
+        let state = self.state_at(hash)?;
+
+        let key = <xmultisig::MultiSigAddrsOf<Runtime>>::key_for(&who);
+        let addrs =
+            Self::pickout::<Vec<AccountId>>(&state, &key, Hasher::BLAKE2256)?.unwrap_or_default();
+
+        let mut list = Vec::new();
+        for addr in addrs {
+            if let Some(info) = self.get_multisig_info(&state, &addr)? {
+                list.push(info);
+            }
+        }
+
+        Ok(Some(list))
+    }
+
+    fn multisig_info(
+        &self,
+        addr: AccountIdForRpc,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<MultiSigInfo>> {
+        let state = self.state_at(hash)?;
+        Ok(self.get_multisig_info(&state, &addr.unchecked_into())?)
+    }
+
+    fn multisig_pending(
+        &self,
+        addr: AccountIdForRpc,
+        hash: Option<<Block as BlockT>::Hash>,
+    ) -> Result<Option<Vec<MultiSigPendingInfo>>> {
+        let addr: AccountId = addr.unchecked_into();
+        let state = self.state_at(hash)?;
+
+        let key = <xmultisig::MultiSigAddrInfo<Runtime>>::key_for(&addr);
+        let owners =
+            match Self::pickout::<xmultisig::AddrInfo<AccountId>>(&state, &key, Hasher::BLAKE2256)?
+            {
+                Some(info) => info.owner_list,
+                None => return Ok(None),
+            };
+
+        let key = <xmultisig::PendingListFor<Runtime>>::key_for(&addr);
+        let pending_list =
+            Self::pickout::<Vec<H256>>(&state, &key, Hasher::BLAKE2256)?.unwrap_or_default();
+
+        let mut result = Vec::new();
+        for multi_sig_id in pending_list {
+            let key =
+                <xmultisig::PendingStateV1For<Runtime>>::key_for(&(addr.clone(), multi_sig_id));
+            let pending = match Self::pickout::<xmultisig::PendingStateV1<Call>>(
+                &state,
+                &key,
+                Hasher::BLAKE2256,
+            )? {
+                Some(pending) => pending,
+                None => {
+                    let key = <xmultisig::PendingStateFor<Runtime>>::key_for(&(
+                        addr.clone(),
+                        multi_sig_id,
+                    ));
+                    match Self::pickout::<xmultisig::PendingState<Call>>(
+                        &state,
+                        &key,
+                        Hasher::BLAKE2256,
+                    )? {
+                        Some(pending) => pending.into(),
+                        None => continue,
+                    }
+                }
+            };
+
+            let key = <xmultisig::PendingExpiryOf<Runtime>>::key_for(&(addr.clone(), multi_sig_id));
+            let expire_at = Self::pickout::<BlockNumber>(&state, &key, Hasher::BLAKE2256)?;
+
+            let (confirmed, missing): (Vec<_>, Vec<_>) = owners
+                .iter()
+                .enumerate()
+                .partition(|(index, _)| pending.owners_done.contains(&(*index as u32)));
+            result.push(MultiSigPendingInfo {
+                multi_sig_id,
+                proposal: parse_call(&pending.proposal),
+                yet_needed: pending.yet_needed,
+                confirmed: confirmed
+                    .into_iter()
+                    .map(|(_, (owner, _))| owner.clone().into())
+                    .collect(),
+                missing: missing
+                    .into_iter()
+                    .map(|(_, (owner, _))| owner.clone().into())
+                    .collect(),
+                expire_at,
+            });
+        }
+
+        Ok(Some(result))
+    }
+
     fn mock_bitcoin_new_trustees(
         &self,
         candidates: Vec<AccountIdForRpc>,
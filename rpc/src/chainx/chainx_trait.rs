@@ -179,9 +179,76 @@ pub trait ChainXApi<Number, Hash, AccountId, Balance, BlockNumber, SignedBlock>
     #[rpc(name = "chainx_getFeeWeightMap")]
     fn fee_weight_map(&self, hash: Option<Hash>) -> Result<Value>;
 
+    #[rpc(name = "chainx_getFeeMultiplier")]
+    fn fee_multiplier(&self, hash: Option<Hash>) -> Result<Value>;
+
+    #[rpc(name = "chainx_getBurnedFee")]
+    fn burned_fee(&self, hash: Option<Hash>) -> Result<Value>;
+
+    #[rpc(name = "chainx_estimateFee")]
+    fn estimate_extrinsic_fee(&self, extrinsic: String, hash: Option<Hash>) -> Result<Value>;
+
+    #[rpc(name = "chainx_dryRun")]
+    fn dry_run_extrinsic(&self, extrinsic: String, hash: Option<Hash>) -> Result<Value>;
+
+    #[rpc(name = "chainx_getAllowance")]
+    fn allowance(
+        &self,
+        owner: AccountId,
+        spender: AccountId,
+        token: String,
+        hash: Option<Hash>,
+    ) -> Result<Balance>;
+
+    #[rpc(name = "chainx_getAllowancesByAccount")]
+    fn allowances_of(&self, owner: AccountId, hash: Option<Hash>) -> Result<Value>;
+
+    #[rpc(name = "chainx_getFrozenAssetsByAccount")]
+    fn frozen_assets_of(&self, who: AccountId, hash: Option<Hash>) -> Result<Vec<String>>;
+
     #[rpc(name = "chainx_getWithdrawTx")]
     fn withdraw_tx(&self, chain: Chain, hash: Option<Hash>) -> Result<Option<WithdrawTxInfo>>;
 
+    #[rpc(name = "chainx_getLockupsByAccount")]
+    fn lockups_of(
+        &self,
+        who: AccountId,
+        page_index: u32,
+        page_size: u32,
+        hash: Option<Hash>,
+    ) -> Result<Option<PageData<LockupInfo>>>;
+
+    #[rpc(name = "chainx_getLockupStats")]
+    fn lockup_stats(&self, hash: Option<Hash>) -> Result<Option<LockupStatistics>>;
+
+    #[rpc(name = "chainx_getUnclaimedSdot")]
+    fn unclaimed_sdot(
+        &self,
+        page_index: u32,
+        page_size: u32,
+        hash: Option<Hash>,
+    ) -> Result<Option<PageData<UnclaimedSdotInfo>>>;
+
+    #[rpc(name = "chainx_getTrusteeSignStats")]
+    fn trustee_sign_stats(&self, hash: Option<Hash>) -> Result<Option<Vec<TrusteeSignStatsInfo>>>;
+
+    #[rpc(name = "chainx_getMultiSigListByAccount")]
+    fn multisig_list(
+        &self,
+        who: AccountId,
+        hash: Option<Hash>,
+    ) -> Result<Option<Vec<MultiSigInfo>>>;
+
+    #[rpc(name = "chainx_getMultiSigInfo")]
+    fn multisig_info(&self, addr: AccountId, hash: Option<Hash>) -> Result<Option<MultiSigInfo>>;
+
+    #[rpc(name = "chainx_getMultiSigPending")]
+    fn multisig_pending(
+        &self,
+        addr: AccountId,
+        hash: Option<Hash>,
+    ) -> Result<Option<Vec<MultiSigPendingInfo>>>;
+
     #[rpc(name = "chainx_getMockBitcoinNewTrustees")]
     fn mock_bitcoin_new_trustees(
         &self,
@@ -38,6 +38,7 @@ use runtime_api::{
 };
 
 use xassets::{Asset, AssetType, Chain, ChainT, Token};
+use xbitcoin::lockup::types::{LockupStatistics, LockupTimeLock};
 use xbridge_common::types::{GenericAllSessionInfo, GenericTrusteeIntentionProps};
 use xprocess::WithdrawalLimit;
 use xspot::TradingPairIndex;
@@ -202,6 +203,8 @@ where
 
         // XFeeApi
         fn transaction_fee(power: Vec<u8>, encoded_len: u64) -> Option<u64>;
+        fn estimate_fee(extrinsic: Vec<u8>) -> result::Result<(AccountId, u64, Balance, Option<(Token, Balance)>), Vec<u8>>;
+        fn dry_run(extrinsic: Vec<u8>) -> (result::Result<(), Vec<u8>>, Vec<Vec<u8>>);
 
         // XStakingApi
         fn intention_set() -> Vec<AccountId>;
@@ -644,6 +647,38 @@ where
         )
     }
 
+    fn get_multisig_info(
+        &self,
+        state: &<B as client::backend::Backend<Block, Blake2Hasher>>::State,
+        addr: &AccountId,
+    ) -> result::Result<Option<MultiSigInfo>, error::Error> {
+        let key = <xmultisig::MultiSigAddrInfo<Runtime>>::key_for(addr);
+        let addr_info = match Self::pickout::<xmultisig::AddrInfo<AccountId>>(
+            state,
+            &key,
+            Hasher::BLAKE2256,
+        )? {
+            Some(info) => info,
+            None => return Ok(None),
+        };
+
+        let key = <xmultisig::OwnerWeightsFor<Runtime>>::key_for(addr);
+        let owner_weights =
+            Self::pickout::<Vec<u32>>(state, &key, Hasher::BLAKE2256)?.unwrap_or_default();
+
+        let key = <xmultisig::MultiSigDepositOf<Runtime>>::key_for(addr);
+        let deposit = Self::pickout::<(AccountId, Balance)>(state, &key, Hasher::BLAKE2256)?
+            .map(|(_, deposit)| deposit)
+            .unwrap_or_default();
+
+        Ok(Some(MultiSigInfo::new(
+            addr.clone(),
+            addr_info,
+            owner_weights,
+            deposit,
+        )))
+    }
+
     fn get_psedu_nomination_records_wrapper(
         &self,
         state: &<B as client::backend::Backend<Block, Blake2Hasher>>::State,
@@ -20,6 +20,7 @@ use btc_ser::serialize as btc_serialize;
 use chainx_primitives::AccountIdForRpc;
 
 use xassets::AssetLimit;
+use xbitcoin::lockup::types::LockupTimeLock;
 use xbitcoin::VoteResult;
 use xrecords::{HeightOrTime, RecordInfo, TxState};
 use xspot::{
@@ -260,6 +261,166 @@ impl WithdrawTxInfo {
     }
 }
 
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrusteeMigrationInfo {
+    /// btc held by the last trustee addresses at the transition
+    pub to_migrate: u64,
+    pub migrated: u64,
+    /// btc still left on the last trustee addresses
+    pub remaining: u64,
+    /// migration txids
+    pub txids: Vec<String>,
+    pub confirmed: bool,
+}
+
+impl From<xbitcoin::TrusteeMigration> for TrusteeMigrationInfo {
+    fn from(migration: xbitcoin::TrusteeMigration) -> Self {
+        TrusteeMigrationInfo {
+            to_migrate: migration.to_migrate,
+            migrated: migration.migrated,
+            remaining: migration.remaining(),
+            txids: migration
+                .txids
+                .iter()
+                .map(|txid| format!("0x{:}", txid.as_bytes().to_hex::<String>()))
+                .collect(),
+            confirmed: migration.confirmed,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct TrusteeSignStatsInfo {
+    pub trustee: AccountId,
+    /// signed withdrawal proposals
+    pub signed: u32,
+    /// rejected withdrawal proposals
+    pub rejected: u32,
+    /// missed signing deadlines
+    pub missed: u32,
+    /// missed signing deadlines in a row
+    pub consecutive_missed: u32,
+}
+
+impl TrusteeSignStatsInfo {
+    pub fn new(trustee: AccountId, stats: xbitcoin::TrusteeSignStats) -> Self {
+        TrusteeSignStatsInfo {
+            trustee,
+            signed: stats.signed,
+            rejected: stats.rejected,
+            missed: stats.missed,
+            consecutive_missed: stats.consecutive_missed,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigInfo {
+    pub addr: AccountIdForRpc,
+    pub addr_type: xmultisig::AddrType,
+    pub required_num: u32,
+    pub owner_list: Vec<(AccountIdForRpc, xmultisig::MultiSigPermission)>,
+    /// weights in the order of `owner_list`
+    pub owner_weights: Vec<u32>,
+    /// reserved deposit for deploying this multisig addr
+    pub deposit: Balance,
+}
+
+impl MultiSigInfo {
+    pub fn new(
+        addr: AccountId,
+        info: xmultisig::AddrInfo<AccountId>,
+        owner_weights: Vec<u32>,
+        deposit: Balance,
+    ) -> Self {
+        // every owner weights 1 if not set
+        let owner_weights = if owner_weights.is_empty() {
+            vec![1; info.owner_list.len()]
+        } else {
+            owner_weights
+        };
+        MultiSigInfo {
+            addr: addr.into(),
+            addr_type: info.addr_type,
+            required_num: info.required_num,
+            owner_list: info
+                .owner_list
+                .into_iter()
+                .map(|(owner, permission)| (owner.into(), permission))
+                .collect(),
+            owner_weights,
+            deposit,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MultiSigPendingInfo {
+    pub multi_sig_id: H256,
+    /// decoded proposal call
+    pub proposal: Value,
+    /// weight still needed
+    pub yet_needed: u32,
+    /// owners who have confirmed
+    pub confirmed: Vec<AccountIdForRpc>,
+    /// owners who have not confirmed yet
+    pub missing: Vec<AccountIdForRpc>,
+    pub expire_at: Option<BlockNumber>,
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnclaimedSdotInfo {
+    /// ethereum address of the claim
+    pub address: String,
+    pub balance: Balance,
+}
+
+impl UnclaimedSdotInfo {
+    pub fn new(address: xsdot::EthereumAddress, balance: Balance) -> Self {
+        UnclaimedSdotInfo {
+            address: format!("0x{:}", address.to_hex::<String>()),
+            balance,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct LockupInfo {
+    /// bitcoin txid of lock tx
+    pub txid: String,
+    /// output index in lock tx
+    pub index: u32,
+    /// locked btc value
+    pub value: u64,
+    /// btc-address of lock output
+    pub address: String,
+    /// time lock info, none for normal lockup
+    pub timelock: Option<LockupTimeLock>,
+}
+
+impl LockupInfo {
+    pub fn new(
+        utxo: (xbitcoin::H256, u32),
+        value: u64,
+        address: String,
+        timelock: Option<LockupTimeLock>,
+    ) -> Self {
+        LockupInfo {
+            txid: format!("0x{:}", utxo.0.as_bytes().to_hex::<String>()),
+            index: utxo.1,
+            value,
+            address,
+            timelock,
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct OrderDetails {
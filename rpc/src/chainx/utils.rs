@@ -2,24 +2,34 @@
 
 use super::*;
 
+use std::fmt::Debug;
 use std::iter::FromIterator;
 
 use log::error;
-use rustc_hex::ToHex;
+use parity_codec::Encode;
+use rustc_hex::{FromHex, ToHex};
+use serde::Serialize;
 
 use serde_json::{json, Map, Value};
 
+use primitives::H160;
+use support::dispatch::{DecodeDifferent, FunctionMetadata};
+
 use btc_keys::DisplayLayout;
 
 // chainx
 use chainx_primitives::AccountIdForRpc;
+use chainx_runtime::Call;
+use xbitcoin::lockup as xbitcoin_lockup;
 use xr_primitives::generic::b58;
 
 use xbridge_common::{
     traits::IntoVecu8,
     types::{GenericAllSessionInfo, GenericTrusteeIntentionProps},
 };
-use xbridge_features::trustees::{BitcoinPublic, BitcoinTrusteeAddrInfo};
+use xbridge_features::trustees::{
+    BitcoinPublic, BitcoinTrusteeAddrInfo, EthereumTrusteeAddrInfo, EthereumTrusteeType,
+};
 
 /// Convert &[u8] to String
 macro_rules! to_string {
@@ -28,6 +38,14 @@ macro_rules! to_string {
     };
 }
 
+/// Decode the `0x` prefixed hex params.
+pub fn decode_hex_params(params: &str) -> Result<Vec<u8>> {
+    if !params.starts_with("0x") {
+        return Err(Error::BinaryStartErr);
+    }
+    params[2..].from_hex().map_err(|_| Error::HexDecodeErr)
+}
+
 pub fn try_hex_or_str(src: &[u8]) -> String {
     let check_is_str = |src: &[u8]| -> bool {
         for c in src {
@@ -78,12 +96,36 @@ fn parse_generic_trustee_props(
                 })
             }
         }
+        Chain::Ethereum => {
+            let hot_addr = EthereumTrusteeType::from_vecu8(props.0.hot_entity.as_slice());
+            let cold_addr = EthereumTrusteeType::from_vecu8(props.0.cold_entity.as_slice());
+            match (hot_addr, cold_addr) {
+                (Some(hot_addr), Some(cold_addr)) => json!({
+                    "about": to_string!(&props.0.about),
+                    "hotEntity": format_ethereum_addr(hot_addr),
+                    "coldEntity": format_ethereum_addr(cold_addr),
+                }),
+                _ => {
+                    error!(
+                        "parse_generic_trustee_props for ethereum error|hot_entity:{:}|cold_entity:{:}",
+                        try_hex_or_str(&props.0.hot_entity),
+                        try_hex_or_str(&props.0.cold_entity)
+                    );
+                    return None;
+                }
+            }
+        }
         // TODO when add other trustee, must add related parse here
-        _ => unimplemented!("not support for other chain"),
+        _ => return None,
     };
     Some(result)
 }
 
+fn format_ethereum_addr(addr: EthereumTrusteeType) -> String {
+    let addr: H160 = addr.into();
+    format!("{:?}", addr)
+}
+
 pub fn parse_trustee_props(map: BTreeMap<Chain, GenericTrusteeIntentionProps>) -> Option<Value> {
     let map = Map::from_iter(map.into_iter().map(|(chain, generic_props)| {
         (
@@ -110,12 +152,379 @@ pub fn parse_trustee_session_addr(chain: Chain, addr: &[u8]) -> Option<Value> {
                 "redeemScript": try_hex_or_str(&trustee_addr_info.redeem_script)
             })
         }
+        Chain::Ethereum => {
+            let config = EthereumTrusteeAddrInfo::from_vecu8(addr)?;
+            json!({
+                "owners": config.owners.into_iter().map(format_ethereum_addr).collect::<Vec<_>>(),
+                "required": config.required,
+            })
+        }
         // TODO when add other trustee, must add related parse here
-        _ => unimplemented!("not support for other chain"),
+        _ => return None,
     };
     Some(result)
 }
 
+/// A decoded call argument, converted to json by the most specific way its type supports:
+/// the types known by the rpc, then `Serialize`, then `Debug` which all the call args derive.
+struct CallArg<'a, T>(&'a T);
+
+trait CallArgByType {
+    fn to_json(&self) -> Value;
+}
+
+trait CallArgBySerialize {
+    fn to_json(&self) -> Value;
+}
+
+trait CallArgByDebug {
+    fn to_json(&self) -> Value;
+}
+
+impl<'a> CallArgByType for &&CallArg<'a, AccountId> {
+    fn to_json(&self) -> Value {
+        json!(AccountIdForRpc::from(self.0.clone()))
+    }
+}
+
+impl<'a> CallArgByType for &&CallArg<'a, Vec<u8>> {
+    fn to_json(&self) -> Value {
+        json!(try_hex_or_str(self.0))
+    }
+}
+
+impl<'a> CallArgByType for &&CallArg<'a, Option<Vec<u8>>> {
+    fn to_json(&self) -> Value {
+        json!(self.0.as_ref().map(|v| try_hex_or_str(v)))
+    }
+}
+
+impl<'a> CallArgByType for &&CallArg<'a, Box<Call>> {
+    fn to_json(&self) -> Value {
+        parse_call(self.0)
+    }
+}
+
+impl<'a, T: Serialize + Debug> CallArgBySerialize for &CallArg<'a, T> {
+    fn to_json(&self) -> Value {
+        serde_json::to_value(self.0).unwrap_or_else(|_| json!(format!("{:?}", self.0)))
+    }
+}
+
+impl<'a, T: Debug> CallArgByDebug for CallArg<'a, T> {
+    fn to_json(&self) -> Value {
+        json!(format!("{:?}", self.0))
+    }
+}
+
+/// Parse the method and the args of a decoded module call, the args are listed in the order of
+/// the call variants generated by `decl_module!`.
+macro_rules! parse_module_call {
+    ($name:ident => $module:ident, $call:expr; $( $method:ident( $( $arg:ident ),* ), )+) => {
+        match $call {
+            $(
+                $module::Call::$method( $( $arg ),* ) => {
+                    let args: Vec<Value> = vec![
+                        $(
+                            json!({
+                                "name": stringify!($arg),
+                                "value": (&&&CallArg($arg)).to_json(),
+                            })
+                        ),*
+                    ];
+                    json!({
+                        "module": stringify!($name),
+                        "method": stringify!($method),
+                        "args": args,
+                    })
+                }
+            )+
+            #[allow(unreachable_patterns)]
+            _ => json!({ "module": stringify!($name), "method": Value::Null, "args": [] }),
+        }
+    };
+}
+
+/// Parse a call of the modules outside chainx, only the method is known by the call metadata.
+fn parse_metadata_call(module: &str, encoded: &[u8], functions: &[FunctionMetadata]) -> Value {
+    // the call is encoded as the function index and the args in order
+    let (method, args) = match encoded.split_first() {
+        Some((index, args)) => (functions.get(*index as usize), args),
+        None => (None, encoded),
+    };
+    let method = method.map(|function| match function.name {
+        DecodeDifferent::Encode(name) => name.to_string(),
+        DecodeDifferent::Decoded(ref name) => name.clone(),
+    });
+    json!({
+        "module": module,
+        "method": method,
+        "args": format!("0x{:}", args.to_hex::<String>()),
+    })
+}
+
+/// Parse a runtime call into module, method and args,
+/// e.g. `{"module": "XAssets", "method": "transfer", "args": [{"name": "dest", ...}, ...]}`
+pub fn parse_call(call: &Call) -> Value {
+    let mut result = match call {
+        Call::Timestamp(call) => parse_metadata_call(
+            "Timestamp",
+            &call.encode(),
+            chainx_runtime::Timestamp::call_functions(),
+        ),
+        Call::Consensus(call) => parse_metadata_call(
+            "Consensus",
+            &call.encode(),
+            chainx_runtime::Consensus::call_functions(),
+        ),
+        Call::FinalityTracker(call) => parse_metadata_call(
+            "FinalityTracker",
+            &call.encode(),
+            chainx_runtime::FinalityTracker::call_functions(),
+        ),
+        Call::Session(call) => parse_module_call!(Session => xsession, call;
+            set_length(new),
+            force_new_session(apply_rewards),
+        ),
+        Call::Grandpa(call) => parse_module_call!(Grandpa => xgrandpa, call;
+            set_sessions_per_era(per_grandpa),
+            set_finalized_height(height),
+            report_misbehavior(_report),
+        ),
+        Call::XSystem(call) => parse_module_call!(XSystem => xsystem, call;
+            set_block_producer(producer),
+        ),
+        Call::XFeeManager(call) => parse_module_call!(XFeeManager => xfee_manager, call;
+            sponsor(who, budget, calls),
+            accept_sponsor(sponsor),
+            revoke_sponsor(who),
+            set_producer_producer_fee_proportion(proportion),
+            set_fee_burn_proportion(proportion),
+            set_failed_call_weight_proportion(proportion),
+            set_fee_token_premium(premium),
+            modify_switcher(switch, open),
+            modify_switcher_until(switch, until),
+            set_switcher(all_switcher),
+            set_method_call_weight(method, weight),
+            remove_method_call_weight(method),
+            add_blocked_account(who),
+            remove_blocked_account(who),
+        ),
+        Call::XAssets(call) => parse_module_call!(XAssets => xassets, call;
+            register_asset(asset, is_online, is_psedu_intention),
+            revoke_asset(token),
+            set_balance(who, token, balances),
+            transfer(dest, token, value, memo),
+            transfer_batch(token, dests, memo),
+            vested_transfer(dest, token, value, start, cliff, period),
+            vest(token),
+            vest_other(target, token),
+            approve(spender, token, value),
+            transfer_from(owner, dest, token, value, memo),
+            freeze(who, token),
+            unfreeze(who, token),
+            modify_asset_info(token, token_name, desc),
+            set_asset_limit_props(token, props),
+            set_min_vested_transfer(token, value),
+            modify_asset_limit(token, limit, can_do),
+        ),
+        Call::XAssetsRecords(call) => parse_module_call!(XAssetsRecords => xrecords, call;
+            deposit_from_root(who, token, balance),
+            withdrawal_from_root(who, token, balance),
+            fix_withdrawal_state(withdrawal_id, state),
+            fix_withdrawal_state_list(item),
+        ),
+        Call::XAssetsProcess(call) => parse_module_call!(XAssetsProcess => xprocess, call;
+            withdraw(token, value, addr, ext),
+            revoke_withdraw(id),
+            modify_token_black_list(token),
+        ),
+        Call::XStaking(call) => parse_module_call!(XStaking => xstaking, call;
+            nominate(target, value, memo),
+            renominate(from, to, value, memo),
+            unnominate(target, value, memo),
+            claim(target),
+            unfreeze(target, revocation_index),
+            refresh(url, desire_to_run, next_key, about),
+            register(name),
+            set_sessions_per_era(new),
+            set_bonding_duration(new),
+            set_validator_count(new),
+            set_missed_blocks_severity(new),
+            set_maximum_intention_count(new),
+            set_minimum_penalty(new),
+            set_distribution_ratio(new),
+            set_minimum_candidate_threshold(new),
+            set_upper_bond_factor(new),
+            set_trustee_election_eras(new),
+            set_nomination_record(
+                nominator,
+                nominee,
+                new_nomination,
+                new_last_vote_weight,
+                new_last_vote_weight_update,
+                new_revocations
+            ),
+            set_intention_profs(
+                intention,
+                new_total_nomination,
+                new_last_total_vote_weight,
+                new_last_total_vote_weight_update
+            ),
+            set_nomination_record_v1(
+                nominator,
+                nominee,
+                new_nomination,
+                new_last_vote_weight,
+                new_last_vote_weight_update,
+                new_revocations
+            ),
+            set_intention_profs_v1(
+                intention,
+                new_total_nomination,
+                new_last_total_vote_weight,
+                new_last_total_vote_weight_update
+            ),
+            remove_zombie_intentions(zombies),
+            set_global_distribution_ratio(new),
+        ),
+        Call::XTokens(call) => parse_module_call!(XTokens => xtokens, call;
+            claim(token),
+            set_token_discount(token, value),
+            set_deposit_reward(value),
+            set_claim_restriction(token, new),
+            set_deposit_record(
+                depositor,
+                token,
+                new_last_deposit_weight,
+                new_last_deposit_weight_update
+            ),
+            set_deposit_record_v1(
+                depositor,
+                token,
+                new_last_deposit_weight,
+                new_last_deposit_weight_update
+            ),
+            set_psedu_intention_profs(
+                token,
+                new_last_total_deposit_weight,
+                new_last_total_deposit_weight_update
+            ),
+            set_psedu_intention_profs_v1(
+                token,
+                new_last_total_deposit_weight,
+                new_last_total_deposit_weight_update
+            ),
+            set_airdrop_distribution_ratio(token, new_shares),
+            remove_airdrop_asset(token),
+            set_fixed_cross_chain_asset_power_map(token, new_power),
+            remove_cross_chain_asset(token),
+        ),
+        Call::XSpot(call) => parse_module_call!(XSpot => xspot, call;
+            put_order(pair_index, order_type, side, amount, price),
+            cancel_order(pair_index, order_index),
+            set_cancel_order(who, pair_index, order_index),
+            set_handicap(pair_index, highest_bid, lowest_offer),
+            refund_locked(who, token),
+        ),
+        Call::XBridgeOfBTC(call) => parse_module_call!(XBridgeOfBTC => xbitcoin, call;
+            push_header(header),
+            push_transaction(tx),
+            create_withdraw_tx(withdrawal_id_list, tx),
+            sign_withdraw_tx(tx),
+            fix_withdrawal_state_by_trustees(withdrawal_id, state),
+            set_btc_withdrawal_fee_by_trustees(fee),
+            remove_tx_and_proposal(txhash, drop_proposal),
+            set_trustee_utxos(utxos),
+            confirm_trustee_migration(),
+            set_btc_withdrawal_fee(fee),
+            set_sign_withdrawal_duration(duration),
+            set_max_consecutive_missed(count),
+            set_btc_deposit_limit(value),
+            set_btc_deposit_limit_by_trustees(value),
+            remove_pending(addr, who),
+            remove_pending_by_trustees(addr, who),
+            set_best_index(hash),
+            set_header_confirmed_state(hash, confirmed),
+        ),
+        Call::XBridgeOfSDOT(call) => parse_module_call!(XBridgeOfSDOT => xsdot, call;
+            claim(ethereum_signature, sign_data, input_data),
+            claim_typed(ethereum_signature, input_data),
+            set_claim_deadline(deadline),
+            set_claim_addresses(addresses),
+            sweep_unclaimed(addresses),
+        ),
+        Call::XBridgeFeatures(call) => {
+            parse_module_call!(XBridgeFeatures => xbridge_features, call;
+                setup_bitcoin_trustee(about, hot_entity, cold_entity),
+                setup_ethereum_trustee(about, hot_entity, cold_entity),
+                transition_trustee_session(chain, new_trustees),
+                transition_trustee_session_by_root(chain, new_trustees),
+                set_trustee_info_config(chain, config),
+            )
+        }
+        Call::XMultiSig(call) => parse_module_call!(XMultiSig => xmultisig, call;
+            deploy(owners, required_num),
+            remove_multi_sig_addr(),
+            execute(multi_sig_addr, proposal),
+            confirm(multi_sig_addr, multi_sig_id),
+            remove_multi_sig_for(multi_sig_addr, multi_sig_id),
+            transition(owners, required_num),
+            set_owner_weights(weights, threshold),
+            set_proposal_policy(expiry, delay),
+            veto(multi_sig_addr, multi_sig_id),
+            set_deploy_deposit(value),
+            index_multisig_owners(multi_sig_addrs),
+        ),
+        Call::XFisher(call) => parse_module_call!(XFisher => xfisher, call;
+            report_double_signer(double_signer, fst_header, snd_header),
+            register_fisherman(who),
+            remove_fisherman(who),
+        ),
+        Call::XBridgeOfBTCLockup(call) => {
+            parse_module_call!(XBridgeOfBTCLockup => xbitcoin_lockup, call;
+                push_transaction(tx),
+                register_timelock_script(redeem_script),
+                release_lock(utxos),
+                set_locked_coin_limit(limit),
+                index_locked_utxo(utxos),
+                set_min_lock_duration(duration),
+                set_lock_duration_bonus(tiers),
+                set_lock_bonus_budget(budget),
+            )
+        }
+        Call::XContracts(call) => parse_module_call!(XContracts => xcontracts, call;
+            update_schedule(schedule),
+            put_code(gas_limit, code),
+            call(dest, value, gas_limit, data),
+            instantiate(endowment, gas_limit, code_hash, data),
+            claim_surcharge(_dest, aux_sender),
+            set_gas_price(price),
+            set_println(state),
+            convert_to_xrc20(token, value, gas_limit),
+            convert_to_asset(to, value),
+            set_token_xrc20(token, xrc20_addr, selectors),
+            set_xrc20_selector(token, selectors),
+            remove_token_xrc20(token),
+            force_issue_xrc20(token, issues, gas_limit),
+        ),
+        Call::XBridgeOfETH(call) => parse_module_call!(XBridgeOfETH => xethereum, call;
+            push_header(header),
+            push_receipt(block_hash, tx_index, proof),
+            set_genesis_header(header, total_difficulty),
+            set_confirmation_number(confirmation_number),
+            set_merge_block_number(number),
+            set_lock_contract(contract),
+            set_relayers(relayers, threshold),
+            set_token_mapping(erc20, token, decimals),
+        ),
+        #[allow(unreachable_patterns)]
+        _ => json!({ "module": Value::Null, "method": Value::Null, "args": [] }),
+    };
+    result["encoded"] = json!(format!("0x{:}", call.encode().to_hex::<String>()));
+    result
+}
+
 pub fn parse_trustee_session_info(
     chain: Chain,
     number: u32,
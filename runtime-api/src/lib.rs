@@ -58,12 +58,19 @@ pub mod xspot_api {
 
 pub mod xfee_api {
     use super::*;
+    use xassets::Token;
 
     decl_runtime_apis! {
         pub trait XFeeApi {
             fn transaction_fee(call: Vec<u8>, encoded_len: u64) -> Option<u64>;
 
             fn fee_weight_map() -> BTreeMap<Vec<u8>, u64>;
+
+            /// (payer, fee power, fee in PCX, fee in the fee token)
+            fn estimate_fee(extrinsic: Vec<u8>) -> Result<(AccountIdForApi, u64, Balance, Option<(Token, Balance)>), Vec<u8>>;
+
+            /// (dispatch result, encoded events)
+            fn dry_run(extrinsic: Vec<u8>) -> (Result<(), Vec<u8>>, Vec<Vec<u8>>);
         }
     }
 }
@@ -2,7 +2,7 @@
 use rstd::collections::btree_map::BTreeMap;
 use rstd::prelude::Vec;
 
-use xr_primitives::XString;
+use xr_primitives::{Token, XString};
 
 use xfee_manager::CallSwitcher;
 
@@ -11,6 +11,8 @@ use xbitcoin::lockup::Call as XBitcoinLockupCall;
 use xbitcoin::Call as XBitcoinCall;
 use xbridge_features::Call as XBridgeFeaturesCall;
 use xcontracts::Call as XContractsCall;
+use xethereum::Call as XEthereumCall;
+use xfee_manager::Call as XFeeManagerCall;
 use xfisher::Call as XFisherCall;
 use xmultisig::Call as XMultiSigCall;
 use xprocess::Call as XAssetsProcessCall;
@@ -19,7 +21,7 @@ use xspot::Call as XSpotCall;
 use xstaking::Call as XStakingCall;
 use xtokens::Call as XTokensCall;
 
-use crate::Call;
+use crate::{Call, XFeeManager, XSpot};
 
 pub trait CheckFee {
     fn check_fee(
@@ -27,6 +29,9 @@ pub trait CheckFee {
         switcher: BTreeMap<CallSwitcher, bool>,
         method_weight_map: BTreeMap<XString, u64>,
     ) -> Option<u64>;
+
+    /// Return the method name in the form of `MethodCallWeight` keys.
+    fn method_name(&self) -> Option<XString>;
 }
 
 impl CheckFee for Call {
@@ -34,50 +39,132 @@ impl CheckFee for Call {
     /// total_fee = base_fee * fee_power + byte_fee * bytes
     ///
     /// fee_power = power_per_call
+    ///
+    /// the total_fee is then scaled by the fee multiplier in `xfee_manager::transaction_fee`,
+    /// which is adjusted each block by the block fullness in xexecutive.
     fn check_fee(
         &self,
         switcher: BTreeMap<CallSwitcher, bool>,
         method_weight_map: BTreeMap<XString, u64>,
     ) -> Option<u64> {
+        let get_switcher = |call_switcher: CallSwitcher| -> bool {
+            switcher.get(&call_switcher).map(|b| *b).unwrap_or(false)
+        };
+
         // MultiSigCall is on the top priority and can't be forbidden.
         if let Call::XMultiSig(call) = self {
             match call {
-                XMultiSigCall::execute(..) => return Some(50),
+                // the proposal is executed by the multisig addr, thus its weight is charged here,
+                // and it must pass the switchers as if it was called directly.
+                XMultiSigCall::execute(_, proposal) => {
+                    if call_forbidden(proposal, &get_switcher) {
+                        return None;
+                    }
+                    return Some(50 + call_weight(proposal, &method_weight_map).unwrap_or(0));
+                }
                 XMultiSigCall::confirm(..) => return Some(25),
+                XMultiSigCall::veto(..) => return Some(25),
                 XMultiSigCall::remove_multi_sig_for(..) => return Some(1000),
                 _ => (),
             }
         }
 
-        let get_switcher = |call_switcher: CallSwitcher| -> bool {
-            switcher.get(&call_switcher).map(|b| *b).unwrap_or(false)
-        };
-
-        // Check if a certain emergency switch is on.
-        if get_switcher(CallSwitcher::Global) {
+        if call_forbidden(&self, &get_switcher) {
             return None;
-        };
+        }
+        call_weight(&self, &method_weight_map)
+    }
 
-        match self {
-            Call::XSpot(..) if get_switcher(CallSwitcher::Spot) => {
-                return None;
-            }
-            Call::XBridgeOfBTC(..) if get_switcher(CallSwitcher::XBTC) => {
-                return None;
-            }
-            Call::XBridgeOfBTCLockup(..) if get_switcher(CallSwitcher::XBTCLockup) => {
-                return None;
-            }
-            Call::XBridgeOfSDOT(..) if get_switcher(CallSwitcher::SDOT) => {
-                return None;
-            }
-            Call::XContracts(..) if get_switcher(CallSwitcher::XContracts) => {
-                return None;
-            }
-            _ => (),
+    fn method_name(&self) -> Option<XString> {
+        call_method_name(&self)
+    }
+}
+
+/// The weight of a call, scaled by the number of the transfers for a batch transfer.
+fn call_weight(call: &Call, method_weight_map: &BTreeMap<XString, u64>) -> Option<u64> {
+    let weight = call_weight_func(call, method_weight_map)?;
+    match call {
+        Call::XAssets(XAssetsCall::transfer_batch(_, dests, _)) => {
+            weight.checked_mul(dests.len() as u64)
+        }
+        _ => Some(weight),
+    }
+}
+
+/// Check the switchers of a proposal dispatched by a multisig addr.
+pub fn proposal_forbidden(call: &Call) -> bool {
+    call_forbidden(call, &|switch| XFeeManager::get_switcher(switch))
+}
+
+/// Check the emergency switchers of all the calls and of the module of the call.
+fn call_forbidden(call: &Call, get_switcher: &dyn Fn(CallSwitcher) -> bool) -> bool {
+    // the switchers can always be modified by the council, or a closed global switcher could
+    // never be reopened.
+    match call {
+        Call::XFeeManager(XFeeManagerCall::modify_switcher(..))
+        | Call::XFeeManager(XFeeManagerCall::modify_switcher_until(..))
+        | Call::XFeeManager(XFeeManagerCall::set_switcher(..)) => return false,
+        _ => (),
+    }
+
+    if get_switcher(CallSwitcher::Global) {
+        return true;
+    };
+
+    let module_switched = match call {
+        Call::XSpot(..) => get_switcher(CallSwitcher::Spot),
+        Call::XBridgeOfBTC(..) => get_switcher(CallSwitcher::XBTC),
+        Call::XBridgeOfBTCLockup(..) => get_switcher(CallSwitcher::XBTCLockup),
+        Call::XBridgeOfSDOT(..) => get_switcher(CallSwitcher::SDOT),
+        Call::XContracts(..) => get_switcher(CallSwitcher::XContracts),
+        Call::XBridgeOfETH(..) => get_switcher(CallSwitcher::XETH),
+        _ => false,
+    };
+
+    module_switched || call_switched(call, get_switcher)
+}
+
+/// Check the switchers of a certain method, trading pair or asset of the call.
+pub fn call_switched(call: &Call, get_switcher: &dyn Fn(CallSwitcher) -> bool) -> bool {
+    if let Some(method) = call_method_name(call) {
+        if get_switcher(CallSwitcher::Method(method)) {
+            return true;
         }
-        call_weight_func(&self, &method_weight_map)
     }
+
+    if let Call::XSpot(XSpotCall::put_order(pair_index, ..)) = call {
+        if get_switcher(CallSwitcher::SpotPair(*pair_index)) {
+            return true;
+        }
+    }
+
+    call_tokens(call)
+        .into_iter()
+        .any(|token| get_switcher(CallSwitcher::Token(token)))
+}
+
+/// The assets a call operates on.
+fn call_tokens(call: &Call) -> Vec<Token> {
+    let token = match call {
+        Call::XAssets(XAssetsCall::transfer(_, token, ..)) => token,
+        Call::XAssets(XAssetsCall::transfer_batch(token, ..)) => token,
+        Call::XAssets(XAssetsCall::approve(_, token, ..)) => token,
+        Call::XAssets(XAssetsCall::transfer_from(_, _, token, ..)) => token,
+        Call::XAssets(XAssetsCall::vested_transfer(_, token, ..)) => token,
+        Call::XAssets(XAssetsCall::vest(token)) => token,
+        Call::XAssets(XAssetsCall::vest_other(_, token)) => token,
+        Call::XAssetsProcess(XAssetsProcessCall::withdraw(token, ..)) => token,
+        Call::XTokens(XTokensCall::claim(token)) => token,
+        Call::XContracts(XContractsCall::convert_to_xrc20(token, ..)) => token,
+        // both the base and the quote of the trading pair.
+        Call::XSpot(XSpotCall::put_order(pair_index, ..)) => {
+            return XSpot::trading_pair_of(*pair_index)
+                .map(|pair| vec![pair.base(), pair.quote()])
+                .unwrap_or_default();
+        }
+        _ => return Vec::new(),
+    };
+    vec![token.clone()]
 }
 
 #[macro_export]
@@ -116,6 +203,21 @@ macro_rules! match_method_call_func {
             }
         }
 
+        #[inline]
+        pub fn call_method_name(func_call: &Call) -> Option<XString> {
+            match func_call {
+                $(
+                    Call::$module(call) => match call {
+                        $(
+                            $module_call::$method(..) => Some(stringify!($module $method).as_bytes().to_vec()),
+                        )+
+                        _ => None,
+                    },
+                )+
+                _ => None,
+            }
+        }
+
         #[allow(unused)]
         pub fn call_weight_map(method_weight_map: &BTreeMap<XString, u64>) -> BTreeMap<Vec<u8>, u64> {
             let mut m = BTreeMap::new();
@@ -136,6 +238,12 @@ match_method_call_func! {
 
     XAssets, XAssetsCall => (
         transfer : 1,
+        transfer_batch : 1,
+        approve : 1,
+        transfer_from : 1,
+        vested_transfer : 2,
+        vest : 1,
+        vest_other : 1,
     );
 
     XAssetsProcess, XAssetsProcessCall => (
@@ -152,6 +260,7 @@ match_method_call_func! {
 
     XBridgeOfBTCLockup, XBitcoinLockupCall => (
         push_transaction : 50,
+        register_timelock_script : 10,
     );
 
     XStaking, XStakingCall => (
@@ -175,10 +284,27 @@ match_method_call_func! {
 
     XBridgeOfSDOT, SdotCall => (
         claim : 2,
+        claim_typed : 2,
+    );
+
+    XBridgeOfETH, XEthereumCall => (
+        push_header : 10,
+        push_receipt : 50,
+    );
+
+    XMultiSig, XMultiSigCall => (
+        deploy : 1000,
     );
 
     XBridgeFeatures, XBridgeFeaturesCall => (
         setup_bitcoin_trustee : 1000,
+        setup_ethereum_trustee : 1000,
+    );
+
+    XFeeManager, XFeeManagerCall => (
+        sponsor : 5,
+        accept_sponsor : 2,
+        revoke_sponsor : 2,
     );
 
     XFisher, XFisherCall => (
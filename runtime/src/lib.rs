@@ -81,8 +81,8 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
     spec_name: create_runtime_str!("chainx"),
     impl_name: create_runtime_str!("chainx-net"),
     authoring_version: 1,
-    spec_version: 7,
-    impl_version: 7,
+    spec_version: 8,
+    impl_version: 8,
     apis: RUNTIME_API_VERSIONS,
 };
 
@@ -156,6 +156,14 @@ impl xaccounts::Trait for Runtime {
 // fees
 impl xfee_manager::Trait for Runtime {
     type Event = Event;
+    type AssetPrice = SpotAssetPrice;
+}
+
+pub struct SpotAssetPrice;
+impl xfee_manager::AssetPrice<Balance> for SpotAssetPrice {
+    fn aver_asset_price(token: &xassets::Token) -> Option<Balance> {
+        XSpot::aver_asset_price(token)
+    }
 }
 // assets
 impl xassets::Trait for Runtime {
@@ -218,6 +226,12 @@ impl xsdot::Trait for Runtime {
     type Event = Event;
 }
 
+impl xethereum::Trait for Runtime {
+    type AccountExtractor = xbridge_common::extractor::Extractor<AccountId>;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = Event;
+}
+
 impl xbridge_features::Trait for Runtime {
     type TrusteeMultiSig = xbridge_features::SimpleTrusteeMultiSigIdFor<Runtime>;
     type Event = Event;
@@ -228,9 +242,28 @@ impl xmultisig::Trait for Runtime {
     type GenesisMultiSig = xmultisig::ChainXGenesisMultisig<Runtime>;
     type Proposal = Call;
     type TrusteeCall = trustee::TrusteeCall;
+    type ProposalHook = MultiSigProposalHook;
     type Event = Event;
 }
 
+/// A proposal dispatched by a multisig addr must pass the switchers as if it was called
+/// directly, including a queued one. Only the checks of the top-level call decide the fee
+/// charged for a failed extrinsic, the flag set by the proposal is cleared.
+pub struct MultiSigProposalHook;
+impl xmultisig::ProposalHook<Call> for MultiSigProposalHook {
+    fn before_dispatch(proposal: &Call) -> result::Result<(), &'static str> {
+        if fee::proposal_forbidden(proposal) {
+            return Err("the proposal is forbidden by the call switchers");
+        }
+        XFeeManager::clear_call_failed_early();
+        Ok(())
+    }
+
+    fn after_dispatch() {
+        XFeeManager::clear_call_failed_early();
+    }
+}
+
 impl finality_tracker::Trait for Runtime {
     type OnFinalizationStalled = xgrandpa::SyncedAuthorities<Runtime>;
 }
@@ -374,6 +407,8 @@ construct_runtime!(
         XBridgeOfBTCLockup: xbitcoin_lockup::{Module, Call, Storage, Event<T>},
 
         XContracts: xcontracts,
+
+        XBridgeOfETH: xethereum::{Module, Call, Storage, Event<T>},
     }
 );
 
@@ -587,6 +622,32 @@ impl_runtime_apis! {
             let method_call_weight = XFeeManager::method_call_weight();
             fee::call_weight_map(&method_call_weight)
         }
+
+        fn estimate_fee(extrinsic: Vec<u8>) -> result::Result<(AccountId, u64, Balance, Option<(xassets::Token, Balance)>), Vec<u8>> {
+            let uxt: UncheckedExtrinsic = if let Some(uxt) = Decode::decode(&mut extrinsic.as_slice()) {
+                uxt
+            } else {
+                return Err(b"decode extrinsic error".to_vec());
+            };
+            Executive::estimate_fee(uxt).map_err(|e| e.as_bytes().to_vec())
+        }
+
+        fn dry_run(extrinsic: Vec<u8>) -> (result::Result<(), Vec<u8>>, Vec<Vec<u8>>) {
+            let uxt: UncheckedExtrinsic = if let Some(uxt) = Decode::decode(&mut extrinsic.as_slice()) {
+                uxt
+            } else {
+                return (Err(b"decode extrinsic error".to_vec()), Vec::new());
+            };
+            // the changes of runtime api calls are never committed.
+            let events_before = System::events().len();
+            let result = Executive::dry_run(uxt).map_err(|e| e.as_bytes().to_vec());
+            let events = System::events()
+                .into_iter()
+                .skip(events_before)
+                .map(|record| record.event.encode())
+                .collect();
+            (result, events)
+        }
     }
 
     impl runtime_api::xsession_api::XSessionApi<Block> for Runtime {
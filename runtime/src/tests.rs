@@ -2,6 +2,15 @@
 
 use super::*;
 use hex_literal::hex;
+use parity_codec::Compact;
+use runtime_io::{with_externalities, TestExternalities};
+use runtime_primitives::generic::Era;
+use runtime_primitives::traits::Header as HeaderT;
+use runtime_primitives::transaction_validity::TransactionValidity;
+use runtime_primitives::StorageOverlay;
+use substrate_primitives::{ed25519, Blake2Hasher, Pair};
+use xassets::ChainT;
+
 #[test]
 fn check_header_with_same() {
     use substrate_primitives::ed25519::Public;
@@ -45,3 +54,320 @@ fn check_header_with_same() {
     let r = HeaderChecker::check_header(&pubkey, &header1, &header2);
     assert_eq!(r, Ok((6410, 6735)));
 }
+
+type FeeTestExecutive =
+    xexecutive::Executive<Runtime, Block, system::ChainContext<Runtime>, XFeeManager, ()>;
+
+fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
+    system::GenesisConfig::<Runtime>::default()
+        .build_storage()
+        .unwrap()
+        .0
+        .into()
+}
+
+fn initialize_block(number: BlockNumber) {
+    FeeTestExecutive::initialize_block(&Header::new(
+        number,
+        Default::default(),
+        Default::default(),
+        [69u8; 32].into(),
+        Default::default(),
+    ));
+}
+
+#[test]
+fn finalize_block_updates_fee_multiplier() {
+    with_externalities(&mut new_test_ext(), || {
+        assert_eq!(XFeeManager::fee_multiplier(), 10_000);
+
+        // the block is over the target weight, the fee goes up by 1/8
+        initialize_block(1);
+        XFeeManager::note_block_fee_weight(1_000_000);
+        FeeTestExecutive::finalize_block();
+        assert_eq!(XFeeManager::fee_multiplier(), 11_250);
+        assert_eq!(XFeeManager::block_fee_weight(), 0);
+
+        // an empty block, the fee goes down but not below the base
+        initialize_block(2);
+        FeeTestExecutive::finalize_block();
+        assert_eq!(XFeeManager::fee_multiplier(), 10_000);
+    });
+}
+
+fn signed_extrinsic(
+    pair: &ed25519::Pair,
+    index: Index,
+    function: Call,
+    era: Era,
+    birth_hash: Hash,
+    acc: Acceleration,
+    fee_token: Option<xassets::Token>,
+) -> UncheckedExtrinsic {
+    let payload = (
+        Compact::<Index>::from(index),
+        function.clone(),
+        era,
+        birth_hash,
+        Compact::<Acceleration>::from(acc),
+    );
+    let signed = indices::address::Address::Id(pair.public());
+    match fee_token {
+        Some(token) => {
+            let signature = pair.sign(&(&payload, &token).encode());
+            UncheckedExtrinsic::new_signed_with_fee_token(
+                index, function, signed, signature, era, acc, token,
+            )
+        }
+        None => {
+            let signature = pair.sign(&payload.encode());
+            UncheckedExtrinsic::new_signed(index, function, signed, signature, era, acc)
+        }
+    }
+}
+
+fn revoke_sponsor(pair: &ed25519::Pair, era: Era, birth_hash: Hash) -> UncheckedExtrinsic {
+    let function = Call::XFeeManager(xfee_manager::Call::revoke_sponsor(Default::default()));
+    signed_extrinsic(pair, 0, function, era, birth_hash, 1, None)
+}
+
+#[test]
+fn mortal_transaction_longevity_ends_at_death() {
+    with_externalities(&mut new_test_ext(), || {
+        let pair = ed25519::Pair::generate();
+        let birth_hash = Hash::repeat_byte(1);
+        system::BlockHash::<Runtime>::insert(10, birth_hash);
+
+        // born at block 10 and dies at block 42
+        let era = Era::mortal(32, 10);
+        assert_eq!(era.death(10), 42);
+        for current in 10..42 {
+            System::set_block_number(current);
+            match FeeTestExecutive::validate_transaction(revoke_sponsor(&pair, era, birth_hash)) {
+                TransactionValidity::Valid { longevity, .. } => {
+                    assert_eq!(longevity, era.death(current) - current)
+                }
+                _ => panic!("the transaction should be valid before its death"),
+            }
+        }
+
+        // the birth block is out of the era after the death
+        System::set_block_number(42);
+        match FeeTestExecutive::validate_transaction(revoke_sponsor(&pair, era, birth_hash)) {
+            TransactionValidity::Invalid(_) => {}
+            _ => panic!("the transaction should be rejected after its death"),
+        }
+    });
+}
+
+fn sponsor(who: &ed25519::Pair) -> Call {
+    Call::XFeeManager(xfee_manager::Call::sponsor(who.public(), 1_000_000, vec![]))
+}
+
+/// Register PCX and BTC, 1 BTC is worth 1 PCX.
+fn setup_assets() {
+    xfee_manager::TransactionBaseFee::<Runtime>::put(10);
+    xfee_manager::TransactionByteFee::<Runtime>::put(1);
+
+    let pcx = xassets::Asset::new(
+        XAssets::TOKEN.to_vec(),
+        b"PolkadotChainX".to_vec(),
+        xassets::Chain::ChainX,
+        8,
+        b"PCX onchain token".to_vec(),
+    )
+    .unwrap();
+    XAssets::bootstrap_register_asset(pcx, true, false).unwrap();
+    let btc = xassets::Asset::new(
+        b"BTC".to_vec(),
+        b"X-BTC".to_vec(),
+        xassets::Chain::Bitcoin,
+        8,
+        b"ChainX's cross-chain Bitcoin".to_vec(),
+    )
+    .unwrap();
+    XAssets::bootstrap_register_asset(btc, true, true).unwrap();
+    XSpot::add_trading_pair(
+        xspot::CurrencyPair::new(b"BTC".to_vec(), XAssets::TOKEN.to_vec()),
+        4,
+        2,
+        10_000,
+        true,
+    )
+    .unwrap();
+}
+
+/// Apply `xt` and check the payer is charged as estimated, return the payer and the fee token.
+fn apply_as_estimated(xt: UncheckedExtrinsic) -> (AccountId, xassets::Token) {
+    let (payer, _, fee, token_fee) = FeeTestExecutive::estimate_fee(xt.clone()).unwrap();
+    let (token, fee) = token_fee.unwrap_or((XAssets::TOKEN.to_vec(), fee));
+    let before = XAssets::free_balance_of(&payer, &token);
+    assert!(FeeTestExecutive::apply_extrinsic(xt).is_ok());
+    assert_eq!(before - XAssets::free_balance_of(&payer, &token), fee);
+    (payer, token)
+}
+
+#[test]
+fn estimate_fee_equals_charged_fee() {
+    with_externalities(&mut new_test_ext(), || {
+        setup_assets();
+        initialize_block(1);
+        let birth_hash = System::block_hash(0);
+        let xt = |pair: &ed25519::Pair, index, function, acc, fee_token| {
+            signed_extrinsic(
+                pair,
+                index,
+                function,
+                Era::immortal(),
+                birth_hash,
+                acc,
+                fee_token,
+            )
+        };
+
+        let sender = ed25519::Pair::generate();
+        let sponsor_pair = ed25519::Pair::generate();
+        XAssets::pcx_issue(&sender.public(), 1_000_000).unwrap();
+        XAssets::pcx_issue(&sponsor_pair.public(), 1_000_000).unwrap();
+        XAssets::issue(&b"BTC".to_vec(), &sender.public(), 1_000_000).unwrap();
+
+        // accelerated
+        let payer = apply_as_estimated(xt(&sender, 0, sponsor(&sponsor_pair), 3, None));
+        assert_eq!(payer, (sender.public(), XAssets::TOKEN.to_vec()));
+
+        // paid in BTC
+        let btc = Some(b"BTC".to_vec());
+        let payer = apply_as_estimated(xt(&sender, 1, sponsor(&sponsor_pair), 1, btc));
+        assert_eq!(payer, (sender.public(), b"BTC".to_vec()));
+
+        // paid by the sponsor
+        apply_as_estimated(xt(&sponsor_pair, 0, sponsor(&sender), 1, None));
+        let accept = Call::XFeeManager(xfee_manager::Call::accept_sponsor(sponsor_pair.public()));
+        apply_as_estimated(xt(&sender, 2, accept, 1, None));
+        let payer = apply_as_estimated(xt(&sender, 3, sponsor(&sponsor_pair), 2, None));
+        assert_eq!(payer, (sponsor_pair.public(), XAssets::TOKEN.to_vec()));
+    });
+}
+
+#[test]
+fn dry_run_leaves_storage_unchanged() {
+    let sender = ed25519::Pair::generate();
+    let other = ed25519::Pair::generate();
+    let mut ext = new_test_ext();
+    let xt = with_externalities(&mut ext, || {
+        setup_assets();
+        initialize_block(1);
+        XAssets::pcx_issue(&sender.public(), 1_000_000).unwrap();
+        let birth_hash = System::block_hash(0);
+        signed_extrinsic(
+            &sender,
+            0,
+            sponsor(&other),
+            Era::immortal(),
+            birth_hash,
+            1,
+            None,
+        )
+    });
+    let storage: StorageOverlay = ext.into();
+
+    // the client runs the runtime api on an overlay of the state which is discarded afterwards.
+    let events = with_externalities(&mut TestExternalities::new(storage.clone()), || {
+        assert_eq!(FeeTestExecutive::dry_run(xt.clone()), Ok(()));
+        assert_eq!(System::account_nonce(&sender.public()), 1);
+        System::events()
+    });
+
+    with_externalities(&mut TestExternalities::new(storage), || {
+        assert_eq!(System::account_nonce(&sender.public()), 0);
+        assert_eq!(XAssets::pcx_free_balance(&sender.public()), 1_000_000);
+        assert!(XFeeManager::sponsorship_of(&(sender.public(), other.public())).is_none());
+
+        // applying it for real does the same as the dry run.
+        assert!(FeeTestExecutive::apply_extrinsic(xt).is_ok());
+        assert_eq!(System::events(), events);
+    });
+}
+
+#[test]
+fn token_switcher_covers_all_uses_of_the_token() {
+    use crate::fee::call_switched;
+    use xfee_manager::CallSwitcher;
+
+    with_externalities(&mut new_test_ext(), || {
+        setup_assets();
+        let btc = b"BTC".to_vec();
+        let mut switcher = BTreeMap::new();
+        switcher.insert(CallSwitcher::Token(btc.clone()), true);
+        xfee_manager::Switcher::<Runtime>::put(switcher.clone());
+        let get_switcher = |s: CallSwitcher| switcher.get(&s).cloned().unwrap_or(false);
+
+        // the trading pair BTC/PCX
+        let put_order = Call::XSpot(xspot::Call::put_order(
+            0,
+            xspot::OrderType::Limit,
+            xspot::Side::Buy,
+            1,
+            10_000,
+        ));
+        assert!(call_switched(&put_order, &get_switcher));
+
+        let convert = Call::XContracts(xcontracts::Call::convert_to_xrc20(btc.clone(), 1, 1));
+        assert!(call_switched(&convert, &get_switcher));
+
+        // the proposal of a multisig address
+        let transfer = Call::XAssets(xassets::Call::transfer(
+            indices::address::Address::Id(Default::default()),
+            btc.clone(),
+            1,
+            b"memo".to_vec(),
+        ));
+        assert!(call_switched(&transfer, &get_switcher));
+        let execute = Call::XMultiSig(xmultisig::Call::execute(
+            Default::default(),
+            Box::new(transfer),
+        ));
+        assert_eq!(execute.check_fee(switcher.clone(), BTreeMap::new()), None);
+
+        // paying the fee
+        assert_eq!(
+            XFeeManager::fee_in_token(&btc, 100),
+            Err("this token is forbidden to pay the transaction fee")
+        );
+    });
+}
+
+#[test]
+fn multisig_proposal_checks_the_switchers() {
+    use xfee_manager::CallSwitcher;
+    use xmultisig::ProposalHook;
+
+    with_externalities(&mut new_test_ext(), || {
+        let mut switcher = BTreeMap::new();
+        switcher.insert(CallSwitcher::Global, true);
+        xfee_manager::Switcher::<Runtime>::put(switcher);
+
+        let transfer = Call::XAssets(xassets::Call::transfer(
+            indices::address::Address::Id(Default::default()),
+            b"PCX".to_vec(),
+            1,
+            b"memo".to_vec(),
+        ));
+        assert_eq!(
+            MultiSigProposalHook::before_dispatch(&transfer),
+            Err("the proposal is forbidden by the call switchers")
+        );
+
+        // the council can always reopen the switchers
+        let reopen = Call::XFeeManager(xfee_manager::Call::modify_switcher(
+            CallSwitcher::Global,
+            false,
+        ));
+        assert_eq!(MultiSigProposalHook::before_dispatch(&reopen), Ok(()));
+
+        // the checks of the proposal don't decide the fee of the outer call
+        assert!(XFeeManager::check_call::<()>(Err("bad call")).is_err());
+        MultiSigProposalHook::after_dispatch();
+        assert!(!XFeeManager::take_call_failed_early());
+    });
+}
@@ -6,6 +6,7 @@ use system;
 use super::{AccountId, Call};
 use xbitcoin::Call as XBitcoinCall;
 use xbridge_features::Call as XBridgeFeaturesCall;
+use xmultisig::Call as XMultiSigCall;
 use xmultisig::LimitedCall;
 use xsupport::{error, info};
 
@@ -32,6 +33,10 @@ impl LimitedCall<AccountId> for TrusteeCall {
                 XBridgeFeaturesCall::transition_trustee_session(..) => true,
                 _ => false,
             },
+            Call::XMultiSig(call) => match call {
+                XMultiSigCall::set_proposal_policy(..) => true,
+                _ => false,
+            },
             _ => false,
         }
     }
@@ -8,6 +8,7 @@ use xfee_manager::CallSwitcher;
 use xassets::Call as XAssetsCall;
 use xcontracts::Call as XContractsCall;
 
+use crate::fee::call_switched;
 use crate::Call;
 
 pub trait XContractsCheckFee {
@@ -40,6 +41,10 @@ impl XContractsCheckFee for Call {
             _ => (),
         }
 
+        if call_switched(&self, &get_switcher) {
+            return None;
+        }
+
         match self {
             Call::XAssets(call) => match call {
                 XAssetsCall::transfer(..) => {
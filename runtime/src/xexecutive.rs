@@ -35,9 +35,13 @@ use support::Dispatchable;
 use system::extrinsics_root;
 use xfee_manager::MakePayment;
 use xr_primitives::traits::Accelerable;
+use xr_primitives::Token;
 
 mod internal {
     pub const MAX_TRANSACTIONS_SIZE: u32 = 4 * 1024 * 1024;
+    /// the fee multiplier goes up when a block exceeds one of the targets, and goes down otherwise.
+    pub const TARGET_TRANSACTIONS_SIZE: u32 = MAX_TRANSACTIONS_SIZE / 4;
+    pub const TARGET_TRANSACTIONS_WEIGHT: u64 = 200_000;
 
     pub enum ApplyError {
         BadSignature(&'static str),
@@ -68,7 +72,7 @@ impl<
     System: system::Trait + xfee_manager::Trait,
     Block: traits::Block<Header=System::Header, Hash=System::Hash>,
     Context: Default,
-    Payment: MakePayment<System::AccountId>,
+    Payment: MakePayment<System::AccountId, Balance=System::Balance>,
     AllModules: OnInitialize<System::BlockNumber> + OnFinalize<System::BlockNumber> + OffchainWorker<System::BlockNumber>,
 > ExecuteBlock<Block> for Executive<System, Block, Context, Payment, AllModules> where
     Block::Extrinsic: Checkable<Context> + Codec,
@@ -85,7 +89,7 @@ impl<
     System: system::Trait + xfee_manager::Trait,
     Block: traits::Block<Header=System::Header, Hash=System::Hash>,
     Context: Default,
-    Payment: MakePayment<System::AccountId>,
+    Payment: MakePayment<System::AccountId, Balance=System::Balance>,
     AllModules: OnInitialize<System::BlockNumber> + OnFinalize<System::BlockNumber> + OffchainWorker<System::BlockNumber>,
 > Executive<System, Block, Context, Payment, AllModules> where
     Block::Extrinsic: Checkable<Context> + Codec,
@@ -139,6 +143,7 @@ impl<
         extrinsics.into_iter().for_each(Self::apply_extrinsic_no_note);
 
         // post-extrinsics book-keeping.
+        Self::update_fee_multiplier();
         <system::Module<System>>::note_finished_extrinsics();
         <AllModules as OnFinalize<System::BlockNumber>>::on_finalize(block_number);
     }
@@ -146,6 +151,7 @@ impl<
     /// Finalize the block - it is up the caller to ensure that all header fields are valid
     /// except state-root.
     pub fn finalize_block() -> System::Header {
+        Self::update_fee_multiplier();
         <system::Module<System>>::note_finished_extrinsics();
         <AllModules as OnFinalize<System::BlockNumber>>::on_finalize(<system::Module<System>>::block_number());
 
@@ -154,6 +160,15 @@ impl<
         <system::Module<System>>::finalize()
     }
 
+    /// Adjust the fee multiplier for next block by the fullness of current block.
+    fn update_fee_multiplier() {
+        <xfee_manager::Module<System>>::update_fee_multiplier(
+            internal::TARGET_TRANSACTIONS_WEIGHT,
+            internal::TARGET_TRANSACTIONS_SIZE as u64,
+            <system::Module<System>>::all_extrinsics_weight() as u64,
+        );
+    }
+
     /// Apply extrinsic outside of the block execution function.
     /// This doesn't attempt to validate anything regarding the block, but it builds a list of uxt
     /// hashes.
@@ -172,6 +187,44 @@ impl<
         }
     }
 
+    /// Apply an extrinsic against current state without noting it, return the reason if it
+    /// fails. It's up to the caller to discard the changes.
+    pub fn dry_run(uxt: Block::Extrinsic) -> result::Result<(), &'static str> {
+        let l = uxt.encode().len();
+        match Self::apply_extrinsic_with_len(uxt, l, None) {
+            Ok(internal::ApplyOutcome::Success) => Ok(()),
+            Ok(internal::ApplyOutcome::Fail(e)) => Err(e),
+            Err(internal::ApplyError::CantPay) => Err("not enough funds for transaction fee"),
+            Err(internal::ApplyError::BadSignature(e)) => Err(e),
+            Err(internal::ApplyError::Stale) => Err("the transaction is outdated"),
+            Err(internal::ApplyError::Future) => Err("the transaction will be valid in the future"),
+            Err(internal::ApplyError::FullBlock) => Err("block is full"),
+            Err(internal::ApplyError::NotAllow) => Err("this call is not allowed"),
+        }
+    }
+
+    /// Estimate the fee of an extrinsic exactly as it would be charged, return the payer, the
+    /// fee power, the fee in PCX and the fee in the fee token if it's paid in other tokens.
+    pub fn estimate_fee(uxt: Block::Extrinsic) -> result::Result<(System::AccountId, u64, System::Balance, Option<(Token, System::Balance)>), &'static str> {
+        let encoded_len = uxt.encode().len();
+        let xt = uxt.check(&Default::default())?;
+
+        let acc = xt.acceleration().ok_or("unsigned extrinsic has no fee")?;
+        if acc.is_zero() {
+            return Err("acceleration can't be zero");
+        }
+        let fee_token = xt.fee_token();
+        let (f, s) = xt.deconstruct();
+        let sender = s.ok_or("unsigned extrinsic has no fee")?;
+
+        let switcher = <xfee_manager::Module<System>>::switcher();
+        let method_call_weight = <xfee_manager::Module<System>>::method_call_weight();
+        let weight = f.check_fee(switcher, method_call_weight).ok_or("this call is not allowed")?;
+
+        let (payer, fee, token_fee) = <xfee_manager::Module<System>>::estimate_fee(&sender, f.method_name().as_ref(), encoded_len, weight, acc.into(), fee_token.as_ref())?;
+        Ok((payer, weight, fee, token_fee))
+    }
+
     /// Apply an extrinsic inside the block execution function.
     fn apply_extrinsic_no_note(uxt: Block::Extrinsic) {
         let l = uxt.encode().len();
@@ -213,6 +266,7 @@ impl<
         }
 
         let acc = xt.acceleration();
+        let fee_token = xt.fee_token();
         // decode parameters
         let (f, s) = xt.deconstruct();
 
@@ -224,14 +278,22 @@ impl<
             }
         }
 
+        // (sender, payer, fee token, weight, fee paid)
+        let mut payment = None;
         if signed_extrinsic {
             // fee check
             let acc = acc.unwrap();
             let switcher = <xfee_manager::Module<System>>::switcher();
             let method_call_weight = <xfee_manager::Module<System>>::method_call_weight();
             if let Some(weight) = f.check_fee(switcher, method_call_weight) {
-                // pay any fees.
-                Payment::make_payment(&s.clone().unwrap(), encoded_len, weight, acc.into()).map_err(|_| internal::ApplyError::CantPay)?;
+                // pay any fees, by the sponsor if the sender is sponsored.
+                let sender = s.clone().unwrap();
+                let (payer, fee_token) = match <xfee_manager::Module<System>>::sponsor_for(&sender, f.method_name().as_ref(), encoded_len, weight, acc.into()) {
+                    Some((sponsor, _)) => (sponsor, None),
+                    None => (sender.clone(), fee_token),
+                };
+                let paid = Payment::make_payment(&payer, encoded_len, weight, acc.into(), fee_token.as_ref()).map_err(|_| internal::ApplyError::CantPay)?;
+                payment = Some((sender, payer, fee_token, weight, paid));
 
                 // AUDIT: Under no circumstances may this function panic from here onwards.
 
@@ -248,9 +310,26 @@ impl<
         }
 
         // and dispatch
+        <xfee_manager::Module<System>>::clear_call_failed_early();
         let r = f.dispatch(s.into());
+        let failed_early = <xfee_manager::Module<System>>::take_call_failed_early();
         <system::Module<System>>::note_applied_extrinsic(&r, encoded_len as u32);
 
+        if let Some((sender, payer, fee_token, weight, paid)) = payment {
+            // a dispatch failed in the checks before changing any storage is only charged a
+            // part of the weight, refund the rest.
+            let actual_weight = if r.is_err() && failed_early {
+                <xfee_manager::Module<System>>::failed_call_weight(weight)
+            } else {
+                weight
+            };
+            let fee = Payment::refund_payment(&payer, encoded_len, weight, actual_weight, fee_token.as_ref(), paid);
+            if payer != sender {
+                <xfee_manager::Module<System>>::consume_sponsorship(&payer, &sender, fee);
+            }
+            <xfee_manager::Module<System>>::note_block_fee_weight(weight);
+        }
+
         r.map(|_| internal::ApplyOutcome::Success).or_else(|e| match e {
             runtime_primitives::BLOCK_FULL => Err(internal::ApplyError::FullBlock),
             e => Ok(internal::ApplyOutcome::Fail(e))
@@ -315,6 +394,8 @@ impl<
             None => return TransactionValidity::Invalid(ACC_ERROR),
         }
 
+        // the transaction would be dropped from the pool once its era is over.
+        let longevity = xt.longevity();
         let valid = if let (Some(sender), Some(index), Some(acceleration)) = (xt.sender(), xt.index(), xt.acceleration()) {
             // check index
             let expected_index = <system::Module<System>>::account_nonce(sender);
@@ -333,7 +414,7 @@ impl<
                 priority: acceleration.into() as TransactionPriority,
                 requires,
                 provides,
-                longevity: TransactionLongevity::max_value(),
+                longevity: longevity.unwrap_or(TransactionLongevity::max_value()),
                 propagate: true,
             }
         } else {
@@ -345,6 +426,7 @@ impl<
         };
 
         let acc = xt.acceleration().unwrap();
+        let fee_token = xt.fee_token();
         let (f, s) = xt.deconstruct();
 
         if let Some(ref sender) = s {
@@ -359,7 +441,12 @@ impl<
         let switcher = <xfee_manager::Module<System>>::switcher();
         let method_call_weight = <xfee_manager::Module<System>>::method_call_weight();
         if let Some(fee_power) = f.check_fee(switcher, method_call_weight) {
-            if Payment::check_payment(&s.clone().unwrap(), encoded_len, fee_power, acc.into()).is_err() {
+            let sender = s.clone().unwrap();
+            let paid = match <xfee_manager::Module<System>>::sponsor_for(&sender, f.method_name().as_ref(), encoded_len, fee_power, acc.into()) {
+                Some((sponsor, _)) => Payment::check_payment(&sponsor, encoded_len, fee_power, acc.into(), None),
+                None => Payment::check_payment(&sender, encoded_len, fee_power, acc.into(), fee_token.as_ref()),
+            };
+            if paid.is_err() {
                 return TransactionValidity::Invalid(ApplyError::CantPay as i8);
             } else {
                 return valid;
@@ -6,6 +6,7 @@
 use runtime_primitives::traits::{Applyable, MaybeDisplay, Member, SimpleArithmetic};
 
 use crate::traits::Accelerable;
+use crate::Token;
 
 /// Definition of something that the external world might want to say; its
 /// existence implies that it has been checked and is good, particularly with
@@ -16,6 +17,11 @@ pub struct CheckedExtrinsic<AccountId, Index, Call, Acceleration> {
     /// Who this purports to be from and the number of extrinsics have come before
     /// from the same signer, if anyone (note this is not a signature).
     pub signed: Option<(AccountId, Index, Acceleration)>,
+    /// The token used to pay the transaction fee, `None` means PCX.
+    pub fee_token: Option<Token>,
+    /// How many blocks the transaction stays valid for from the block it's checked at, derived
+    /// from the era, if anyone.
+    pub longevity: Option<u64>,
     /// The function that should be called.
     pub function: Call,
 }
@@ -61,4 +67,12 @@ where
     fn acceleration(&self) -> Option<Self::Acceleration> {
         self.signed.as_ref().map(|x| x.2)
     }
+
+    fn fee_token(&self) -> Option<Token> {
+        self.fee_token.clone()
+    }
+
+    fn longevity(&self) -> Option<u64> {
+        self.longevity
+    }
 }
@@ -33,8 +33,12 @@ use runtime_primitives::{
 };
 
 use super::checked_extrinsic::CheckedExtrinsic;
+use crate::Token;
 
 const TRANSACTION_VERSION: u8 = 1;
+/// Signed extrinsic which pays the fee in `fee_token` instead of PCX, the fee token is
+/// encoded right after the signature and is a part of the signed payload.
+const FEE_TOKEN_TRANSACTION_VERSION: u8 = 2;
 
 /// A extrinsic right from the external world. This is unchecked and so
 /// can contain a signature.
@@ -50,6 +54,8 @@ pub struct UncheckedMortalCompactExtrinsic<Address, Index, Call, Signature, Acce
         Era,
         Compact<Acceleration>,
     )>,
+    /// The token used to pay the transaction fee, `None` means PCX.
+    pub fee_token: Option<Token>,
     /// The function that should be called.
     pub function: Call,
 }
@@ -68,6 +74,24 @@ impl<Address, Index, Call, Signature, Acceleration>
     ) -> Self {
         UncheckedMortalCompactExtrinsic {
             signature: Some((signed, signature, index.into(), era, acceleration.into())),
+            fee_token: None,
+            function,
+        }
+    }
+
+    /// New instance of a signed extrinsic which pays the fee in `fee_token`.
+    pub fn new_signed_with_fee_token(
+        index: Index,
+        function: Call,
+        signed: Address,
+        signature: Signature,
+        era: Era,
+        acceleration: Acceleration,
+        fee_token: Token,
+    ) -> Self {
+        UncheckedMortalCompactExtrinsic {
+            signature: Some((signed, signature, index.into(), era, acceleration.into())),
+            fee_token: Some(fee_token),
             function,
         }
     }
@@ -76,6 +100,7 @@ impl<Address, Index, Call, Signature, Acceleration>
     pub fn new_unsigned(function: Call) -> Self {
         UncheckedMortalCompactExtrinsic {
             signature: None,
+            fee_token: None,
             function,
         }
     }
@@ -118,22 +143,31 @@ where
                     .ok_or("transaction birth block ancient")?;
                 let raw_payload = (index, self.function, era, h, acceleration);
                 let signed = context.lookup(signed)?; // if error, would return "invalid account index"
-                if !raw_payload.using_encoded(|payload| {
+                let verify = |payload: &[u8]| {
                     if payload.len() > 256 {
                         signature.verify(&blake2_256(payload)[..], &signed)
                     } else {
                         signature.verify(payload, &signed)
                     }
-                }) {
+                };
+                let verified = match self.fee_token.as_ref() {
+                    Some(token) => (&raw_payload, token).using_encoded(verify),
+                    None => raw_payload.using_encoded(verify),
+                };
+                if !verified {
                     return Err("bad signature in extrinsic");
                 }
                 CheckedExtrinsic {
                     signed: Some((signed, (raw_payload.0).0, (raw_payload.4).0)),
+                    fee_token: self.fee_token,
+                    longevity: Some(era.death(current_u64).saturating_sub(current_u64)),
                     function: raw_payload.1,
                 }
             }
             None => CheckedExtrinsic {
                 signed: None,
+                fee_token: None,
+                longevity: None,
                 function: self.function,
             },
         })
@@ -161,9 +195,11 @@ where
 
         let is_signed = version & 0b1000_0000 != 0;
         let version = version & 0b0111_1111;
-        if version != TRANSACTION_VERSION {
-            return None;
-        }
+        let with_fee_token = match version {
+            TRANSACTION_VERSION => false,
+            FEE_TOKEN_TRANSACTION_VERSION if is_signed => true,
+            _ => return None,
+        };
 
         Some(UncheckedMortalCompactExtrinsic {
             signature: if is_signed {
@@ -171,6 +207,11 @@ where
             } else {
                 None
             },
+            fee_token: if with_fee_token {
+                Some(Decode::decode(input)?)
+            } else {
+                None
+            },
             function: Decode::decode(input)?,
         })
     }
@@ -189,12 +230,17 @@ where
     fn encode(&self) -> Vec<u8> {
         super::encode_with_vec_prefix::<Self, _>(|v| {
             // 1 byte version id.
-            match self.signature.as_ref() {
-                Some(s) => {
+            match (self.signature.as_ref(), self.fee_token.as_ref()) {
+                (Some(s), Some(token)) => {
+                    v.push(FEE_TOKEN_TRANSACTION_VERSION | 0b1000_0000);
+                    s.encode_to(v);
+                    token.encode_to(v);
+                }
+                (Some(s), None) => {
                     v.push(TRANSACTION_VERSION | 0b1000_0000);
                     s.encode_to(v);
                 }
-                None => {
+                (None, _) => {
                     v.push(TRANSACTION_VERSION & 0b0111_1111);
                 }
             }
@@ -315,6 +361,79 @@ mod tests {
         assert_eq!(Ex::decode(&mut &encoded[..]), Some(ux));
     }
 
+    #[test]
+    fn signed_with_fee_token_codec_should_work() {
+        let ux = Ex::new_signed_with_fee_token(
+            0,
+            vec![0u8; 0],
+            DUMMY_ACCOUNTID,
+            TestSig(DUMMY_ACCOUNTID, vec![0u8; 0]),
+            Era::immortal(),
+            1,
+            b"BTC".to_vec(),
+        );
+        let encoded = ux.encode();
+        assert_eq!(Ex::decode(&mut &encoded[..]), Some(ux));
+    }
+
+    #[test]
+    fn fee_token_signed_check_should_work() {
+        let ux = Ex::new_signed_with_fee_token(
+            0,
+            vec![0u8; 0],
+            DUMMY_ACCOUNTID,
+            TestSig(
+                DUMMY_ACCOUNTID,
+                (
+                    Compact::from(DUMMY_ACCOUNTID),
+                    vec![0u8; 0],
+                    Era::immortal(),
+                    0u64,
+                    Compact::<u32>::from(1),
+                    b"BTC".to_vec(),
+                )
+                    .encode(),
+            ),
+            Era::immortal(),
+            1,
+            b"BTC".to_vec(),
+        );
+        assert_eq!(
+            <Ex as Checkable<TestContext>>::check(ux, &TestContext),
+            Ok(CEx {
+                signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: Some(b"BTC".to_vec()),
+                longevity: Some(u64::max_value() - 42),
+                function: vec![0u8; 0]
+            })
+        );
+
+        // the fee token must be signed as well.
+        let ux = Ex::new_signed_with_fee_token(
+            0,
+            vec![0u8; 0],
+            DUMMY_ACCOUNTID,
+            TestSig(
+                DUMMY_ACCOUNTID,
+                (
+                    Compact::from(DUMMY_ACCOUNTID),
+                    vec![0u8; 0],
+                    Era::immortal(),
+                    0u64,
+                    Compact::<u32>::from(1),
+                )
+                    .encode(),
+            ),
+            Era::immortal(),
+            1,
+            b"BTC".to_vec(),
+        );
+        assert_eq!(
+            <Ex as Checkable<TestContext>>::check(ux, &TestContext),
+            Err("bad signature in extrinsic")
+        );
+    }
+
     #[test]
     fn unsigned_check_should_work() {
         let ux = Ex::new_unsigned(vec![0u8; 0]);
@@ -364,6 +483,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(u64::max_value() - 42),
                 function: vec![0u8; 0]
             })
         );
@@ -394,6 +515,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(32),
                 function: vec![0u8; 0]
             })
         );
@@ -424,6 +547,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(1),
                 function: vec![0u8; 0]
             })
         );
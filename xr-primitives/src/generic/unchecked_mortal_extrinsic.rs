@@ -107,11 +107,15 @@ where
                 }
                 CheckedExtrinsic {
                     signed: Some((signed, raw_payload.0, raw_payload.4)),
+                    fee_token: None,
+                    longevity: Some(era.death(current_u64).saturating_sub(current_u64)),
                     function: raw_payload.1,
                 }
             }
             None => CheckedExtrinsic {
                 signed: None,
+                fee_token: None,
+                longevity: None,
                 function: self.function,
             },
         })
@@ -323,6 +327,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(u64::max_value() - 42),
                 function: vec![0u8; 0]
             })
         );
@@ -353,6 +359,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(32),
                 function: vec![0u8; 0]
             })
         );
@@ -383,6 +391,8 @@ mod tests {
             <Ex as Checkable<TestContext>>::check(ux, &TestContext),
             Ok(CEx {
                 signed: Some((DUMMY_ACCOUNTID, 0, 1)),
+                fee_token: None,
+                longevity: Some(1),
                 function: vec![0u8; 0]
             })
         );
@@ -8,6 +8,8 @@ pub use num_traits::{
 
 use runtime_primitives::traits::{MaybeDisplay, Member, SimpleArithmetic};
 
+use crate::Token;
+
 /// Work together with sr_primitives::traits::Applyable
 pub trait Accelerable: Sized + Send + Sync {
     type AccountId: Member + MaybeDisplay;
@@ -16,4 +18,10 @@ pub trait Accelerable: Sized + Send + Sync {
     type Acceleration: Member + MaybeDisplay + SimpleArithmetic + Copy + Into<u32> + From<u32>;
 
     fn acceleration(&self) -> Option<Self::Acceleration>;
+
+    /// The token used to pay the transaction fee, `None` means PCX.
+    fn fee_token(&self) -> Option<Token>;
+
+    /// How many blocks the transaction stays valid for, derived from the era.
+    fn longevity(&self) -> Option<u64>;
 }
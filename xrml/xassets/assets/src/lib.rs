@@ -8,6 +8,7 @@ mod pcx;
 pub mod traits;
 mod trigger;
 pub mod types;
+mod vesting;
 
 mod mock;
 mod tests;
@@ -41,9 +42,14 @@ use self::trigger::AssetTriggerEventAfter;
 pub use self::types::{
     is_valid_desc, is_valid_memo, is_valid_token, Asset, AssetErr, AssetLimit, AssetType, Chain,
     Desc, DescString, Memo, NegativeImbalance, PositiveImbalance, Precision, SignedBalance,
-    SignedImbalanceT, Token, TokenString,
+    SignedImbalanceT, Token, TokenString, VestingSchedule,
 };
 
+/// Max number of transfers in a `transfer_batch`.
+pub const MAX_TRANSFER_BATCH_LEN: usize = 1000;
+/// Max number of the vesting schedules of an account for a token.
+pub const MAX_VESTING_SCHEDULES: usize = 16;
+
 pub struct SimpleAccountIdDeterminator<T: Trait>(::rstd::marker::PhantomData<T>);
 
 impl<AccountId: Default, BlockNumber> TokenJackpotAccountIdFor<AccountId, BlockNumber> for () {
@@ -121,6 +127,18 @@ decl_event!(
         NewAccount(AccountId),
         /// change token balance, SignedBalance mark Positive or Negative
         Change(Token, AccountId, AssetType, SignedBalance),
+        /// owner, spender, token, allowance
+        Approve(AccountId, AccountId, Token, Balance),
+        /// owner, spender, dest, token, value
+        TransferFrom(AccountId, AccountId, AccountId, Token, Balance),
+        /// transactor, token, number of the transfers, total value
+        TransferBatch(AccountId, Token, u32, Balance),
+        /// who, token, total value of the vesting schedule
+        AddVesting(AccountId, Token, Balance),
+        /// who, token, released value
+        Vest(AccountId, Token, Balance),
+        Freeze(AccountId, Token),
+        Unfreeze(AccountId, Token),
     }
 );
 
@@ -176,6 +194,147 @@ decl_module! {
             Ok(())
         }
 
+        /// transfer to multiple accounts, nothing is transferred if any of them fails
+        pub fn transfer_batch(origin, token: Token, dests: Vec<(<T::Lookup as StaticLookup>::Source, T::Balance)>, memo: Memo) -> Result {
+            let transactor = ensure_signed(origin)?;
+            debug!("[transfer_batch]|from:{:?}|token:{:}|count:{:}|memo:{:}", transactor, token!(token), dests.len(), u8array_to_string(&memo));
+            is_valid_memo::<T>(&memo)?;
+            if dests.is_empty() {
+                return Err("the transfer batch is empty");
+            }
+            if dests.len() > MAX_TRANSFER_BATCH_LEN {
+                return Err("too many transfers in a batch");
+            }
+
+            Self::is_valid_asset(&token)?;
+            Self::can_transfer(&token)?;
+            Self::can_move(&token)?;
+
+            let mut total: T::Balance = Zero::zero();
+            let mut transfers = Vec::with_capacity(dests.len());
+            for (dest, value) in dests {
+                let dest = <T as system::Trait>::Lookup::lookup(dest)?;
+                total = total.checked_add(&value).ok_or("the total value of the batch overflows")?;
+                transfers.push((dest, value));
+            }
+            if Self::free_balance_of(&transactor, &token) < total {
+                return Err(AssetErr::NotEnough.info());
+            }
+            if Self::is_frozen(&transactor, &token) {
+                return Err(AssetErr::Frozen.info());
+            }
+            Self::ensure_vesting_unlocked(&token, &transactor, total).map_err(|e| e.info())?;
+
+            // the balance, the freeze and the vesting lock are checked for the total,
+            // thus none of the transfers would fail.
+            for (dest, value) in transfers.iter() {
+                let _ = Self::move_free_balance(&token, &transactor, dest, *value).map_err(|e| e.info())?;
+            }
+            Self::deposit_event(RawEvent::TransferBatch(transactor, token, transfers.len() as u32, total));
+            Ok(())
+        }
+
+        /// transfer to `dest` and lock it by a vesting schedule
+        pub fn vested_transfer(origin, dest: <T::Lookup as StaticLookup>::Source, token: Token, value: T::Balance, start: T::BlockNumber, cliff: T::BlockNumber, period: T::BlockNumber) -> Result {
+            let transactor = ensure_signed(origin)?;
+            let dest = <T as system::Trait>::Lookup::lookup(dest)?;
+            debug!("[vested_transfer]|from:{:?}|to:{:?}|token:{:}|value:{:}|start:{:?}|cliff:{:?}|period:{:?}", transactor, dest, token!(token), value, start, cliff, period);
+            if transactor == dest {
+                return Err("can't vest to yourself");
+            }
+
+            Self::can_transfer(&token)?;
+            // the schedules of an account are limited, dust schedules must not fill them.
+            if value < Self::min_vested_transfer(&token) {
+                return Err("the vested value is too small");
+            }
+            let schedule = VestingSchedule { total: value, released: Zero::zero(), start, cliff, period };
+            Self::check_vesting_schedule(&dest, &token, &schedule)?;
+            let _ = Self::move_free_balance(&token, &transactor, &dest, value).map_err(|e| e.info())?;
+            Self::add_vesting_schedule(&dest, &token, schedule);
+            Ok(())
+        }
+
+        /// release the vested balance of the transactor
+        pub fn vest(origin, token: Token) -> Result {
+            let who = ensure_signed(origin)?;
+            Self::release_vested(&who, &token)
+        }
+
+        /// release the vested balance of `target`
+        pub fn vest_other(origin, target: <T::Lookup as StaticLookup>::Source, token: Token) -> Result {
+            let _ = ensure_signed(origin)?;
+            let target = <T as system::Trait>::Lookup::lookup(target)?;
+            Self::release_vested(&target, &token)
+        }
+
+        /// allow `spender` to transfer at most `value` of `token` from the transactor, replace the old allowance
+        pub fn approve(origin, spender: <T::Lookup as StaticLookup>::Source, token: Token, value: T::Balance) -> Result {
+            let owner = ensure_signed(origin)?;
+            let spender = <T as system::Trait>::Lookup::lookup(spender)?;
+            debug!("[approve]|owner:{:?}|spender:{:?}|token:{:}|value:{:}", owner, spender, token!(token), value);
+            if owner == spender {
+                return Err("can't approve to yourself");
+            }
+
+            Self::is_valid_asset(&token)?;
+            Self::can_transfer(&token)?;
+            Self::set_allowance(&owner, &spender, &token, value);
+            Self::deposit_event(RawEvent::Approve(owner, spender, token, value));
+            Ok(())
+        }
+
+        /// transfer from `owner` to `dest` within the allowance approved to the transactor
+        pub fn transfer_from(origin, owner: <T::Lookup as StaticLookup>::Source, dest: <T::Lookup as StaticLookup>::Source, token: Token, value: T::Balance, memo: Memo) -> Result {
+            let spender = ensure_signed(origin)?;
+            let owner = <T as system::Trait>::Lookup::lookup(owner)?;
+            let dest = <T as system::Trait>::Lookup::lookup(dest)?;
+            debug!("[transfer_from]|owner:{:?}|spender:{:?}|to:{:?}|token:{:}|value:{:}|memo:{:}", owner, spender, dest, token!(token), value, u8array_to_string(&memo));
+            is_valid_memo::<T>(&memo)?;
+
+            Self::can_transfer(&token)?;
+            let allowance = Self::allowance(&(owner.clone(), spender.clone(), token.clone()));
+            let remaining = allowance.checked_sub(&value).ok_or("allowance is not enough")?;
+            let _ = Self::move_free_balance(&token, &owner, &dest, value).map_err(|e| e.info())?;
+            Self::set_allowance(&owner, &spender, &token, remaining);
+            Self::deposit_event(RawEvent::TransferFrom(owner, spender, dest, token, value));
+            Ok(())
+        }
+
+        /// freeze the balance of an account in a token, it can't be moved out of the account
+        pub fn freeze(who: <T::Lookup as StaticLookup>::Source, token: Token) -> Result {
+            let who = <T as system::Trait>::Lookup::lookup(who)?;
+            let _ = Self::get_asset(&token)?;
+            info!("[freeze]|freeze by root|who:{:?}|token:{:}", who, token!(token));
+
+            let mut tokens = Self::frozen_tokens_of(&who);
+            if tokens.contains(&token) {
+                return Err("this account has been frozen for this asset");
+            }
+            tokens.push(token.clone());
+            FrozenTokensOf::<T>::insert(&who, tokens);
+            Self::deposit_event(RawEvent::Freeze(who, token));
+            Ok(())
+        }
+
+        pub fn unfreeze(who: <T::Lookup as StaticLookup>::Source, token: Token) -> Result {
+            let who = <T as system::Trait>::Lookup::lookup(who)?;
+            info!("[unfreeze]|unfreeze by root|who:{:?}|token:{:}", who, token!(token));
+
+            let mut tokens = Self::frozen_tokens_of(&who);
+            if !tokens.contains(&token) {
+                return Err("this account is not frozen for this asset");
+            }
+            tokens.retain(|t| *t != token);
+            if tokens.is_empty() {
+                FrozenTokensOf::<T>::remove(&who);
+            } else {
+                FrozenTokensOf::<T>::insert(&who, tokens);
+            }
+            Self::deposit_event(RawEvent::Unfreeze(who, token));
+            Ok(())
+        }
+
         pub fn modify_asset_info(token: Token, token_name: Option<Token>, desc: Option<Desc>) {
             if let Some(ref mut info) = Self::asset_info(&token) {
                 token_name.map(|name| info.0.set_token_name(name));
@@ -195,6 +354,13 @@ decl_module! {
             }
         }
 
+        /// set the minimum value of a vested transfer of a token
+        pub fn set_min_vested_transfer(token: Token, value: T::Balance) -> Result {
+            Self::is_valid_asset(&token)?;
+            MinVestedTransfer::<T>::insert(&token, value);
+            Ok(())
+        }
+
         pub fn modify_asset_limit(token: Token, limit: AssetLimit, can_do: bool) {
             if Self::asset_info(&token).is_some() {
                 AssetLimitProps::<T>::mutate(token, |limit_map| {
@@ -228,6 +394,19 @@ decl_storage! {
         /// asset balance for a token, use btree_map to accept different asset type
         pub TotalAssetBalance get(total_asset_balance): map Token => BTreeMap<AssetType, T::Balance>;
 
+        /// (owner, spender, token) => the amount the spender could transfer from the owner
+        pub Allowance get(allowance): map (T::AccountId, T::AccountId, Token) => T::Balance;
+        /// owner => (spender, token) of all non-zero allowances
+        pub AllowancesOf get(allowances_of): map T::AccountId => Vec<(T::AccountId, Token)>;
+
+        /// the tokens an account is frozen for
+        pub FrozenTokensOf get(frozen_tokens_of): map T::AccountId => Vec<Token>;
+
+        /// the vesting schedules of an account for a token
+        pub Vesting get(vesting): map (T::AccountId, Token) => Vec<VestingSchedule<T::Balance, T::BlockNumber>>;
+        /// the minimum value of a vested transfer of a token
+        pub MinVestedTransfer get(min_vested_transfer): map Token => T::Balance;
+
         /// memo len
         pub MemoLen get(memo_len) config(): u32;
     }
@@ -251,6 +430,28 @@ impl<T: Trait> Module<T> {
         Self::register_asset(asset, is_online, is_psedu_intention)
     }
 
+    /// Set the allowance and keep `AllowancesOf` in sync, zero allowance is removed.
+    fn set_allowance(
+        owner: &T::AccountId,
+        spender: &T::AccountId,
+        token: &Token,
+        value: T::Balance,
+    ) {
+        let key = (owner.clone(), spender.clone(), token.clone());
+        let item = (spender.clone(), token.clone());
+        if value.is_zero() {
+            Allowance::<T>::remove(&key);
+            AllowancesOf::<T>::mutate(owner, |list| list.retain(|i| *i != item));
+        } else {
+            Allowance::<T>::insert(&key, value);
+            AllowancesOf::<T>::mutate(owner, |list| {
+                if !list.contains(&item) {
+                    list.push(item);
+                }
+            });
+        }
+    }
+
     pub fn should_not_free_type(type_: AssetType) -> Result {
         if type_ == AssetType::Free {
             return Err("should not be free type here");
@@ -371,6 +572,10 @@ impl<T: Trait> Module<T> {
         }
     }
 
+    pub fn is_frozen(who: &T::AccountId, token: &Token) -> bool {
+        Self::frozen_tokens_of(who).contains(token)
+    }
+
     pub fn can_do(token: &Token, limit: AssetLimit) -> bool {
         Self::asset_limit_props(token)
             .get(&limit)
@@ -473,6 +678,8 @@ impl<T: Trait> Module<T> {
 
             Self::can_destroy_withdrawal(token)?;
 
+            // not checking the freeze, the withdrawal has been sent out on the other chain when
+            // it's finished.
             let type_ = AssetType::ReservedWithdrawal;
 
             debug!("[destroy]|normal destroy withdrawal token for account");
@@ -489,6 +696,10 @@ impl<T: Trait> Module<T> {
             Self::can_destroy_free(token)?;
 
             let type_ = AssetType::Free;
+            if Self::is_frozen(who, token) {
+                return Err(AssetErr::Frozen.info());
+            }
+            Self::ensure_vesting_unlocked(token, who, value).map_err(|e| e.info())?;
 
             debug!("[destroy_free]|destroy free token for account directly");
             let _imbalance = Self::inner_destroy(token, who, type_, value)?;
@@ -648,6 +859,11 @@ impl<T: Trait> Module<T> {
 
         Self::can_move(token).map_err(|_| AssetErr::NotAllow)?;
 
+        // the frozen balance could only be moved back to free in the account.
+        if (from != to || to_type != AssetType::Free) && Self::is_frozen(from, token) {
+            return Err(AssetErr::Frozen);
+        }
+
         let from_key = (from.clone(), token.clone());
         let to_key = (to.clone(), token.clone());
 
@@ -666,6 +882,9 @@ impl<T: Trait> Module<T> {
             Some(b) => b,
             None => return Err(AssetErr::OverFlow),
         };
+        if from_type == AssetType::Free && (from != to || !Self::is_vesting_lockable(to_type)) {
+            Self::ensure_vesting_unlocked(token, from, value)?;
+        }
 
         // finish basic check, start self check
         if from == to && from_type == to_type {
@@ -744,6 +963,17 @@ impl<T: Trait> Module<T> {
         Self::issue(&Self::TOKEN.to_vec(), who, value)
     }
 
+    /// Destroy free PCX of an account directly, reducing the total issuance.
+    pub fn pcx_destroy_free(who: &T::AccountId, value: T::Balance) -> Result {
+        let token = Self::TOKEN.to_vec();
+        if Self::is_frozen(who, &token) {
+            return Err(AssetErr::Frozen.info());
+        }
+        Self::ensure_vesting_unlocked(&token, who, value).map_err(|e| e.info())?;
+        let _imbalance = Self::inner_destroy(&token, who, AssetType::Free, value)?;
+        Ok(())
+    }
+
     pub fn pcx_move_balance(
         from: &T::AccountId,
         from_type: AssetType,
@@ -36,12 +36,12 @@ impl<T: Trait> Currency<T::AccountId> for Module<T> {
     }
 
     fn ensure_can_withdraw(
-        _who: &T::AccountId,
-        _amount: Self::Balance,
+        who: &T::AccountId,
+        amount: Self::Balance,
         _reason: WithdrawReason,
         _new_balance: Self::Balance,
     ) -> Result {
-        Ok(())
+        Self::ensure_vesting_unlocked(&Self::TOKEN.to_vec(), who, amount).map_err(|e| e.info())
     }
 
     fn transfer(source: &T::AccountId, dest: &T::AccountId, value: Self::Balance) -> Result {
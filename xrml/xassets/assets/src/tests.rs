@@ -847,3 +847,413 @@ fn test_move() {
         assert_eq!(XAssets::free_balance_of(&b, &token), 100);
     })
 }
+
+#[test]
+fn test_transfer_from() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: u64 = 1; // owner
+        let b: u64 = 2; // spender
+        let c: u64 = 3; // dest
+        let btc_token = b"BTC".to_vec();
+        XAssets::issue(&btc_token, &a, 50).unwrap();
+
+        assert_err!(
+            XAssets::approve(Some(a).into(), a.into(), btc_token.clone(), 30),
+            "can't approve to yourself"
+        );
+        assert_ok!(XAssets::approve(
+            Some(a).into(),
+            b.into(),
+            btc_token.clone(),
+            30
+        ));
+        assert_eq!(XAssets::allowance(&(a, b, btc_token.clone())), 30);
+        assert_eq!(XAssets::allowances_of(&a), vec![(b, btc_token.clone())]);
+
+        assert_err!(
+            XAssets::transfer_from(
+                Some(b).into(),
+                a.into(),
+                c.into(),
+                btc_token.clone(),
+                31,
+                b"".to_vec()
+            ),
+            "allowance is not enough"
+        );
+        assert_ok!(XAssets::transfer_from(
+            Some(b).into(),
+            a.into(),
+            c.into(),
+            btc_token.clone(),
+            20,
+            b"".to_vec()
+        ));
+        assert_eq!(XAssets::free_balance_of(&a, &btc_token), 30);
+        assert_eq!(XAssets::free_balance_of(&c, &btc_token), 20);
+        assert_eq!(XAssets::allowance(&(a, b, btc_token.clone())), 10);
+
+        // the allowance is kept if the owner doesn't have enough balance
+        assert_ok!(XAssets::approve(
+            Some(a).into(),
+            b.into(),
+            btc_token.clone(),
+            100
+        ));
+        assert_err!(
+            XAssets::transfer_from(
+                Some(b).into(),
+                a.into(),
+                c.into(),
+                btc_token.clone(),
+                50,
+                b"".to_vec()
+            ),
+            "balance too low for this account"
+        );
+        assert_eq!(XAssets::allowance(&(a, b, btc_token.clone())), 100);
+
+        // approve zero to cancel
+        assert_ok!(XAssets::approve(
+            Some(a).into(),
+            b.into(),
+            btc_token.clone(),
+            0
+        ));
+        assert!(XAssets::allowances_of(&a).is_empty());
+
+        XAssets::modify_asset_limit(btc_token.clone(), AssetLimit::CanTransfer, false);
+        assert_err!(
+            XAssets::approve(Some(a).into(), b.into(), btc_token.clone(), 30),
+            "this asset do not allow transfer"
+        );
+    })
+}
+
+#[test]
+fn test_transfer_batch() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: u64 = 1; // accountid
+        let b: u64 = 2; // accountid
+        let c: u64 = 3; // accountid
+        let btc_token = b"BTC".to_vec();
+        XAssets::issue(&btc_token, &a, 100).unwrap();
+
+        assert_err!(
+            XAssets::transfer_batch(Some(a).into(), btc_token.clone(), vec![], b"".to_vec()),
+            "the transfer batch is empty"
+        );
+        // nothing is transferred if the balance is not enough for all
+        assert_err!(
+            XAssets::transfer_batch(
+                Some(a).into(),
+                btc_token.clone(),
+                vec![(b.into(), 60), (c.into(), 50)],
+                b"".to_vec()
+            ),
+            "balance too low for this account"
+        );
+        assert_eq!(XAssets::free_balance_of(&a, &btc_token), 100);
+        assert_eq!(XAssets::free_balance_of(&b, &btc_token), 0);
+
+        assert_ok!(XAssets::transfer_batch(
+            Some(a).into(),
+            btc_token.clone(),
+            vec![(b.into(), 30), (c.into(), 40), (b.into(), 10)],
+            b"".to_vec()
+        ));
+        assert_eq!(XAssets::free_balance_of(&a, &btc_token), 20);
+        assert_eq!(XAssets::free_balance_of(&b, &btc_token), 40);
+        assert_eq!(XAssets::free_balance_of(&c, &btc_token), 40);
+    })
+}
+
+#[test]
+fn test_vesting() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: u64 = 1; // accountid
+        let b: u64 = 2; // accountid
+        let c: u64 = 3; // accountid
+        let token = XAssets::TOKEN.to_vec();
+
+        assert_err!(
+            XAssets::vested_transfer(Some(a).into(), b.into(), token.clone(), 100, 10, 5, 100),
+            "the vesting cliff should not be before the start"
+        );
+        // linear from block 10 to block 110
+        assert_ok!(XAssets::vested_transfer(
+            Some(a).into(),
+            b.into(),
+            token.clone(),
+            100,
+            10,
+            10,
+            100
+        ));
+        assert_eq!(XAssets::free_balance_of(&b, &token), 510 + 100);
+
+        // only the unlocked part could be transferred
+        assert_ok!(XAssets::transfer(
+            Some(b).into(),
+            c.into(),
+            token.clone(),
+            510,
+            b"".to_vec()
+        ));
+        assert_err!(
+            XAssets::transfer(Some(b).into(), c.into(), token.clone(), 1, b"".to_vec()),
+            "balance is locked by vesting"
+        );
+        assert_err!(
+            XAssets::pcx_move_balance(&b, AssetType::Free, &b, AssetType::ReservedDexSpot, 1),
+            AssetErr::VestingLocked
+        );
+        // the locked balance could be staked
+        assert_ok!(XAssets::pcx_move_balance(
+            &b,
+            AssetType::Free,
+            &b,
+            AssetType::ReservedStaking,
+            100
+        ));
+
+        system::Module::<Test>::set_block_number(5);
+        assert_err!(
+            XAssets::vest(Some(b).into(), token.clone()),
+            "nothing is vested yet"
+        );
+
+        system::Module::<Test>::set_block_number(60);
+        assert_ok!(XAssets::vest_other(Some(c).into(), b.into(), token.clone()));
+        assert_eq!(XAssets::vesting_locked(&b, &token), 50);
+        assert_ok!(XAssets::pcx_move_balance(
+            &b,
+            AssetType::ReservedStaking,
+            &b,
+            AssetType::Free,
+            100
+        ));
+        assert_ok!(XAssets::transfer(
+            Some(b).into(),
+            c.into(),
+            token.clone(),
+            50,
+            b"".to_vec()
+        ));
+        assert_err!(
+            XAssets::transfer(Some(b).into(), c.into(), token.clone(), 1, b"".to_vec()),
+            "balance is locked by vesting"
+        );
+
+        system::Module::<Test>::set_block_number(200);
+        assert_ok!(XAssets::vest(Some(b).into(), token.clone()));
+        assert!(XAssets::vesting(&(b, token.clone())).is_empty());
+        assert_ok!(XAssets::transfer(
+            Some(b).into(),
+            c.into(),
+            token.clone(),
+            50,
+            b"".to_vec()
+        ));
+        assert_err!(
+            XAssets::vest(Some(b).into(), token.clone()),
+            "no vesting schedule for this token"
+        );
+
+        // multiple schedules for the same token
+        assert_ok!(XAssets::vested_transfer(
+            Some(a).into(),
+            b.into(),
+            token.clone(),
+            100,
+            200,
+            200,
+            100
+        ));
+        assert_ok!(XAssets::vested_transfer(
+            Some(c).into(),
+            b.into(),
+            token.clone(),
+            100,
+            200,
+            300,
+            0
+        ));
+        system::Module::<Test>::set_block_number(250);
+        assert_ok!(XAssets::vest(Some(b).into(), token.clone()));
+        assert_eq!(XAssets::vesting(&(b, token.clone())).len(), 2);
+        assert_eq!(XAssets::vesting_locked(&b, &token), 150);
+        for _ in 2..MAX_VESTING_SCHEDULES {
+            assert_ok!(XAssets::vested_transfer(
+                Some(a).into(),
+                b.into(),
+                token.clone(),
+                1,
+                300,
+                300,
+                0
+            ));
+        }
+        assert_err!(
+            XAssets::vested_transfer(Some(a).into(), b.into(), token.clone(), 1, 300, 300, 0),
+            "too many vesting schedules for this token"
+        );
+
+        // dust schedules are rejected
+        let d: u64 = 5;
+        assert_ok!(XAssets::set_min_vested_transfer(token.clone(), 10));
+        assert_err!(
+            XAssets::vested_transfer(Some(a).into(), d.into(), token.clone(), 9, 300, 300, 0),
+            "the vested value is too small"
+        );
+        assert_ok!(XAssets::vested_transfer(
+            Some(a).into(),
+            d.into(),
+            token.clone(),
+            10,
+            300,
+            300,
+            0
+        ));
+
+        // the batch is checked against the vesting lock as a whole
+        let free = XAssets::free_balance_of(&b, &token);
+        let locked = XAssets::vesting_locked(&b, &token);
+        assert_err!(
+            XAssets::transfer_batch(
+                Some(b).into(),
+                token.clone(),
+                vec![(c.into(), 1), (a.into(), free - locked)],
+                b"".to_vec()
+            ),
+            "balance is locked by vesting"
+        );
+        assert_eq!(XAssets::free_balance_of(&b, &token), free);
+
+        // lock the existing balance in genesis without issuing
+        let total_issuance = XAssets::all_type_total_asset_balance(&token);
+        let schedule = VestingSchedule {
+            total: 1000,
+            released: 0,
+            start: 0,
+            cliff: 0,
+            period: 1000,
+        };
+        assert_err!(
+            XAssets::bootstrap_lock_vesting(
+                &a,
+                &token,
+                VestingSchedule {
+                    total: 2000,
+                    ..schedule
+                }
+            ),
+            "not enough balance to be locked by vesting"
+        );
+        let a: u64 = 4;
+        XAssets::pcx_issue(&a, 1000).unwrap();
+        let total_issuance = total_issuance + 1000;
+        assert_ok!(XAssets::bootstrap_lock_vesting(&a, &token, schedule));
+        assert_eq!(
+            XAssets::all_type_total_asset_balance(&token),
+            total_issuance
+        );
+        assert_eq!(XAssets::vesting_locked(&a, &token), 1000);
+
+        // cliff schedule
+        let schedule = VestingSchedule {
+            total: 100,
+            released: 0,
+            start: 0,
+            cliff: 100,
+            period: 100,
+        };
+        assert_eq!(XAssets::vested_amount(&schedule, 99), 0);
+        assert_eq!(XAssets::vested_amount(&schedule, 100), 100);
+    })
+}
+
+#[test]
+fn test_freeze() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: u64 = 1; // accountid
+        let b: u64 = 2; // accountid
+        let btc_token = b"BTC".to_vec();
+        let chainx_token = XAssets::TOKEN.to_vec();
+        XAssets::issue(&btc_token, &a, 100).unwrap();
+        XAssets::issue(&btc_token, &b, 100).unwrap();
+        XAssets::move_balance(
+            &btc_token,
+            &a,
+            AssetType::Free,
+            &a,
+            AssetType::ReservedWithdrawal,
+            10,
+        )
+        .unwrap();
+
+        assert_ok!(XAssets::freeze(a.into(), btc_token.clone()));
+        assert_err!(
+            XAssets::freeze(a.into(), btc_token.clone()),
+            "this account has been frozen for this asset"
+        );
+        assert_eq!(XAssets::frozen_tokens_of(&a), vec![btc_token.clone()]);
+
+        assert_err!(
+            XAssets::transfer(
+                Some(a).into(),
+                b.into(),
+                btc_token.clone(),
+                10,
+                b"".to_vec()
+            ),
+            "this account is frozen for this asset"
+        );
+        assert_err!(
+            XAssets::move_balance(
+                &btc_token,
+                &a,
+                AssetType::Free,
+                &a,
+                AssetType::ReservedWithdrawal,
+                10
+            ),
+            AssetErr::Frozen
+        );
+        // the frozen balance could be moved back to free
+        assert_ok!(XAssets::move_balance(
+            &btc_token,
+            &a,
+            AssetType::ReservedWithdrawal,
+            &a,
+            AssetType::Free,
+            10
+        ));
+        // other accounts and other tokens are not affected
+        assert_ok!(XAssets::transfer(
+            Some(b).into(),
+            a.into(),
+            btc_token.clone(),
+            10,
+            b"".to_vec()
+        ));
+        assert_ok!(XAssets::transfer(
+            Some(a).into(),
+            b.into(),
+            chainx_token.clone(),
+            10,
+            b"".to_vec()
+        ));
+
+        assert_ok!(XAssets::unfreeze(a.into(), btc_token.clone()));
+        assert!(XAssets::frozen_tokens_of(&a).is_empty());
+        assert_ok!(XAssets::transfer(
+            Some(a).into(),
+            b.into(),
+            btc_token.clone(),
+            10,
+            b"".to_vec()
+        ));
+        assert_eq!(XAssets::free_balance_of(&a, &btc_token), 100);
+    })
+}
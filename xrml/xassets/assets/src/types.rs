@@ -158,6 +158,7 @@ define_enum!(
         ReservedCurrency,
         ReservedXRC20,
         GasPayment,
+        ReservedMultiSig,
     }
 );
 
@@ -190,6 +191,8 @@ pub enum AssetErr {
     InvalidToken,
     InvalidAccount,
     NotAllow,
+    VestingLocked,
+    Frozen,
 }
 
 impl AssetErr {
@@ -202,10 +205,32 @@ impl AssetErr {
             AssetErr::InvalidToken => "not a valid token for this account",
             AssetErr::InvalidAccount => "account Locked",
             AssetErr::NotAllow => "not allow to do",
+            AssetErr::VestingLocked => "balance is locked by vesting",
+            AssetErr::Frozen => "this account is frozen for this asset",
         }
     }
 }
 
+/// Nothing is vested before `cliff`, then `total` is vested linearly from `start` to
+/// `start + period`. A cliff schedule is the one whose `cliff` is `start + period`.
+#[derive(PartialEq, Eq, Clone, Copy, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+pub struct VestingSchedule<Balance, BlockNumber> {
+    pub total: Balance,
+    /// The amount has been released by `vest`.
+    pub released: Balance,
+    pub start: BlockNumber,
+    pub cliff: BlockNumber,
+    pub period: BlockNumber,
+}
+
+impl<Balance: Saturating + Copy, BlockNumber> VestingSchedule<Balance, BlockNumber> {
+    /// The amount still locked.
+    pub fn locked(&self) -> Balance {
+        self.total.saturating_sub(self.released)
+    }
+}
+
 /// Token can only use ASCII alphanumeric character or "-.|~".
 pub fn is_valid_token(v: &[u8]) -> Result {
     if v.len() > MAX_TOKEN_LEN || v.is_empty() {
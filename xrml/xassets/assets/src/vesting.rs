@@ -0,0 +1,154 @@
+// Copyright 2018-2019 Chainpool.
+
+use primitives::traits::{SaturatedConversion, Saturating, Zero};
+use rstd::result;
+use support::dispatch::Result;
+use support::StorageMap;
+
+use crate::types::{AssetErr, AssetType, Token, VestingSchedule};
+use crate::{Module, RawEvent, Trait, Vesting, MAX_VESTING_SCHEDULES};
+
+/// The balance of these types is counted for the vesting lock, thus the locked balance could be staked.
+const VESTING_LOCKABLE_TYPES: [AssetType; 3] = [
+    AssetType::Free,
+    AssetType::ReservedStaking,
+    AssetType::ReservedStakingRevocation,
+];
+
+impl<T: Trait> Module<T> {
+    pub fn is_vesting_lockable(type_: AssetType) -> bool {
+        VESTING_LOCKABLE_TYPES.contains(&type_)
+    }
+
+    /// The amount vested at block `now`, including the released part.
+    pub fn vested_amount(
+        schedule: &VestingSchedule<T::Balance, T::BlockNumber>,
+        now: T::BlockNumber,
+    ) -> T::Balance {
+        if now < schedule.cliff {
+            return Zero::zero();
+        }
+        if schedule.period.is_zero() || now >= schedule.start.saturating_add(schedule.period) {
+            return schedule.total;
+        }
+
+        let elapsed = (now - schedule.start).saturated_into::<u128>();
+        let period = schedule.period.saturated_into::<u128>();
+        let total = schedule.total.saturated_into::<u128>();
+        (total * elapsed / period).saturated_into::<u64>().into()
+    }
+
+    /// The balance counted for the vesting lock.
+    fn vesting_lockable_balance(key: &(T::AccountId, Token)) -> T::Balance {
+        VESTING_LOCKABLE_TYPES
+            .iter()
+            .fold(Zero::zero(), |acc: T::Balance, type_| {
+                acc.saturating_add(Self::asset_type_balance(key, *type_))
+            })
+    }
+
+    /// The balance still locked by all the vesting schedules.
+    pub fn vesting_locked(who: &T::AccountId, token: &Token) -> T::Balance {
+        Self::vesting(&(who.clone(), token.clone()))
+            .iter()
+            .fold(Zero::zero(), |acc: T::Balance, schedule| {
+                acc.saturating_add(schedule.locked())
+            })
+    }
+
+    /// Check that moving `value` out of the free balance won't touch the balance locked by vesting.
+    pub fn ensure_vesting_unlocked(
+        token: &Token,
+        who: &T::AccountId,
+        value: T::Balance,
+    ) -> result::Result<(), AssetErr> {
+        let locked = Self::vesting_locked(who, token);
+        if locked.is_zero() {
+            return Ok(());
+        }
+
+        let lockable = Self::vesting_lockable_balance(&(who.clone(), token.clone()));
+        if lockable.saturating_sub(value) < locked {
+            return Err(AssetErr::VestingLocked);
+        }
+        Ok(())
+    }
+
+    pub(crate) fn check_vesting_schedule(
+        who: &T::AccountId,
+        token: &Token,
+        schedule: &VestingSchedule<T::Balance, T::BlockNumber>,
+    ) -> Result {
+        Self::is_valid_asset(token)?;
+        if schedule.total.is_zero() {
+            return Err("the vesting value can't be zero");
+        }
+        if schedule.cliff < schedule.start {
+            return Err("the vesting cliff should not be before the start");
+        }
+        if Self::vesting(&(who.clone(), token.clone())).len() >= MAX_VESTING_SCHEDULES {
+            return Err("too many vesting schedules for this token");
+        }
+        Ok(())
+    }
+
+    pub(crate) fn add_vesting_schedule(
+        who: &T::AccountId,
+        token: &Token,
+        schedule: VestingSchedule<T::Balance, T::BlockNumber>,
+    ) {
+        Vesting::<T>::mutate(&(who.clone(), token.clone()), |schedules| {
+            schedules.push(schedule)
+        });
+        Self::deposit_event(RawEvent::AddVesting(
+            who.clone(),
+            token.clone(),
+            schedule.total,
+        ));
+    }
+
+    /// Lock the existing balance of `who` by the vesting schedule in genesis, nothing is issued.
+    #[cfg(feature = "std")]
+    pub fn bootstrap_lock_vesting(
+        who: &T::AccountId,
+        token: &Token,
+        schedule: VestingSchedule<T::Balance, T::BlockNumber>,
+    ) -> Result {
+        Self::check_vesting_schedule(who, token, &schedule)?;
+        let locked = Self::vesting_locked(who, token).saturating_add(schedule.total);
+        if Self::vesting_lockable_balance(&(who.clone(), token.clone())) < locked {
+            return Err("not enough balance to be locked by vesting");
+        }
+        Self::add_vesting_schedule(who, token, schedule);
+        Ok(())
+    }
+
+    /// Release the vested balance of all the schedules, the completed ones are removed.
+    pub(crate) fn release_vested(who: &T::AccountId, token: &Token) -> Result {
+        let key = (who.clone(), token.clone());
+        let mut schedules = Self::vesting(&key);
+        if schedules.is_empty() {
+            return Err("no vesting schedule for this token");
+        }
+
+        let now = system::Module::<T>::block_number();
+        let mut value: T::Balance = Zero::zero();
+        for schedule in schedules.iter_mut() {
+            let vested = Self::vested_amount(schedule, now).saturating_sub(schedule.released);
+            schedule.released = schedule.released.saturating_add(vested);
+            value = value.saturating_add(vested);
+        }
+        if value.is_zero() {
+            return Err("nothing is vested yet");
+        }
+
+        schedules.retain(|schedule| schedule.released < schedule.total);
+        if schedules.is_empty() {
+            Vesting::<T>::remove(&key);
+        } else {
+            Vesting::<T>::insert(&key, schedules);
+        }
+        Self::deposit_event(RawEvent::Vest(who.clone(), token.clone(), value));
+        Ok(())
+    }
+}
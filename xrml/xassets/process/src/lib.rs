@@ -38,23 +38,10 @@ decl_module! {
         fn withdraw(origin, token: Token, value: T::Balance, addr: AddrStr, ext: Memo) -> Result {
             let who = ensure_signed(origin)?;
 
-            Self::can_withdraw(&token)?;
+            xfee_manager::Module::<T>::check_call(Self::check_withdraw(&token, value, &addr, &ext))?;
 
             debug!("[withdraw]withdraw|who:{:?}|token:{:}|value:{:}", who, token!(token), value);
 
-            let asset = xassets::Module::<T>::get_asset(&token)?;
-            if asset.chain() == Chain::ChainX {
-                return Err("Can't withdraw the asset on ChainX")
-            }
-
-            Self::verify_addr(&token, &addr, &ext)?;
-
-            let limit = Self::withdrawal_limit(&token).ok_or("token should has withdrawal limit")?;
-            // withdrawal value should larger than minimal_withdrawal, allow equal
-            if value < limit.minimal_withdrawal {
-                return Err("withdrawal value should larger than requirement")
-            }
-
             xrecords::Module::<T>::withdrawal(&who, &token, value, addr, ext)?;
             Ok(())
         }
@@ -86,6 +73,24 @@ decl_storage! {
 }
 
 impl<T: Trait> Module<T> {
+    fn check_withdraw(token: &Token, value: T::Balance, addr: &[u8], ext: &[u8]) -> Result {
+        Self::can_withdraw(token)?;
+
+        let asset = xassets::Module::<T>::get_asset(token)?;
+        if asset.chain() == Chain::ChainX {
+            return Err("Can't withdraw the asset on ChainX");
+        }
+
+        Self::verify_addr(token, addr, ext)?;
+
+        let limit = Self::withdrawal_limit(token).ok_or("token should has withdrawal limit")?;
+        // withdrawal value should larger than minimal_withdrawal, allow equal
+        if value < limit.minimal_withdrawal {
+            return Err("withdrawal value should larger than requirement");
+        }
+        Ok(())
+    }
+
     #[inline]
     fn can_withdraw(token: &Token) -> Result {
         ensure_with_errorlog!(
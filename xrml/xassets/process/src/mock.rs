@@ -102,6 +102,7 @@ impl xrecords::Trait for Test {
 
 impl xfee_manager::Trait for Test {
     type Event = ();
+    type AssetPrice = ();
 }
 
 impl xbitcoin::lockup::Trait for Test {
@@ -121,6 +122,12 @@ impl xbitcoin::Trait for Test {
     type Event = ();
 }
 
+impl xethereum::Trait for Test {
+    type AccountExtractor = DummyExtractor;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
 pub struct DummyExtractor;
 impl xbridge_common::traits::Extractable<u64> for DummyExtractor {
     fn account_info(_data: &[u8], _: u8) -> Option<(u64, Option<Vec<u8>>)> {
@@ -151,6 +158,7 @@ impl xmultisig::Trait for Test {
     type GenesisMultiSig = DummyGenesisMultiSig;
     type Proposal = DummyCall;
     type TrusteeCall = TrusteeCall;
+    type ProposalHook = ();
     type Event = ();
 }
 
@@ -217,6 +225,7 @@ pub type XAssets = xassets::Module<Test>;
 pub type XRecords = xrecords::Module<Test>;
 pub type XBitCoin = xbitcoin::Module<Test>;
 pub type XProcess = Module<Test>;
+pub type XFeeManager = xfee_manager::Module<Test>;
 pub type XBridgeFeatures = xbridge_features::Module<Test>;
 pub type XStaking = xstaking::Module<Test>;
 
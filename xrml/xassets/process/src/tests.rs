@@ -24,6 +24,8 @@ fn test_check_btc_addr() {
             ),
             "Verify btc addr err"
         );
+        // failed in the checks before changing any storage
+        assert!(XFeeManager::take_call_failed_early());
 
         let origin = system::RawOrigin::Signed(1).into();
         assert_ok!(XProcess::withdraw(
@@ -33,6 +35,7 @@ fn test_check_btc_addr() {
             b"mjKE11gjVN4JaC9U8qL6ZB5vuEBgmwik7b".to_vec(),
             b"".to_vec()
         ));
+        assert!(!XFeeManager::take_call_failed_early());
 
         assert_eq!(XAssets::free_balance_of(&1, &b"BTC".to_vec()), 900);
 
@@ -100,6 +100,11 @@ impl<T: Trait> Module<T> {
     fn withdraw_check_before(who: &T::AccountId, token: &Token, value: T::Balance) -> Result {
         Self::before(who, token)?;
 
+        // check it before the application is stored, locking the balance would fail later.
+        if xassets::Module::<T>::is_frozen(who, token) {
+            return Err("the applicant is frozen for this asset");
+        }
+
         let free = xassets::Module::<T>::free_balance_of(who, token);
         if free < value {
             return Err("free balance not enough for this account");
@@ -200,6 +205,10 @@ impl<T: Trait> Module<T> {
                     error!("[withdrawal_processing]|application state not `Applying`|id:{:}|state:{:?}", id, node.data.state());
                     return Err("application state not `Applying`");
                 }
+                if xassets::Module::<T>::is_frozen(&node.data.applicant, &node.data.token) {
+                    error!("[withdrawal_processing]|the applicant is frozen for this asset|id:{:}|applicant:{:?}", id, node.data.applicant);
+                    return Err("the applicant is frozen for this asset");
+                }
                 v.push((*id, node));
             } else {
                 error!(
@@ -90,6 +90,61 @@ fn test_normal2() {
     })
 }
 
+#[test]
+fn test_withdrawal_frozen() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: u64 = 1; // accountid
+        let btc_token = b"BTC".to_vec();
+        assert_ok!(XRecords::deposit(&a, &btc_token, 100));
+
+        assert_ok!(XRecords::withdrawal(
+            &a,
+            &btc_token,
+            50,
+            b"addr".to_vec(),
+            b"ext".to_vec()
+        ));
+        assert_ok!(XRecords::withdrawal(
+            &a,
+            &btc_token,
+            30,
+            b"addr".to_vec(),
+            b"ext".to_vec()
+        ));
+        let numbers = XRecords::withdrawal_application_numbers(Chain::Bitcoin, 10).unwrap();
+
+        // the trustees can't process the application of a frozen account
+        assert_ok!(XAssets::freeze(a, btc_token.clone()));
+        assert_err!(
+            XRecords::withdrawal_processing(&numbers),
+            "the applicant is frozen for this asset"
+        );
+        // nor could a frozen account apply for a new withdrawal
+        assert_err!(
+            XRecords::withdrawal(&a, &btc_token, 10, b"addr".to_vec(), b"ext".to_vec()),
+            "the applicant is frozen for this asset"
+        );
+        assert_ok!(XAssets::unfreeze(a, btc_token.clone()));
+        assert_ok!(XRecords::withdrawal_processing(&numbers));
+
+        // the application being processed has been sent out, it's finished even if frozen
+        assert_ok!(XAssets::freeze(a, btc_token.clone()));
+        assert_ok!(XRecords::withdrawal_finish(numbers[0]));
+        assert_eq!(
+            XAssets::asset_balance_of(&a, &btc_token, AssetType::ReservedWithdrawal),
+            30
+        );
+
+        // and it could still be revoked back to free
+        assert_ok!(XRecords::withdrawal_revoke_by_trustee(numbers[1]));
+        assert_eq!(XAssets::free_balance_of(&a, &btc_token), 50);
+        assert_eq!(
+            XAssets::asset_balance_of(&a, &btc_token, AssetType::ReservedWithdrawal),
+            0
+        );
+    })
+}
+
 #[test]
 fn test_withdrawal_larger() {
     with_externalities(&mut new_test_ext(), || {
@@ -47,6 +47,10 @@ decl_storage! {
         // xassets
         config(pcx): (xassets::Token, xassets::Precision, xassets::Desc);
         config(asset_list): Vec<(xassets::Asset, bool, bool)>;
+        // lock the balances allocated in genesis, (who, token, value, start, cliff, period)
+        config(vesting): Vec<(T::AccountId, xassets::Token, T::Balance, T::BlockNumber, T::BlockNumber, T::BlockNumber)>;
+        // lock the initial PCX of the team, (start, cliff, period)
+        config(team_vesting): Option<(T::BlockNumber, T::BlockNumber, T::BlockNumber)>;
 
         // xstaking
         config(intentions): Vec<(T::AccountId, T::SessionKey, T::Balance, Name, URL, Vec<u8>)>;
@@ -221,10 +225,31 @@ decl_storage! {
 
                     // After deploying the multisig for team, issue the genesis initial 20% immediately.
                     // The amount is hard-coded here. 50 * 20% = 10
-                    <xassets::Module<T>>::pcx_issue(
-                        &team_account,
-                        (10 * 10_u64.pow(pcx_precision as u32)).into()
-                    ).unwrap();
+                    let team_initial: T::Balance = (10 * 10_u64.pow(pcx_precision as u32)).into();
+                    <xassets::Module<T>>::pcx_issue(&team_account, team_initial).unwrap();
+
+                    if let Some((start, cliff, period)) = config.team_vesting {
+                        let schedule = xassets::VestingSchedule {
+                            total: team_initial,
+                            released: Zero::zero(),
+                            start,
+                            cliff,
+                            period,
+                        };
+                        xassets::Module::<T>::bootstrap_lock_vesting(&team_account, &pcx, schedule).unwrap();
+                    }
+                }
+
+                // vesting, after all the balances are allocated.
+                for (who, token, value, start, cliff, period) in config.vesting.iter() {
+                    let schedule = xassets::VestingSchedule {
+                        total: *value,
+                        released: Zero::zero(),
+                        start: *start,
+                        cliff: *cliff,
+                        period: *period,
+                    };
+                    xassets::Module::<T>::bootstrap_lock_vesting(who, token, schedule).unwrap();
                 }
 
                 // xspot
@@ -15,7 +15,7 @@ mod types;
 use parity_codec::Decode;
 
 // Substrate
-use primitives::traits::MaybeDebug;
+use primitives::traits::{MaybeDebug, SaturatedConversion};
 use rstd::{prelude::*, result};
 use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
 use system::ensure_signed;
@@ -36,23 +36,26 @@ use xsupport::{trustees, u8array_to_addr};
 // light-bitcoin
 use btc_chain::{BlockHeader, Transaction};
 use btc_keys::{Address as BitcoinAddress, DisplayLayout, Error as AddressError, Public};
-use btc_primitives::H256;
-pub use btc_primitives::H264;
+pub use btc_primitives::{H256, H264};
+use btc_script::Script;
 use btc_ser::{deserialize, Reader};
 
 pub use self::traits::RelayTransaction;
 use self::tx::handler::remove_pending_deposit;
 #[cfg(feature = "std")]
 use self::tx::utils::addr2vecu8;
-use self::tx::utils::{get_sig_num, get_trustee_address_pair, trustee_session};
+use self::tx::utils::{
+    equal_addr, get_last_trustee_address_pair, get_sig_num, get_trustee_address_pair, is_key,
+    trustee_session,
+};
 use self::tx::{
     check_withdraw_tx, create_multi_address, detect_transaction_type, handle_tx,
     insert_trustee_vote_state, parse_and_check_signed_tx, validate_transaction,
 };
 use self::types::DepositCache;
 pub use self::types::{
-    BlockHeaderInfo, Params, RelayTx, TrusteeAddrInfo, TxInfo, TxType, VoteResult,
-    WithdrawalProposal,
+    BlockHeaderInfo, Params, RelayTx, TrusteeAddrInfo, TrusteeMigration, TrusteeSignStats, TxInfo,
+    TxType, VoteResult, WithdrawalProposal,
 };
 
 pub use self::lockup::types::LockupRelayTx;
@@ -92,6 +95,12 @@ decl_event!(
         WithdrawalFatalErr(Vec<u8>, Vec<u8>),
         /// reject_count, sum_count, withdrawal id list
         DropWithdrawalProposal(u32, u32, Vec<u32>),
+        /// trustees who missed the signing deadline of current withdrawal proposal
+        MissWithdrawalProposal(Vec<AccountId>),
+        /// trustee session number, migration tx hash, migrated value
+        TrusteeMigration(u32, H256, u64),
+        /// the fund migration of the trustee session is confirmed
+        TrusteeMigrationConfirmed(u32),
     }
 );
 
@@ -115,6 +124,24 @@ decl_storage! {
         pub PendingDepositMap get(pending_deposit): map BitcoinAddress => Option<Vec<DepositCache>>;
         /// withdrawal tx outs for account, tx_hash => outs ( out index => withdrawal account )
         pub CurrentWithdrawalProposal get(withdrawal_proposal): Option<WithdrawalProposal<T::AccountId>>;
+        /// trustees should sign or reject current withdrawal proposal before this height
+        pub WithdrawalProposalDeadline get(withdrawal_proposal_deadline): Option<T::BlockNumber>;
+        /// trustee session in which current withdrawal proposal is created, its trustees should sign it
+        pub WithdrawalProposalSession get(withdrawal_proposal_session): Option<u32>;
+        /// blocks for trustees to sign or reject a withdrawal proposal, default is one day
+        pub SignWithdrawalDuration get(sign_withdrawal_duration): T::BlockNumber = T::BlockNumber::saturated_from::<u64>(43_200);
+        /// signing statistics for trustees
+        pub TrusteeSignStatsOf get(trustee_sign_stats_of): map T::AccountId => TrusteeSignStats;
+        /// relayed utxos held by trustee addresses, (tx hash, output index) => (trustee addr, value)
+        pub TrusteeUtxoOf get(trustee_utxo_of): map (H256, u32) => Option<(BitcoinAddress, u64)>;
+        /// total value of the relayed utxos held by each trustee address
+        pub TrusteeAddrBalanceOf get(trustee_addr_balance_of): map BitcoinAddress => u64;
+        /// fund migration into each trustee session from the last session
+        pub TrusteeMigrationOf get(trustee_migration_of): map u32 => Option<TrusteeMigration>;
+        /// the trustee session which is waiting for the fund migration
+        pub MigratingSession get(migrating_session): Option<u32>;
+        /// trustees who missed this count of deadlines in a row would be dropped at next election, zero means never
+        pub MaxConsecutiveMissed get(max_consecutive_missed): u32 = 3;
 
         /// get GenesisInfo (header, height)
         pub GenesisInfo get(genesis_info) config(genesis): (BlockHeader, u32);
@@ -186,6 +213,10 @@ decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         fn deposit_event<T>() = default;
 
+        fn on_finalize(now: T::BlockNumber) {
+            Self::check_withdrawal_deadline(now);
+        }
+
         /// if use `BlockHeader` struct would export in metadata, cause complex in front-end
         pub fn push_header(origin, header: Vec<u8>) -> Result {
             let _from = ensure_signed(origin)?;
@@ -262,11 +293,31 @@ decl_module! {
             Ok(())
         }
 
+        /// seed the trustee utxos relayed before they are tracked, e.g. at the runtime upgrade
+        pub fn set_trustee_utxos(utxos: Vec<(H256, u32, BitcoinAddress, u64)>) {
+            Self::seed_trustee_utxos(utxos);
+        }
+
+        /// confirm the fund migration by hand, e.g. the remaining is spent as tx fee
+        pub fn confirm_trustee_migration() -> Result {
+            let session_number = Self::migrating_session().ok_or("no trustee migration is ongoing")?;
+            Self::apply_confirm_trustee_migration(session_number);
+            Ok(())
+        }
+
         pub fn set_btc_withdrawal_fee(fee: T::Balance) -> Result {
             BtcWithdrawalFee::<T>::put(fee.into());
             Ok(())
         }
 
+        pub fn set_sign_withdrawal_duration(duration: T::BlockNumber) {
+            SignWithdrawalDuration::<T>::put(duration);
+        }
+
+        pub fn set_max_consecutive_missed(count: u32) {
+            MaxConsecutiveMissed::<T>::put(count);
+        }
+
         pub fn set_btc_deposit_limit(value: T::Balance) {
             BtcMinDeposit::<T>::put(value.into());
         }
@@ -458,6 +509,239 @@ impl<T: Trait> Module<T> {
         BitcoinAddress::from_layout(&r)
     }
 
+    /// start tracking the fund migration into the new trustee session
+    /// from the addresses of the last session
+    pub fn start_trustee_migration(session_number: u32, last_addrs: &[BitcoinAddress]) {
+        let to_migrate: u64 = last_addrs
+            .iter()
+            .map(|addr| Self::trustee_addr_balance_of(addr))
+            .sum();
+        info!(
+            "[start_trustee_migration]|session number:{:}|to migrate:{:}",
+            session_number, to_migrate
+        );
+        TrusteeMigrationOf::<T>::insert(
+            session_number,
+            TrusteeMigration {
+                to_migrate,
+                ..Default::default()
+            },
+        );
+        MigratingSession::<T>::put(session_number);
+    }
+
+    /// the session could not be retired before its fund migration is confirmed
+    pub fn ensure_trustee_migration_confirmed(session_number: u32) -> Result {
+        match Self::trustee_migration_of(session_number) {
+            Some(ref migration) if !migration.confirmed => {
+                error!(
+                    "[ensure_trustee_migration_confirmed]|the fund migration is not confirmed yet|session number:{:}|remaining:{:}",
+                    session_number,
+                    migration.remaining()
+                );
+                Err("the fund migration of current trustee session is not confirmed yet")
+            }
+            _ => Ok(()),
+        }
+    }
+
+    fn seed_trustee_utxos(utxos: Vec<(H256, u32, BitcoinAddress, u64)>) {
+        // the last trustee addrs have been counted when the ongoing migration started
+        let migrating_addrs = match Self::migrating_session() {
+            Some(_) => get_last_trustee_address_pair::<T>()
+                .map(|(hot_addr, cold_addr)| vec![hot_addr, cold_addr])
+                .unwrap_or_default(),
+            None => vec![],
+        };
+        for (txid, index, addr, value) in utxos {
+            let outpoint = (txid, index);
+            if TrusteeUtxoOf::<T>::exists(&outpoint) {
+                continue;
+            }
+            info!(
+                "[seed_trustee_utxos]|trustee utxo|tx_hash:{:}|index:{:}|value:{:}",
+                outpoint.0, outpoint.1, value
+            );
+            TrusteeUtxoOf::<T>::insert(&outpoint, &(addr.clone(), value));
+            TrusteeAddrBalanceOf::<T>::mutate(&addr, |balance| {
+                *balance = balance.saturating_add(value)
+            });
+            if migrating_addrs.iter().any(|a| equal_addr(a, &addr)) {
+                if let Some(session_number) = Self::migrating_session() {
+                    TrusteeMigrationOf::<T>::mutate(session_number, |migration| {
+                        if let Some(migration) = migration {
+                            migration.to_migrate += value;
+                        }
+                    });
+                }
+            }
+        }
+    }
+
+    /// total value of the tracked utxos of `addrs` spent by the tx, the outputs are net of the fee
+    fn spent_trustee_value(tx: &Transaction, addrs: &[BitcoinAddress]) -> u64 {
+        tx.inputs
+            .iter()
+            .filter_map(|input| {
+                Self::trustee_utxo_of(&(
+                    input.previous_output.hash.clone(),
+                    input.previous_output.index,
+                ))
+            })
+            .filter(|(addr, _)| addrs.iter().any(|a| equal_addr(a, addr)))
+            .map(|(_, value)| value)
+            .sum()
+    }
+
+    /// track the utxos spent from and sent to the trustee addresses by a relayed tx
+    fn update_trustee_utxos(txid: &H256, tx: &Transaction, trustee_addrs: &[BitcoinAddress]) {
+        for input in tx.inputs.iter() {
+            let outpoint = (
+                input.previous_output.hash.clone(),
+                input.previous_output.index,
+            );
+            if let Some((addr, value)) = TrusteeUtxoOf::<T>::take(&outpoint) {
+                TrusteeAddrBalanceOf::<T>::mutate(&addr, |balance| {
+                    *balance = balance.saturating_sub(value)
+                });
+            }
+        }
+        for (index, output) in tx.outputs.iter().enumerate() {
+            let script: Script = output.script_pubkey.to_vec().into();
+            if let Some(addr) = trustee_addrs.iter().find(|addr| is_key::<T>(&script, addr)) {
+                debug!(
+                    "[update_trustee_utxos]|trustee utxo|tx_hash:{:}|index:{:}|value:{:}",
+                    txid, index, output.value
+                );
+                TrusteeUtxoOf::<T>::insert(
+                    &(txid.clone(), index as u32),
+                    &(addr.clone(), output.value),
+                );
+                TrusteeAddrBalanceOf::<T>::mutate(addr, |balance| {
+                    *balance = balance.saturating_add(output.value)
+                });
+            }
+        }
+    }
+
+    fn apply_trustee_migration(txid: &H256, value: u64) {
+        let session_number = match Self::migrating_session() {
+            Some(n) => n,
+            None => {
+                warn!(
+                    "[apply_trustee_migration]|no trustee migration is ongoing|tx_hash:{:}",
+                    txid
+                );
+                return;
+            }
+        };
+        let mut migration = match Self::trustee_migration_of(session_number) {
+            Some(m) => m,
+            None => return,
+        };
+
+        migration.migrated += value;
+        migration.txids.push(txid.clone());
+        let finished = migration.remaining() == 0;
+        info!(
+            "[apply_trustee_migration]|session number:{:}|tx_hash:{:}|value:{:}|remaining:{:}",
+            session_number,
+            txid,
+            value,
+            migration.remaining()
+        );
+        TrusteeMigrationOf::<T>::insert(session_number, migration);
+        Self::deposit_event(RawEvent::TrusteeMigration(
+            session_number,
+            txid.clone(),
+            value,
+        ));
+
+        if finished {
+            Self::apply_confirm_trustee_migration(session_number);
+        }
+    }
+
+    fn apply_confirm_trustee_migration(session_number: u32) {
+        TrusteeMigrationOf::<T>::mutate(session_number, |migration| {
+            if let Some(migration) = migration {
+                migration.confirmed = true;
+            }
+        });
+        MigratingSession::<T>::kill();
+        Self::deposit_event(RawEvent::TrusteeMigrationConfirmed(session_number));
+    }
+
+    /// whether the trustee has missed too many signing deadlines in a row
+    pub fn is_absent_trustee(who: &T::AccountId) -> bool {
+        let max = Self::max_consecutive_missed();
+        max != 0 && Self::trustee_sign_stats_of(who).consecutive_missed >= max
+    }
+
+    pub fn clear_consecutive_missed(who: &T::AccountId) {
+        TrusteeSignStatsOf::<T>::mutate(who, |stats| stats.consecutive_missed = 0);
+    }
+
+    /// record the trustees who neither signed nor rejected current proposal before the deadline
+    fn check_withdrawal_deadline(now: T::BlockNumber) {
+        let proposal = match Self::take_expired_withdrawal_proposal(now) {
+            Some(proposal) => proposal,
+            None => return,
+        };
+        // the trustees of a newer session are not charged for the proposal of the last one
+        let session = match WithdrawalProposalSession::<T>::take() {
+            Some(number) => T::TrusteeSessionProvider::trustee_session(number),
+            None => trustee_session::<T>(),
+        };
+        match session {
+            Ok(info) => Self::record_missed_trustees(&info.trustee_list, &proposal),
+            Err(_) => error!("[check_withdrawal_deadline]|trustee session not found"),
+        }
+    }
+
+    /// return current proposal if its deadline has passed, the deadline is removed so that
+    /// the missed trustees are only counted once for a proposal
+    fn take_expired_withdrawal_proposal(
+        now: T::BlockNumber,
+    ) -> Option<WithdrawalProposal<T::AccountId>> {
+        let deadline = Self::withdrawal_proposal_deadline()?;
+        let proposal =
+            match Self::withdrawal_proposal().filter(|p| p.sig_state == VoteResult::Unfinish) {
+                Some(proposal) => proposal,
+                None => {
+                    WithdrawalProposalDeadline::<T>::kill();
+                    return None;
+                }
+            };
+        if now < deadline {
+            return None;
+        }
+        WithdrawalProposalDeadline::<T>::kill();
+        Some(proposal)
+    }
+
+    fn record_missed_trustees(
+        trustee_list: &[T::AccountId],
+        proposal: &WithdrawalProposal<T::AccountId>,
+    ) {
+        let absent = trustee_list
+            .iter()
+            .filter(|who| !proposal.trustee_list.iter().any(|(voter, _)| voter == *who))
+            .cloned()
+            .collect::<Vec<_>>();
+        for who in absent.iter() {
+            TrusteeSignStatsOf::<T>::mutate(who, |stats| {
+                stats.missed += 1;
+                stats.consecutive_missed += 1;
+            });
+        }
+        warn!(
+            "[record_missed_trustees]|trustees missed the signing deadline|absent:{:?}",
+            absent
+        );
+        Self::deposit_event(RawEvent::MissWithdrawalProposal(absent));
+    }
+
     fn ensure_trustee(who: &T::AccountId) -> Result {
         let trustee_session_info = trustee_session::<T>()?;
         if trustee_session_info.trustee_list.iter().any(|n| n == who) {
@@ -698,6 +982,12 @@ impl<T: Trait> Module<T> {
         }
 
         CurrentWithdrawalProposal::<T>::put(proposal);
+        WithdrawalProposalDeadline::<T>::put(
+            system::Module::<T>::block_number() + Self::sign_withdrawal_duration(),
+        );
+        WithdrawalProposalSession::<T>::put(
+            T::TrusteeSessionProvider::current_trustee_session_number(),
+        );
 
         Ok(())
     }
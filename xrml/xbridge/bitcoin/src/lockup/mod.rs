@@ -3,15 +3,16 @@ pub mod types;
 use parity_codec::Decode;
 // substrate
 use primitives::traits::MaybeDebug;
-use rstd::{prelude::*, result};
+use rstd::{cmp, prelude::*, result};
 use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
 use system::ensure_signed;
 
 // light-bitcoin
 use btc_chain::Transaction;
-use btc_keys::{Address as BitcoinAddress, Network};
+use btc_crypto::dhash160;
+use btc_keys::{Address as BitcoinAddress, Network, Type};
 use btc_primitives::H256;
-use btc_script::Script;
+use btc_script::{Opcode, Script};
 
 use xassets::{Chain, ChainT};
 use xbridge_common::traits::{CrossChainBindingV2, Extractable};
@@ -27,7 +28,18 @@ use crate::tx::utils::{
 use crate::types::TxType;
 use crate::{Module as XBitcoin, Trait as XBitcoinTrait};
 
-use self::types::LockupRelayTx;
+use self::types::{LockupRelayTx, LockupStatistics, LockupTimeLock, TimeLock, TimeLockKind};
+
+/// `OP_CHECKLOCKTIMEVERIFY` value lower than this is a block height, otherwise a timestamp
+const LOCKTIME_THRESHOLD: u32 = 500_000_000;
+/// `OP_CHECKSEQUENCEVERIFY` flags, see BIP68
+const SEQUENCE_LOCKTIME_DISABLE_FLAG: u32 = 1 << 31;
+const SEQUENCE_LOCKTIME_TYPE_FLAG: u32 = 1 << 22;
+const SEQUENCE_LOCKTIME_MASK: u32 = 0x0000_ffff;
+/// max count of tiers in `LockDurationBonus`
+const MAX_LOCK_BONUS_TIERS: usize = 16;
+/// max bonus per mille of a tier in `LockDurationBonus`
+const MAX_LOCK_BONUS_PER_MILLE: u32 = 100;
 
 pub trait Trait: XBitcoinTrait + xbridge_common::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
@@ -42,6 +54,12 @@ decl_event!(
         Unlock(H256, u32, H256, u32),
         /// use root to unlock UTXO
         UnlockedFromRoot(H256, u32),
+        /// p2sh addr, time lock kind, time lock value
+        RegisterTimeLock(Vec<u8>, TimeLockKind, u32),
+        /// accountid, txid, txoutput_index, unlock_height, PCX bonus
+        TimeLock(AccountId, H256, u32, u32, u64),
+        /// accountid, txid, txoutput_index, PCX bonus taken back for the early released UTXO
+        ReclaimBonus(AccountId, H256, u32, u64),
     }
 );
 
@@ -64,9 +82,28 @@ decl_module! {
             Ok(())
         }
 
+        /// register a CLTV/CSV redeem script, the P2SH addr of it could be used as a time-locked lock output
+        pub fn register_timelock_script(origin, redeem_script: Vec<u8>) -> Result {
+            let from = ensure_signed(origin)?;
+            let script: Script = redeem_script.into();
+            let timelock = parse_timelock_script(&script).ok_or("not a valid time lock redeem script")?;
+
+            let addr = BitcoinAddress {
+                kind: Type::P2SH,
+                network: get_networkid::<T>(),
+                hash: dhash160(&script),
+            };
+            debug!("[register_timelock_script]|from:{:?}|addr:{:?}|timelock:{:?}", from, addr, timelock);
+
+            TimeLockFor::<T>::insert(&addr, timelock);
+            Self::deposit_event(RawEvent::RegisterTimeLock(addr2vecu8(&addr), timelock.kind, timelock.value));
+            Ok(())
+        }
+
         pub fn release_lock(utxos: Vec<(H256, u32)>) {
+            let height = current_btc_height::<T>();
             for utxo in utxos {
-                if destroy_utxo::<T>(utxo.0, utxo.1) {
+                if destroy_utxo::<T>(utxo.0, utxo.1, height) {
                     Self::deposit_event(RawEvent::UnlockedFromRoot(utxo.0, utxo.1));
                 }
             }
@@ -76,6 +113,47 @@ decl_module! {
             LockedCoinLimit::<T>::put(&limit);
             info!("[set_locked_coin_limit]|set new lockup bitoin limit to:{:?}", limit);
         }
+
+        /// add UTXOs locked before account index exists into the index and statistics
+        pub fn index_locked_utxo(utxos: Vec<(H256, u32)>) {
+            for utxo in utxos {
+                if let Some((accountid, value, _)) = Self::locked_up_btc(&utxo) {
+                    if Self::account_locked_utxo(&accountid).contains(&utxo) {
+                        continue;
+                    }
+                    let timelock = Self::locked_up_btc_timelock(&utxo);
+                    add_to_index::<T>(&accountid, utxo, value, timelock);
+                    info!("[index_locked_utxo]|index utxo|who:{:?}|utxo:{:?}", accountid, utxo);
+                }
+            }
+        }
+
+        pub fn set_min_lock_duration(duration: u32) {
+            MinLockDuration::<T>::put(duration);
+            info!("[set_min_lock_duration]|set new min lock duration to:{:}", duration);
+        }
+
+        /// tiers of (lock duration in bitcoin blocks, PCX bonus per mille of the locked value)
+        pub fn set_lock_duration_bonus(tiers: Vec<(u32, u32)>) -> Result {
+            if tiers.len() > MAX_LOCK_BONUS_TIERS {
+                return Err("too many lock duration bonus tiers");
+            }
+            if tiers.iter().any(|(_, per_mille)| *per_mille > MAX_LOCK_BONUS_PER_MILLE) {
+                return Err("lock duration bonus per mille is too large");
+            }
+            let mut tiers = tiers;
+            tiers.sort();
+            tiers.dedup_by_key(|(duration, _)| *duration);
+            LockDurationBonus::<T>::put(&tiers);
+            info!("[set_lock_duration_bonus]|set new lock duration bonus to:{:?}", tiers);
+            Ok(())
+        }
+
+        /// set the remaining PCX which could be paid from the council account as the lock duration bonus
+        pub fn set_lock_bonus_budget(budget: u64) {
+            LockBonusBudget::<T>::put(budget);
+            info!("[set_lock_bonus_budget]|set new lock bonus budget to:{:}", budget);
+        }
     }
 }
 
@@ -89,6 +167,22 @@ decl_storage! {
 
         /// single addr and ont output limit coin value, default limit is 0.01 BTC ~ 10 BTC
         pub LockedCoinLimit get(locked_coin_limit): (u64, u64) = (1*1000000, 10*100000000);
+
+        /// time lock of registered redeem script, P2SH addr => time lock
+        pub TimeLockFor get(timelock_for): map BitcoinAddress => Option<TimeLock>;
+        /// time lock info for locked up UTXO, only exist for time-locked lockup
+        pub LockedUpBTCTimeLock get(locked_up_btc_timelock): map (H256, u32) => Option<LockupTimeLock>;
+        /// lock output which would be unlocked in less than this count of bitcoin blocks is not treated as time-locked
+        pub MinLockDuration get(min_lock_duration): u32 = 4320;
+        /// sorted tiers of (lock duration, PCX bonus per mille) for time-locked lockup, the longest matched tier is used
+        pub LockDurationBonus get(lock_duration_bonus): Vec<(u32, u32)>;
+        /// remaining PCX for the lock duration bonus, the bonus is paid from the council account
+        pub LockBonusBudget get(lock_bonus_budget): u64;
+
+        /// locked UTXO list for an account
+        pub AccountLockedUTXO get(account_locked_utxo): map T::AccountId => Vec<(H256, u32)>;
+        /// global statistics for lockup
+        pub LockupStats get(lockup_stats): LockupStatistics;
     }
 }
 
@@ -198,9 +292,12 @@ pub fn detect_unlock_tx<T: Trait>(tx: &Transaction) -> bool {
 }
 
 pub fn handle_lockup_tx<T: Trait>(tx_handle: &TxHandler) -> Result {
-    match tx_handle.tx_info.tx_type {
-        TxType::Lock => handle_lock_tx::<T>(&tx_handle.tx_info.raw_tx, &tx_handle.tx_hash)?,
-        TxType::Unlock => handle_unlock_tx::<T>(&tx_handle.tx_info.raw_tx, &tx_handle.tx_hash),
+    let tx_info = &tx_handle.tx_info;
+    match tx_info.tx_type {
+        TxType::Lock => handle_lock_tx::<T>(&tx_info.raw_tx, &tx_handle.tx_hash, tx_info.height)?,
+        TxType::Unlock => {
+            handle_unlock_tx::<T>(&tx_info.raw_tx, &tx_handle.tx_hash, tx_info.height)
+        }
         _ => panic!("[handle_lockup_tx]|should not handle type expect `Lock`|`Unlock`"),
     }
     Ok(())
@@ -209,6 +306,7 @@ pub fn handle_lockup_tx<T: Trait>(tx_handle: &TxHandler) -> Result {
 pub(crate) fn handle_lock_tx<T: Trait>(
     tx: &Transaction,
     tx_hash: &H256,
+    height: u32,
 ) -> result::Result<(), &'static str> {
     let network = get_networkid::<T>();
     let addr_type = xsystem::Module::<T>::address_type();
@@ -234,11 +332,13 @@ pub(crate) fn handle_lock_tx<T: Trait>(
 
     // try to unlock tx before new issue, if any error in it, just print error log
     // it's unlock and lock tx
-    handle_unlock_tx::<T>(tx, tx_hash);
+    handle_unlock_tx::<T>(tx, tx_hash, height);
 
     // new value should not more than single addr limit
     let current_value = Module::<T>::address_locked_coin(addr);
-    let addr_value = current_value + output_value;
+    let addr_value = current_value
+        .checked_add(output_value)
+        .ok_or("lock value overflow")?;
     if addr_value > value_limit.1 {
         error!("[handle_lock_tx]|lock value more than single addr limit|cur_value:{:}|try lock:{:}|addr:{:?}", current_value, output_value, addr);
         return Err("lock value more than single addr limit");
@@ -246,9 +346,19 @@ pub(crate) fn handle_lock_tx<T: Trait>(
 
     let (accountid, channel) = account_info;
     let key = (*tx_hash, out_index as u32);
+    let mut timelock = lockup_timelock::<T>(&addr, height, output_value);
+    // the bonus is paid from the council account, only the actual paid value is recorded
+    if let Some(ref mut info) = timelock {
+        info.bonus = pay_bonus::<T>(&accountid, info.bonus);
+    }
     LockedUpBTC::<T>::insert(&key, (accountid.clone(), output_value, addr));
     AddressLockedCoin::<T>::insert(addr, addr_value);
-    // issue lockup token
+    if let Some(ref info) = timelock {
+        LockedUpBTCTimeLock::<T>::insert(&key, info);
+    }
+    add_to_index::<T>(&accountid, key, output_value, timelock);
+    LockupStats::<T>::mutate(|stats| stats.lock_times += 1);
+    // issue lockup token 1:1 for the locked value
     update_binding::<T>(&accountid, channel);
     issue_token::<T>(&accountid, output_value);
 
@@ -259,15 +369,68 @@ pub(crate) fn handle_lock_tx<T: Trait>(
         out_index as u32,
         addr2vecu8(&addr),
     ));
+    if let Some(info) = timelock {
+        Module::<T>::deposit_event(RawEvent::TimeLock(
+            accountid,
+            *tx_hash,
+            out_index as u32,
+            info.unlock_height,
+            info.bonus,
+        ));
+    }
 
     Ok(())
 }
 
-pub fn handle_unlock_tx<T: Trait>(tx: &Transaction, tx_hash: &H256) {
+/// get time lock info for a lock output, only the P2SH addr of a registered time lock script
+/// which could not be spent in `MinLockDuration` is treated as time-locked
+fn lockup_timelock<T: Trait>(
+    addr: &BitcoinAddress,
+    lock_height: u32,
+    value: u64,
+) -> Option<LockupTimeLock> {
+    if addr.kind != Type::P2SH {
+        return None;
+    }
+    let timelock = Module::<T>::timelock_for(addr)?;
+    let unlock_height = timelock.unlock_height(lock_height);
+    let min_duration = Module::<T>::min_lock_duration();
+    if unlock_height < lock_height.saturating_add(min_duration) {
+        warn!("[lockup_timelock]|lock duration less than min duration, treat as normal lockup|lock height:{:}|unlock height:{:}|min duration:{:}", lock_height, unlock_height, min_duration);
+        return None;
+    }
+
+    let mut info = LockupTimeLock {
+        lock_height,
+        unlock_height,
+        bonus: 0,
+    };
+    info.bonus = cmp::min(
+        duration_bonus(&Module::<T>::lock_duration_bonus(), info.duration(), value),
+        Module::<T>::lock_bonus_budget(),
+    );
+    Some(info)
+}
+
+/// bonus for lock duration, use the longest tier that the duration reaches
+pub fn duration_bonus(tiers: &[(u32, u32)], duration: u32, value: u64) -> u64 {
+    tiers
+        .iter()
+        .filter(|(min_duration, _)| *min_duration <= duration)
+        .last()
+        .map(|(_, per_mille)| value.saturating_mul(u64::from(*per_mille)) / 1000)
+        .unwrap_or_default()
+}
+
+pub fn handle_unlock_tx<T: Trait>(tx: &Transaction, tx_hash: &H256, height: u32) {
     debug!("[handle_unlock_tx]|do unlock tx|tx_hash:{:}", tx_hash);
     // delete utxo storage and destroy token
     for (index, input) in tx.inputs.iter().enumerate() {
-        if destroy_utxo::<T>(input.previous_output.hash, input.previous_output.index) {
+        if destroy_utxo::<T>(
+            input.previous_output.hash,
+            input.previous_output.index,
+            height,
+        ) {
             Module::<T>::deposit_event(RawEvent::Unlock(
                 *tx_hash,
                 index as u32,
@@ -278,7 +441,10 @@ pub fn handle_unlock_tx<T: Trait>(tx: &Transaction, tx_hash: &H256) {
     }
 }
 
-fn destroy_utxo<T: Trait>(hash: H256, index: u32) -> bool {
+/// return false when this UTXO is not locked.
+/// the PCX bonus is taken back when a time-locked UTXO is released before its unlock height,
+/// e.g. released by root.
+fn destroy_utxo<T: Trait>(hash: H256, index: u32, height: u32) -> bool {
     let key = (hash, index);
     if let Some((accountid, value, addr)) = LockedUpBTC::<T>::take(&key) {
         let addr_value = AddressLockedCoin::<T>::take(&addr);
@@ -291,13 +457,166 @@ fn destroy_utxo<T: Trait>(hash: H256, index: u32) -> bool {
             "[destroy_utxo]|unlock utxo|tx_hash:{:}|index:{:}",
             hash, index
         );
+        let timelock = LockedUpBTCTimeLock::<T>::take(&key);
+        if let Some(info) = timelock {
+            if height < info.unlock_height {
+                let reclaimed = reclaim_bonus::<T>(&accountid, info.bonus);
+                Module::<T>::deposit_event(RawEvent::ReclaimBonus(
+                    accountid.clone(),
+                    hash,
+                    index,
+                    reclaimed,
+                ));
+            }
+        }
         destroy_token::<T>(&accountid, value);
+        remove_from_index::<T>(&accountid, key, value, timelock);
+
+        LockupStats::<T>::mutate(|stats| stats.unlock_times += 1);
         true
     } else {
         false
     }
 }
 
+fn add_to_index<T: Trait>(
+    who: &T::AccountId,
+    utxo: (H256, u32),
+    value: u64,
+    timelock: Option<LockupTimeLock>,
+) {
+    let mut utxo_list = Module::<T>::account_locked_utxo(who);
+    let new_account = utxo_list.is_empty();
+    utxo_list.push(utxo);
+    AccountLockedUTXO::<T>::insert(who, utxo_list);
+
+    LockupStats::<T>::mutate(|stats| {
+        stats.total_locked = stats.total_locked.saturating_add(value);
+        stats.utxo_count += 1;
+        if let Some(info) = timelock {
+            stats.total_bonus = stats.total_bonus.saturating_add(info.bonus);
+            stats.timelock_count += 1;
+        }
+        if new_account {
+            stats.account_count += 1;
+        }
+    });
+}
+
+fn remove_from_index<T: Trait>(
+    who: &T::AccountId,
+    utxo: (H256, u32),
+    value: u64,
+    timelock: Option<LockupTimeLock>,
+) {
+    let mut utxo_list = Module::<T>::account_locked_utxo(who);
+    // UTXO locked before the index exists may not in the list
+    if !utxo_list.contains(&utxo) {
+        return;
+    }
+    utxo_list.retain(|item| *item != utxo);
+    let empty = utxo_list.is_empty();
+    if empty {
+        AccountLockedUTXO::<T>::remove(who);
+    } else {
+        AccountLockedUTXO::<T>::insert(who, utxo_list);
+    }
+
+    LockupStats::<T>::mutate(|stats| {
+        stats.total_locked = stats.total_locked.saturating_sub(value);
+        stats.utxo_count = stats.utxo_count.saturating_sub(1);
+        if let Some(info) = timelock {
+            stats.total_bonus = stats.total_bonus.saturating_sub(info.bonus);
+            stats.timelock_count = stats.timelock_count.saturating_sub(1);
+        }
+        if empty {
+            stats.account_count = stats.account_count.saturating_sub(1);
+        }
+    });
+}
+
+/// parse a time lock redeem script, the script should be like:
+/// `<n> OP_CHECKLOCKTIMEVERIFY|OP_CHECKSEQUENCEVERIFY OP_DROP <unlock script>`
+/// n must be a bitcoin block height(for CLTV) or block count(for CSV), and the unlock script
+/// should not contain any branch, so that there is no other path to spend it before unlock height
+pub fn parse_timelock_script(script: &Script) -> Option<TimeLock> {
+    let mut instructions = Vec::new();
+    for instruction in script.iter() {
+        match instruction {
+            Ok(i) => instructions.push(i),
+            Err(_e) => {
+                warn!("[parse_timelock_script]|invalid script|err:{:?}", _e);
+                return None;
+            }
+        }
+    }
+    if instructions.len() < 4 {
+        return None;
+    }
+
+    let value = if instructions[0].opcode.is_within_op_n() {
+        u32::from(instructions[0].opcode.decode_op_n())
+    } else {
+        instructions[0].data.and_then(decode_script_num)?
+    };
+    let kind = match instructions[1].opcode {
+        Opcode::OP_CHECKLOCKTIMEVERIFY => {
+            if value >= LOCKTIME_THRESHOLD {
+                warn!(
+                    "[parse_timelock_script]|only allow block height for CLTV|value:{:}",
+                    value
+                );
+                return None;
+            }
+            TimeLockKind::Absolute
+        }
+        Opcode::OP_CHECKSEQUENCEVERIFY => {
+            if value & (SEQUENCE_LOCKTIME_DISABLE_FLAG | SEQUENCE_LOCKTIME_TYPE_FLAG) != 0
+                || value & !SEQUENCE_LOCKTIME_MASK != 0
+            {
+                warn!(
+                    "[parse_timelock_script]|only allow block count for CSV|value:{:}",
+                    value
+                );
+                return None;
+            }
+            TimeLockKind::Relative
+        }
+        _ => return None,
+    };
+    if instructions[2].opcode != Opcode::OP_DROP {
+        return None;
+    }
+    let has_branch = instructions[3..].iter().any(|i| match i.opcode {
+        Opcode::OP_IF | Opcode::OP_NOTIF | Opcode::OP_ELSE | Opcode::OP_ENDIF => true,
+        _ => false,
+    });
+    if has_branch {
+        warn!("[parse_timelock_script]|time lock script should not contain any branch");
+        return None;
+    }
+    Some(TimeLock { kind, value })
+}
+
+/// decode a positive script number (little endian, sign bit in the last byte), at most 5 bytes
+fn decode_script_num(data: &[u8]) -> Option<u32> {
+    if data.is_empty() || data.len() > 5 {
+        return None;
+    }
+    if data[data.len() - 1] & 0x80 != 0 {
+        // negative
+        return None;
+    }
+    let mut v: u64 = 0;
+    for (i, b) in data.iter().enumerate() {
+        v |= u64::from(*b) << (8 * i);
+    }
+    if v > u64::from(u32::max_value()) {
+        return None;
+    }
+    Some(v as u32)
+}
+
 fn parse_lock_info<AccountId, F>(
     tx: &Transaction,
     network: Network,
@@ -420,6 +739,70 @@ fn issue_token<T: Trait>(who: &T::AccountId, balance: u64) {
     });
 }
 
+/// pay the lock duration bonus from the council account within `LockBonusBudget`,
+/// return the actual paid value
+fn pay_bonus<T: Trait>(who: &T::AccountId, bonus: u64) -> u64 {
+    let bonus = cmp::min(bonus, Module::<T>::lock_bonus_budget());
+    if bonus == 0 {
+        return 0;
+    }
+    let council = xaccounts::Module::<T>::council_account();
+    match xassets::Module::<T>::pcx_move_free_balance(&council, who, bonus.into()) {
+        Ok(()) => {
+            LockBonusBudget::<T>::mutate(|budget| *budget = budget.saturating_sub(bonus));
+            bonus
+        }
+        Err(_e) => {
+            error!(
+                "[pay_bonus]|pay lock duration bonus from council failed|who:{:?}|bonus:{:}|err:{:}",
+                who,
+                bonus,
+                _e.info()
+            );
+            0
+        }
+    }
+}
+
+/// take back the bonus to the council account, at most the free PCX of the account,
+/// the reclaimed value is returned to `LockBonusBudget`
+fn reclaim_bonus<T: Trait>(who: &T::AccountId, bonus: u64) -> u64 {
+    let free: u64 = xassets::Module::<T>::pcx_free_balance(who).into();
+    let reclaimed = cmp::min(bonus, free);
+    if reclaimed < bonus {
+        warn!(
+            "[reclaim_bonus]|free PCX is not enough to take back all the bonus|who:{:?}|bonus:{:}|free:{:}",
+            who, bonus, free
+        );
+    }
+    if reclaimed == 0 {
+        return 0;
+    }
+    let council = xaccounts::Module::<T>::council_account();
+    match xassets::Module::<T>::pcx_move_free_balance(who, &council, reclaimed.into()) {
+        Ok(()) => {
+            LockBonusBudget::<T>::mutate(|budget| *budget = budget.saturating_add(reclaimed));
+            reclaimed
+        }
+        Err(_e) => {
+            error!(
+                "[reclaim_bonus]|take back lock duration bonus failed|who:{:?}|bonus:{:}|err:{:}",
+                who,
+                reclaimed,
+                _e.info()
+            );
+            0
+        }
+    }
+}
+
+/// height of the best bitcoin header in ChainX
+fn current_btc_height<T: Trait>() -> u32 {
+    XBitcoin::<T>::block_header_for(XBitcoin::<T>::best_index())
+        .map(|info| info.height)
+        .unwrap_or_default()
+}
+
 fn destroy_token<T: Trait>(who: &T::AccountId, balance: u64) {
     // notice this `Module` is LockupModule
     let token: xassets::Token = <Module<T> as xassets::ChainT>::TOKEN.to_vec();
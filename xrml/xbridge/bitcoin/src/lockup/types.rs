@@ -2,6 +2,8 @@ use btc_chain::Transaction;
 use btc_primitives::H256;
 use merkle::PartialMerkleTree;
 use parity_codec::{Decode, Encode};
+#[cfg(feature = "std")]
+use serde_derive::{Deserialize, Serialize};
 
 use crate::traits::RelayTransaction;
 
@@ -27,3 +29,78 @@ impl RelayTransaction for LockupRelayTx {
         None
     }
 }
+
+/// the kind of time lock in a lockup redeem script
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+pub enum TimeLockKind {
+    /// `OP_CHECKLOCKTIMEVERIFY`, value is an absolute bitcoin block height
+    Absolute,
+    /// `OP_CHECKSEQUENCEVERIFY`, value is a count of bitcoin blocks after the lock tx
+    Relative,
+}
+
+impl Default for TimeLockKind {
+    fn default() -> Self {
+        TimeLockKind::Absolute
+    }
+}
+
+/// time lock parsed from a registered redeem script
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TimeLock {
+    pub kind: TimeLockKind,
+    pub value: u32,
+}
+
+impl TimeLock {
+    /// the bitcoin height since which the locked output could be spent
+    pub fn unlock_height(&self, lock_height: u32) -> u32 {
+        match self.kind {
+            TimeLockKind::Absolute => self.value,
+            TimeLockKind::Relative => lock_height.saturating_add(self.value),
+        }
+    }
+}
+
+/// time lock info for a locked UTXO, stored alongside `LockedUpBTC`
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LockupTimeLock {
+    /// bitcoin height of the lock tx
+    pub lock_height: u32,
+    /// bitcoin height since which the UTXO could be spent
+    pub unlock_height: u32,
+    /// PCX paid from the council account as the bonus for the lock duration
+    pub bonus: u64,
+}
+
+impl LockupTimeLock {
+    pub fn duration(&self) -> u32 {
+        self.unlock_height.saturating_sub(self.lock_height)
+    }
+}
+
+/// global statistics for lockup
+#[derive(PartialEq, Eq, Clone, Copy, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct LockupStatistics {
+    /// sum value of current locked UTXO
+    pub total_locked: u64,
+    /// sum PCX bonus of current time-locked UTXO
+    pub total_bonus: u64,
+    /// count of current locked UTXO
+    pub utxo_count: u32,
+    /// count of current time-locked UTXO
+    pub timelock_count: u32,
+    /// count of accounts which has locked UTXO
+    pub account_count: u32,
+    /// count of all lock in history
+    pub lock_times: u64,
+    /// count of all unlock in history
+    pub unlock_times: u64,
+}
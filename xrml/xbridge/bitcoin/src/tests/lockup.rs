@@ -1,8 +1,28 @@
 use super::*;
 
-use crate::lockup::{detect_lockup_type, handle_lock_tx};
+use crate::lockup::types::{LockupTimeLock, TimeLock, TimeLockKind};
+use crate::lockup::{detect_lockup_type, duration_bonus, handle_lock_tx, parse_timelock_script};
 use crate::types::TxType;
 
+fn init_lockup_asset() {
+    use rstd::collections::btree_map::BTreeMap;
+    let asset = xassets::Asset::new(
+        XBridgeOfBTCLockup::TOKEN.to_vec(),
+        b"Locked Bitcoin".to_vec(),
+        xassets::Chain::Bitcoin,
+        8,
+        b"test".to_vec(),
+    )
+    .unwrap();
+    assert_eq!(XAssets::register_asset(asset, true, true), Ok(()));
+    let mut props: BTreeMap<xassets::AssetLimit, bool> = BTreeMap::new();
+    props.insert(xassets::AssetLimit::CanMove, false);
+    props.insert(xassets::AssetLimit::CanTransfer, false);
+    props.insert(xassets::AssetLimit::CanWithdraw, false);
+    props.insert(xassets::AssetLimit::CanDestroyWithdrawal, false);
+    XAssets::set_asset_limit_props(XBridgeOfBTCLockup::TOKEN.to_vec(), props).unwrap();
+}
+
 #[test]
 fn test_detect_tx_type() {
     with_externalities(&mut new_test_mainnet(), || {
@@ -101,7 +121,7 @@ fn test_normal() {
         assert_eq!(detect_lockup_type::<Test>(&trd_lock), TxType::Lock);
 
         // handle first lock
-        let r = handle_lock_tx::<Test>(&fst_lock, &fst_hash);
+        let r = handle_lock_tx::<Test>(&fst_lock, &fst_hash, 100);
         assert_eq!(r, Ok(()));
 
         let value = XAssets::free_balance_of(&public, &XBridgeOfBTCLockup::TOKEN.to_vec());
@@ -115,7 +135,7 @@ fn test_normal() {
         );
 
         // handle second lock
-        let r = handle_lock_tx::<Test>(&snd_lock, &snd_hash);
+        let r = handle_lock_tx::<Test>(&snd_lock, &snd_hash, 101);
         assert_eq!(r, Ok(()));
 
         let value = XAssets::free_balance_of(&public, &XBridgeOfBTCLockup::TOKEN.to_vec());
@@ -133,7 +153,7 @@ fn test_normal() {
         );
 
         // handle third lock
-        let r = handle_lock_tx::<Test>(&trd_lock, &trd_hash);
+        let r = handle_lock_tx::<Test>(&trd_lock, &trd_hash, 102);
         assert_eq!(r, Ok(()));
 
         let value = XAssets::free_balance_of(&public, &XBridgeOfBTCLockup::TOKEN.to_vec());
@@ -158,12 +178,334 @@ fn test_normal() {
             true
         );
 
+        // account index and stats
+        assert_eq!(
+            XBridgeOfBTCLockup::account_locked_utxo(&public),
+            vec![(trd_hash, 0)]
+        );
+        let stats = XBridgeOfBTCLockup::lockup_stats();
+        assert_eq!(stats.total_locked, 3415590);
+        assert_eq!(stats.utxo_count, 1);
+        assert_eq!(stats.account_count, 1);
+        assert_eq!(stats.lock_times, 3);
+        assert_eq!(stats.unlock_times, 2);
+
         // unlock
         assert_eq!(detect_lockup_type::<Test>(&fth_lock), TxType::Unlock);
-        crate::lockup::handle_unlock_tx::<Test>(&fth_lock, &fth_hash);
+        crate::lockup::handle_unlock_tx::<Test>(&fth_lock, &fth_hash, 0);
         let value = XAssets::free_balance_of(&public, &XBridgeOfBTCLockup::TOKEN.to_vec());
         assert_eq!(value, 0);
         let r = crate::lockup::AddressLockedCoin::<Test>::exists(&addr3);
         assert_eq!(r, false);
+        assert_eq!(XBridgeOfBTCLockup::account_locked_utxo(&public), vec![]);
+        let stats = XBridgeOfBTCLockup::lockup_stats();
+        assert_eq!(stats.total_locked, 0);
+        assert_eq!(stats.utxo_count, 0);
+        assert_eq!(stats.account_count, 0);
+        assert_eq!(stats.unlock_times, 3);
+    })
+}
+
+#[test]
+fn test_parse_timelock_script() {
+    let pubkey = hex!("03f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a57352616");
+    // 600000 CLTV
+    let cltv = Builder::default()
+        .push_bytes(&[0xc0, 0x27, 0x09])
+        .push_opcode(Opcode::OP_CHECKLOCKTIMEVERIFY)
+        .push_opcode(Opcode::OP_DROP)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(
+        parse_timelock_script(&cltv),
+        Some(TimeLock {
+            kind: TimeLockKind::Absolute,
+            value: 600000
+        })
+    );
+
+    // 4320 blocks CSV
+    let csv = Builder::default()
+        .push_bytes(&[0xe0, 0x10])
+        .push_opcode(Opcode::OP_CHECKSEQUENCEVERIFY)
+        .push_opcode(Opcode::OP_DROP)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(
+        parse_timelock_script(&csv),
+        Some(TimeLock {
+            kind: TimeLockKind::Relative,
+            value: 4320
+        })
+    );
+
+    // CLTV with timestamp
+    let timestamp = Builder::default()
+        .push_bytes(&[0x00, 0x65, 0xcd, 0x1d])
+        .push_opcode(Opcode::OP_CHECKLOCKTIMEVERIFY)
+        .push_opcode(Opcode::OP_DROP)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(parse_timelock_script(&timestamp), None);
+
+    // CSV with time based sequence
+    let csv_time = Builder::default()
+        .push_bytes(&[0xe0, 0x10, 0x40])
+        .push_opcode(Opcode::OP_CHECKSEQUENCEVERIFY)
+        .push_opcode(Opcode::OP_DROP)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(parse_timelock_script(&csv_time), None);
+
+    // has another branch to spend before unlock height
+    let branch = Builder::default()
+        .push_bytes(&[0xc0, 0x27, 0x09])
+        .push_opcode(Opcode::OP_CHECKLOCKTIMEVERIFY)
+        .push_opcode(Opcode::OP_DROP)
+        .push_opcode(Opcode::OP_IF)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_ELSE)
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_ENDIF)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(parse_timelock_script(&branch), None);
+
+    // normal p2pk
+    let p2pk = Builder::default()
+        .push_bytes(&pubkey)
+        .push_opcode(Opcode::OP_CHECKSIG)
+        .into_script();
+    assert_eq!(parse_timelock_script(&p2pk), None);
+}
+
+#[test]
+fn test_duration_bonus() {
+    let tiers = vec![(4320, 50), (25920, 200), (52560, 500)];
+    assert_eq!(duration_bonus(&tiers, 4319, 100000000), 0);
+    assert_eq!(duration_bonus(&tiers, 4320, 100000000), 5000000);
+    assert_eq!(duration_bonus(&tiers, 30000, 100000000), 20000000);
+    assert_eq!(duration_bonus(&tiers, 100000, 100000000), 50000000);
+    assert_eq!(duration_bonus(&[], 100000, 100000000), 0);
+}
+
+#[test]
+fn test_register_timelock_script() {
+    with_externalities(&mut new_test_mainnet(), || {
+        let origin = system::RawOrigin::Signed(Default::default()).into();
+        let pubkey = hex!("03f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a57352616");
+        let cltv = Builder::default()
+            .push_bytes(&[0xc0, 0x27, 0x09])
+            .push_opcode(Opcode::OP_CHECKLOCKTIMEVERIFY)
+            .push_opcode(Opcode::OP_DROP)
+            .push_bytes(&pubkey)
+            .push_opcode(Opcode::OP_CHECKSIG)
+            .into_script();
+        assert_eq!(
+            XBridgeOfBTCLockup::register_timelock_script(origin, cltv.to_bytes().to_vec()),
+            Ok(())
+        );
+        let addr = btc_keys::Address {
+            kind: btc_keys::Type::P2SH,
+            network: btc_keys::Network::Mainnet,
+            hash: dhash160(&cltv),
+        };
+        assert_eq!(
+            XBridgeOfBTCLockup::timelock_for(&addr),
+            Some(TimeLock {
+                kind: TimeLockKind::Absolute,
+                value: 600000
+            })
+        );
+
+        let origin = system::RawOrigin::Signed(Default::default()).into();
+        let p2pk = Builder::default()
+            .push_bytes(&pubkey)
+            .push_opcode(Opcode::OP_CHECKSIG)
+            .into_script();
+        assert_eq!(
+            XBridgeOfBTCLockup::register_timelock_script(origin, p2pk.to_bytes().to_vec()),
+            Err("not a valid time lock redeem script")
+        );
+    })
+}
+
+#[test]
+fn test_timelock() {
+    with_externalities(&mut new_test_mainnet(), || {
+        XBridgeOfBTCLockup::set_locked_coin_limit((1 * 100000, 10 * 100000000)).unwrap();
+        XBridgeOfBTCLockup::set_min_lock_duration(100).unwrap();
+        assert_eq!(
+            XBridgeOfBTCLockup::set_lock_duration_bonus(vec![(1000, 500), (100, 100)]),
+            Err("lock duration bonus per mille is too large")
+        );
+        XBridgeOfBTCLockup::set_lock_duration_bonus(vec![(1000, 100), (100, 50)]).unwrap();
+        init_lockup_asset();
+        let pcx = xassets::Asset::new(
+            XAssets::TOKEN.to_vec(),
+            b"PCX".to_vec(),
+            xassets::Chain::ChainX,
+            8,
+            b"PCX".to_vec(),
+        )
+        .unwrap();
+        assert_eq!(XAssets::register_asset(pcx, true, false), Ok(()));
+        // the bonus is paid from the council account within the budget
+        let council = xaccounts::Module::<Test>::council_account();
+        XAssets::pcx_issue(&council, 1000000).unwrap();
+        XBridgeOfBTCLockup::set_lock_bonus_budget(200000).unwrap();
+
+        let public = hex!("fa6efb5db13089b4712305e39d0a16867c6822e3b1f4c4619937ae8a21961030")
+            .unchecked_into();
+        let token = XBridgeOfBTCLockup::TOKEN.to_vec();
+
+        // 32ki -> 3CwG lock
+        let fst_lock : Transaction= "0200000000010171aa0a9c43e308bb21952737aaabd52d13b8eba217848a962ad585ebda01229400000000171600141e6ad2476469e29df17d0b29779ec74992011ec4ffffffff03c0c62d000000000017a9147b5b8cabbfaf1a62cfc36414922db9caf8bb1bee87697906000000000017a91408938266a815783a42fbefb4402fe0e2f185e308870000000000000000466a44436861696e583a35564a504b3976706a667732486a766747645835367a755469365072377647765962794e354357455a79596162793777404c616f636975733a3343774702483045022100d38981cac1e51957d2f22c17d557c630851cee92e4982949ada6f50d356e303a02201d98713931aa95c114afb20deb6c355edc6970d67312019f019c4c8670428c64012103f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a5735261600000000".into();
+        let fst_hash = fst_lock.hash();
+        let addr1 =
+            XBridgeOfBTC::verify_btc_address(b"3CwGi7JB9LMoiLfaUTbL9okXhiVbeDpygS").unwrap();
+        // 3CwG -> 32ki
+        // 35no
+        let trd_lock : Transaction= "0200000000010255b270077905084940a371c611cd5a84859c6f742ed4811748270483ca6eca870000000017160014c706e93e527a74f9b08dfb897e6667406832c6d2ffffffff694361ed63c406c1109e16a32184b4286636ecebe6a7faaced2727785b90c7a1000000001716001490ce8641913a41dee19796ea4fb54b0173971650ffffffff02261e34000000000017a9140bab8fa2ea965a0dc34b727c59b5dcfd91bc413f870000000000000000466a44436861696e583a35564a504b3976706a667732486a766747645835367a755469365072377647765962794e354357455a79596162793777404c616f636975733a33326b6902483045022100d36693c7c5019dfbe9ce2096167b1646813e7da55e031a48fcbec37b6bb529b0022013861a8c244f7a7fd750605bb6caa029a267a5ef95d629d3c1d07e613b5cb09101210201b23bcc02e8f1abc4e5860036234fd1b0e5d18e3e15d9e7f86dea9d7c4690ef02483045022100e9b42a33f69f43f7a5f1ce4b9aaa07bb2dfb2857ed23dede4c20304d2a81b91e02201b100aa94dae8520783589229dd8e2d8839aa317204fc2e98698d1f9db660135012103ae4939b53306061d9f075edee499c43393fece4632124c9a549cf67e44e20de400000000".into();
+        let trd_hash = trd_lock.hash();
+
+        // 3CwG is a registered CSV script with 500 blocks
+        crate::lockup::TimeLockFor::<Test>::insert(
+            &addr1,
+            TimeLock {
+                kind: TimeLockKind::Relative,
+                value: 500,
+            },
+        );
+
+        assert_eq!(handle_lock_tx::<Test>(&fst_lock, &fst_hash, 1000), Ok(()));
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc_timelock((fst_hash, 0)),
+            Some(LockupTimeLock {
+                lock_height: 1000,
+                unlock_height: 1500,
+                bonus: 150000,
+            })
+        );
+        // L-BTC is issued 1:1, the 5% bonus is paid in PCX from the council
+        assert_eq!(XAssets::free_balance_of(&public, &token), 3000000);
+        assert_eq!(XAssets::pcx_free_balance(&public), 150000);
+        assert_eq!(XAssets::pcx_free_balance(&council), 850000);
+        assert_eq!(XBridgeOfBTCLockup::lock_bonus_budget(), 50000);
+
+        // the PCX bonus is kept after unlock at the unlock height
+        crate::lockup::handle_unlock_tx::<Test>(&trd_lock, &trd_hash, 1500);
+        assert_eq!(XAssets::free_balance_of(&public, &token), 0);
+        assert_eq!(XAssets::pcx_free_balance(&public), 150000);
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc((fst_hash, 0)).is_some(),
+            false
+        );
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc_timelock((fst_hash, 0)).is_some(),
+            false
+        );
+        let stats = XBridgeOfBTCLockup::lockup_stats();
+        assert_eq!(stats.total_bonus, 0);
+        assert_eq!(stats.timelock_count, 0);
+        assert_eq!(stats.unlock_times, 1);
+
+        // the bonus is limited by the remaining budget
+        assert_eq!(handle_lock_tx::<Test>(&fst_lock, &fst_hash, 600000), Ok(()));
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc_timelock((fst_hash, 0)),
+            Some(LockupTimeLock {
+                lock_height: 600000,
+                unlock_height: 600500,
+                bonus: 50000,
+            })
+        );
+        assert_eq!(XAssets::pcx_free_balance(&public), 200000);
+        assert_eq!(XBridgeOfBTCLockup::lock_bonus_budget(), 0);
+
+        // the bonus is taken back when root releases it before the unlock height
+        XBridgeOfBTCLockup::release_lock(vec![(fst_hash, 0)]).unwrap();
+        assert_eq!(XAssets::free_balance_of(&public, &token), 0);
+        assert_eq!(XAssets::pcx_free_balance(&public), 150000);
+        assert_eq!(XAssets::pcx_free_balance(&council), 850000);
+        assert_eq!(XBridgeOfBTCLockup::lock_bonus_budget(), 50000);
+    })
+}
+
+#[test]
+fn test_timelock_without_bonus_budget() {
+    with_externalities(&mut new_test_mainnet(), || {
+        XBridgeOfBTCLockup::set_locked_coin_limit((1 * 100000, 10 * 100000000)).unwrap();
+        XBridgeOfBTCLockup::set_min_lock_duration(100).unwrap();
+        XBridgeOfBTCLockup::set_lock_duration_bonus(vec![(100, 50)]).unwrap();
+        init_lockup_asset();
+
+        let public = hex!("fa6efb5db13089b4712305e39d0a16867c6822e3b1f4c4619937ae8a21961030")
+            .unchecked_into();
+        let fst_lock : Transaction= "0200000000010171aa0a9c43e308bb21952737aaabd52d13b8eba217848a962ad585ebda01229400000000171600141e6ad2476469e29df17d0b29779ec74992011ec4ffffffff03c0c62d000000000017a9147b5b8cabbfaf1a62cfc36414922db9caf8bb1bee87697906000000000017a91408938266a815783a42fbefb4402fe0e2f185e308870000000000000000466a44436861696e583a35564a504b3976706a667732486a766747645835367a755469365072377647765962794e354357455a79596162793777404c616f636975733a3343774702483045022100d38981cac1e51957d2f22c17d557c630851cee92e4982949ada6f50d356e303a02201d98713931aa95c114afb20deb6c355edc6970d67312019f019c4c8670428c64012103f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a5735261600000000".into();
+        let fst_hash = fst_lock.hash();
+        let addr1 =
+            XBridgeOfBTC::verify_btc_address(b"3CwGi7JB9LMoiLfaUTbL9okXhiVbeDpygS").unwrap();
+        crate::lockup::TimeLockFor::<Test>::insert(
+            &addr1,
+            TimeLock {
+                kind: TimeLockKind::Relative,
+                value: 500,
+            },
+        );
+
+        // no PCX is issued for the bonus when there is no budget
+        assert_eq!(handle_lock_tx::<Test>(&fst_lock, &fst_hash, 1000), Ok(()));
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc_timelock((fst_hash, 0)),
+            Some(LockupTimeLock {
+                lock_height: 1000,
+                unlock_height: 1500,
+                bonus: 0,
+            })
+        );
+        assert_eq!(XAssets::pcx_free_balance(&public), 0);
+        assert_eq!(
+            XAssets::all_type_total_asset_balance(&XAssets::TOKEN.to_vec()),
+            0
+        );
+    })
+}
+
+#[test]
+fn test_timelock_less_than_min_duration() {
+    with_externalities(&mut new_test_mainnet(), || {
+        XBridgeOfBTCLockup::set_locked_coin_limit((1 * 100000, 10 * 100000000)).unwrap();
+        XBridgeOfBTCLockup::set_lock_duration_bonus(vec![(100, 100)]).unwrap();
+        init_lockup_asset();
+
+        let public = hex!("fa6efb5db13089b4712305e39d0a16867c6822e3b1f4c4619937ae8a21961030")
+            .unchecked_into();
+        let fst_lock : Transaction= "0200000000010171aa0a9c43e308bb21952737aaabd52d13b8eba217848a962ad585ebda01229400000000171600141e6ad2476469e29df17d0b29779ec74992011ec4ffffffff03c0c62d000000000017a9147b5b8cabbfaf1a62cfc36414922db9caf8bb1bee87697906000000000017a91408938266a815783a42fbefb4402fe0e2f185e308870000000000000000466a44436861696e583a35564a504b3976706a667732486a766747645835367a755469365072377647765962794e354357455a79596162793777404c616f636975733a3343774702483045022100d38981cac1e51957d2f22c17d557c630851cee92e4982949ada6f50d356e303a02201d98713931aa95c114afb20deb6c355edc6970d67312019f019c4c8670428c64012103f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a5735261600000000".into();
+        let fst_hash = fst_lock.hash();
+        let addr1 =
+            XBridgeOfBTC::verify_btc_address(b"3CwGi7JB9LMoiLfaUTbL9okXhiVbeDpygS").unwrap();
+
+        // default min duration is 4320 blocks
+        crate::lockup::TimeLockFor::<Test>::insert(
+            &addr1,
+            TimeLock {
+                kind: TimeLockKind::Absolute,
+                value: 1500,
+            },
+        );
+        assert_eq!(handle_lock_tx::<Test>(&fst_lock, &fst_hash, 1000), Ok(()));
+        assert_eq!(
+            XBridgeOfBTCLockup::locked_up_btc_timelock((fst_hash, 0)).is_some(),
+            false
+        );
+        assert_eq!(
+            XAssets::free_balance_of(&public, &XBridgeOfBTCLockup::TOKEN.to_vec()),
+            3000000
+        );
     })
 }
@@ -101,6 +101,7 @@ impl xassets::Trait for Test {
 
 impl xfee_manager::Trait for Test {
     type Event = ();
+    type AssetPrice = ();
 }
 
 impl xbridge_common::Trait for Test {
@@ -138,6 +139,10 @@ impl xbridge_common::traits::TrusteeSession<AccountId, TrusteeAddrInfo> for Dumm
         })
     }
 
+    fn current_trustee_session_number() -> u32 {
+        0
+    }
+
     fn current_trustee_session(
     ) -> std::result::Result<TrusteeSessionInfo<AccountId, TrusteeAddrInfo>, &'static str> {
         Ok(TrusteeSessionInfo {
@@ -1,4 +1,5 @@
 use super::*;
+use support::assert_ok;
 
 #[test]
 pub fn test_check_trustee_entity() {
@@ -132,3 +133,178 @@ fn test_verify_signed() {
     let r = parse_and_check_signed_tx_impl(&full_sig_tx, script);
     assert_eq!(r, Ok(2))
 }
+
+#[test]
+pub fn test_withdrawal_deadline() {
+    with_externalities(&mut new_test_ext(), || {
+        let tx: Transaction = "02000000000101730a826795953033fc391c1dd42008aa971412b7df2686cbc68938363ba54f1500000000171600141e6ad2476469e29df17d0b29779ec74992011ec4ffffffff0240420f000000000017a9140bab8fa2ea965a0dc34b727c59b5dcfd91bc413f87cfcd24000000000017a9149d0876aa518e9a21be823c598d179498cbcbbb6a8702473044022043db8986c6cb443f3d15bf0d358be87f5314ca241caaca5fd2fc6248ac38b93302206dedf4712d3536866679296aa3897de06bfeb5ad63f6d7dac7190a4ed1b6bc4d012103f165613dfa0ec1cca321423c11b10b8651ce51e4da8d463a7357cf5a5735261600000000".into();
+        let signer = AccountId::from_raw([0; 32]);
+        let rejecter = AccountId::from_raw([1; 32]);
+        let absent = AccountId::from_raw([2; 32]);
+
+        let mut proposal = WithdrawalProposal::new(VoteResult::Unfinish, vec![0], tx, Vec::new());
+        assert_ok!(insert_trustee_vote_state::<Test>(
+            true,
+            &signer,
+            &mut proposal.trustee_list
+        ));
+        assert_ok!(insert_trustee_vote_state::<Test>(
+            false,
+            &rejecter,
+            &mut proposal.trustee_list
+        ));
+        CurrentWithdrawalProposal::<Test>::put(proposal);
+        WithdrawalProposalDeadline::<Test>::put(10);
+        MaxConsecutiveMissed::<Test>::put(2);
+        let trustees = vec![signer.clone(), rejecter.clone(), absent.clone()];
+
+        assert!(XBridgeOfBTC::take_expired_withdrawal_proposal(9).is_none());
+        assert_eq!(XBridgeOfBTC::withdrawal_proposal_deadline(), Some(10));
+        let expired = XBridgeOfBTC::take_expired_withdrawal_proposal(10).unwrap();
+        XBridgeOfBTC::record_missed_trustees(&trustees, &expired);
+        assert_eq!(
+            XBridgeOfBTC::trustee_sign_stats_of(&signer),
+            TrusteeSignStats {
+                signed: 1,
+                rejected: 0,
+                missed: 0,
+                consecutive_missed: 0,
+            }
+        );
+        assert_eq!(XBridgeOfBTC::trustee_sign_stats_of(&rejecter).rejected, 1);
+        assert_eq!(XBridgeOfBTC::trustee_sign_stats_of(&absent).missed, 1);
+        assert_eq!(XBridgeOfBTC::is_absent_trustee(&absent), false);
+        // a proposal is only counted once however long it stays unfinished
+        assert_eq!(XBridgeOfBTC::withdrawal_proposal_deadline(), None);
+        assert!(XBridgeOfBTC::take_expired_withdrawal_proposal(100_000).is_none());
+        XBridgeOfBTC::check_withdrawal_deadline(100_000);
+        assert_eq!(XBridgeOfBTC::trustee_sign_stats_of(&absent).missed, 1);
+
+        // missed the next proposal too
+        WithdrawalProposalDeadline::<Test>::put(100_010);
+        let expired = XBridgeOfBTC::take_expired_withdrawal_proposal(100_010).unwrap();
+        XBridgeOfBTC::record_missed_trustees(&trustees, &expired);
+        assert_eq!(
+            XBridgeOfBTC::trustee_sign_stats_of(&absent).consecutive_missed,
+            2
+        );
+        assert_eq!(XBridgeOfBTC::is_absent_trustee(&absent), true);
+
+        XBridgeOfBTC::clear_consecutive_missed(&absent);
+        assert_eq!(XBridgeOfBTC::is_absent_trustee(&absent), false);
+        assert_eq!(XBridgeOfBTC::trustee_sign_stats_of(&absent).missed, 2);
+
+        // the session of the proposal is only used for its deadline
+        WithdrawalProposalDeadline::<Test>::put(100_020);
+        WithdrawalProposalSession::<Test>::put(0);
+        XBridgeOfBTC::check_withdrawal_deadline(100_020);
+        assert_eq!(XBridgeOfBTC::withdrawal_proposal_session(), None);
+
+        // no deadline after the proposal is removed
+        WithdrawalProposalDeadline::<Test>::put(200_000);
+        CurrentWithdrawalProposal::<Test>::kill();
+        XBridgeOfBTC::check_withdrawal_deadline(100_000);
+        assert_eq!(XBridgeOfBTC::withdrawal_proposal_deadline(), None);
+    });
+}
+
+#[test]
+pub fn test_trustee_migration() {
+    with_externalities(&mut new_test_ext(), || {
+        let hot_addr =
+            XBridgeOfBTC::verify_btc_address(b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF").unwrap();
+        let cold_addr =
+            XBridgeOfBTC::verify_btc_address(b"32kisvhbvSHWuZ76ivUiPN6dCGftMQZFpe").unwrap();
+        TrusteeAddrBalanceOf::<Test>::insert(&hot_addr, 600);
+        TrusteeAddrBalanceOf::<Test>::insert(&cold_addr, 400);
+
+        // no migration for the first session
+        assert_ok!(XBridgeOfBTC::ensure_trustee_migration_confirmed(0));
+
+        XBridgeOfBTC::start_trustee_migration(1, &[hot_addr.clone(), cold_addr.clone()]);
+        assert_eq!(XBridgeOfBTC::migrating_session(), Some(1));
+        assert_eq!(
+            XBridgeOfBTC::ensure_trustee_migration_confirmed(1),
+            Err("the fund migration of current trustee session is not confirmed yet")
+        );
+
+        let fst_tx = H256::from([1; 32]);
+        XBridgeOfBTC::apply_trustee_migration(&fst_tx, 600);
+        let migration = XBridgeOfBTC::trustee_migration_of(1).unwrap();
+        assert_eq!(migration.remaining(), 400);
+        assert_eq!(migration.confirmed, false);
+
+        let snd_tx = H256::from([2; 32]);
+        XBridgeOfBTC::apply_trustee_migration(&snd_tx, 400);
+        assert_eq!(
+            XBridgeOfBTC::trustee_migration_of(1),
+            Some(TrusteeMigration {
+                to_migrate: 1000,
+                migrated: 1000,
+                txids: vec![fst_tx, snd_tx],
+                confirmed: true,
+            })
+        );
+        assert_eq!(XBridgeOfBTC::migrating_session(), None);
+        assert_ok!(XBridgeOfBTC::ensure_trustee_migration_confirmed(1));
+
+        // the remaining is spent as fee, confirm by hand
+        XBridgeOfBTC::start_trustee_migration(2, &[hot_addr, cold_addr]);
+        XBridgeOfBTC::apply_trustee_migration(&H256::from([3; 32]), 990);
+        assert_ok!(XBridgeOfBTC::confirm_trustee_migration());
+        assert_ok!(XBridgeOfBTC::ensure_trustee_migration_confirmed(2));
+        assert_eq!(
+            XBridgeOfBTC::confirm_trustee_migration(),
+            Err("no trustee migration is ongoing")
+        );
+    });
+}
+
+#[test]
+pub fn test_trustee_migration_by_spent_utxos() {
+    with_externalities(&mut new_test_ext(), || {
+        let hot_addr =
+            XBridgeOfBTC::verify_btc_address(b"3LFSUKkP26hun42J1Dy6RATsbgmBJb27NF").unwrap();
+        let cold_addr =
+            XBridgeOfBTC::verify_btc_address(b"32kisvhbvSHWuZ76ivUiPN6dCGftMQZFpe").unwrap();
+        let other_addr =
+            XBridgeOfBTC::verify_btc_address(b"35noA4eJhfzXdz6ruxChFAiGsbkRCpwQbi").unwrap();
+        // spends 3 utxos
+        let tx: Transaction = "020000000386389a63d8e858e06236d2b8de206763f2bd858adcbc8deb03bdb1f673b0d19c040000006b483045022100a4f40ddc02bb0326f476e664ac08015e4fd157c545dc2d03933e037b0b380f0e0220653f2fc0c229d3ce73f0829b53007700d6c517d27bcfdd1ad6ebdfce4fcbf1c20121024bfe28c0f47d7913d3fbd4555a63d448529924332d76c3b66251c9cd4ffa8340000000004e82355663aae88d258871ceff235a9c743291e3b1e1f4c2db6dd0774fe8ec8d010000006a473044022030013c331cbaa3a34a827d3c6a02e9dc93a88ef8ecb63a3d33b5c3087bcb8c7702205808f28435a7f22d30bb9540bafc58f2f0a4e2c3e0e5cc6ab59a2c7fbdfd9a610121024bfe28c0f47d7913d3fbd4555a63d448529924332d76c3b66251c9cd4ffa834000000000bd9bb637bc1e3bfa6209abeb59bdfd24aa1e80d911a00762a467a2488b4ba7fd000000006b483045022100bccff95c3298dd74027e5aa65da216384754136dee8b578cd6e70c7c3d19964d022078d71696e92a41d7d228b94020035b102cc3d4958dee2357c7aeeb509561678d0121024bfe28c0f47d7913d3fbd4555a63d448529924332d76c3b66251c9cd4ffa8340000000000380d99f380000000017a914cb94110435d0635223eebe25ed2aaabc03781c4587c0d40100000000001976a9146e9557e4fce7b1bb47056e357811c51b165ff8f488ac00000000000000003a6a383551574b5a5934514174344e4338733571634a564a6e53624c534a3157396976355334694a4a5055723350646b646e6a4041786f6e6f6d7900000000".into();
+        let outpoint = |i: usize| {
+            (
+                tx.inputs[i].previous_output.hash.clone(),
+                tx.inputs[i].previous_output.index,
+            )
+        };
+
+        // the utxos relayed before the tracking are seeded by root
+        let utxos = vec![
+            (outpoint(0).0, outpoint(0).1, hot_addr.clone(), 600),
+            (outpoint(1).0, outpoint(1).1, cold_addr.clone(), 400),
+            (outpoint(2).0, outpoint(2).1, other_addr.clone(), 100),
+        ];
+        assert_ok!(XBridgeOfBTC::set_trustee_utxos(utxos.clone()));
+        // seeded only once
+        assert_ok!(XBridgeOfBTC::set_trustee_utxos(utxos));
+        assert_eq!(
+            XBridgeOfBTC::trustee_utxo_of(&outpoint(0)).map(|(_, value)| value),
+            Some(600)
+        );
+        assert_eq!(XBridgeOfBTC::trustee_addr_balance_of(&hot_addr), 600);
+        assert_eq!(XBridgeOfBTC::trustee_addr_balance_of(&cold_addr), 400);
+
+        XBridgeOfBTC::start_trustee_migration(1, &[hot_addr.clone(), cold_addr.clone()]);
+        assert_eq!(
+            XBridgeOfBTC::trustee_migration_of(1).unwrap().to_migrate,
+            1000
+        );
+
+        // the inputs of the last trustee addrs are counted, not the outputs net of the fee
+        let value = XBridgeOfBTC::spent_trustee_value(&tx, &[hot_addr, cold_addr]);
+        assert_eq!(value, 1000);
+        XBridgeOfBTC::apply_trustee_migration(&tx.hash(), value);
+        assert_eq!(XBridgeOfBTC::migrating_session(), None);
+        assert_ok!(XBridgeOfBTC::ensure_trustee_migration_confirmed(1));
+    });
+}
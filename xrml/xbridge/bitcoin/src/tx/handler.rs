@@ -25,7 +25,10 @@ use btc_script::Script;
 use crate::types::{DepositAccountInfo, DepositCache, TxInfo, TxType};
 use crate::{CurrentWithdrawalProposal, Module, PendingDepositMap, RawEvent, Trait, TxMarkFor};
 
-use super::utils::{addr2vecu8, ensure_identical, get_hot_trustee_address, is_key, parse_opreturn};
+use super::utils::{
+    addr2vecu8, ensure_identical, get_hot_trustee_address, get_last_trustee_address_pair,
+    get_trustee_address_pair, is_key, parse_opreturn,
+};
 
 use crate::lockup::{handle_lockup_tx, handle_unlock_tx};
 
@@ -67,6 +70,9 @@ impl TxHandler {
             TxType::Lock | TxType::Unlock => {
                 handle_lockup_tx::<T::XBitcoinLockup>(self)?;
             }
+            TxType::TrusteeTransition => {
+                self.trustee_transition::<T>();
+            }
             _ => {
                 info!(
                     "[handle tx]|other type tx|type:{:?}|hash:{:?}|tx:{:?}",
@@ -75,6 +81,8 @@ impl TxHandler {
             }
         };
 
+        self.update_trustee_utxos::<T>();
+
         // handle finish, mark this tx has done
         TxMarkFor::<T>::insert(&self.tx_hash, ());
 
@@ -140,6 +148,25 @@ impl TxHandler {
         Ok(())
     }
 
+    fn update_trustee_utxos<T: Trait>(&self) {
+        // the last trustee addrs still hold utxos until the migration is finished
+        let trustee_addrs = get_trustee_address_pair::<T>()
+            .into_iter()
+            .chain(get_last_trustee_address_pair::<T>().into_iter())
+            .flat_map(|(hot_addr, cold_addr)| vec![hot_addr, cold_addr])
+            .collect::<Vec<_>>();
+        Module::<T>::update_trustee_utxos(&self.tx_hash, &self.tx_info.raw_tx, &trustee_addrs);
+    }
+
+    fn trustee_transition<T: Trait>(&self) {
+        // count what is moved out of the last trustee addrs, before the spent utxos are removed
+        let last_addrs = get_last_trustee_address_pair::<T>()
+            .map(|(hot_addr, cold_addr)| vec![hot_addr, cold_addr])
+            .unwrap_or_default();
+        let value = Module::<T>::spent_trustee_value(&self.tx_info.raw_tx, &last_addrs);
+        Module::<T>::apply_trustee_migration(&self.tx_hash, value);
+    }
+
     fn deposit<T: Trait>(&self) -> Result {
         // try to get check first input for this deposit tx
         let input_addr: Option<Address> = Module::<T>::input_addr_for(&self.tx_hash);
@@ -187,7 +214,11 @@ impl TxHandler {
         // deposit
 
         // handle locked utxo, if any error in it, just print error log
-        handle_unlock_tx::<T::XBitcoinLockup>(&self.tx_info.raw_tx, &self.tx_hash);
+        handle_unlock_tx::<T::XBitcoinLockup>(
+            &self.tx_info.raw_tx,
+            &self.tx_hash,
+            self.tx_info.height,
+        );
 
         // deposit for this account or store this deposit cache
         let deposit_account = match deposit_account_info {
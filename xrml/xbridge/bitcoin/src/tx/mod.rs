@@ -20,7 +20,7 @@ use btc_script::{Builder, Opcode, Script};
 
 use crate::traits::RelayTransaction;
 use crate::types::{TrusteeAddrInfo, TxType};
-use crate::{InputAddrFor, Module, RawEvent, Trait, TxFor};
+use crate::{InputAddrFor, Module, RawEvent, Trait, TrusteeSignStatsOf, TxFor};
 
 use crate::lockup::detect_lockup_type;
 
@@ -303,6 +303,14 @@ pub fn insert_trustee_vote_state<T: Trait>(
         }
         None => {
             trustee_list.push((who.clone(), state));
+            TrusteeSignStatsOf::<T>::mutate(who, |stats| {
+                if state {
+                    stats.signed += 1;
+                } else {
+                    stats.rejected += 1;
+                }
+                stats.consecutive_missed = 0;
+            });
             debug!(
                 "[insert_trustee_vote_state]|insert new vote|who:{:?}|state:{:}",
                 who, state
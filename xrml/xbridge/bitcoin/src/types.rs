@@ -85,6 +85,38 @@ impl<AccountId> WithdrawalProposal<AccountId> {
     }
 }
 
+/// the fund migration from the last trustee addresses to current ones after a transition
+#[derive(PartialEq, Clone, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct TrusteeMigration {
+    /// btc held by the last trustee addresses at the transition
+    pub to_migrate: u64,
+    /// btc moved to current trustee addresses
+    pub migrated: u64,
+    /// migration transactions
+    pub txids: Vec<H256>,
+    pub confirmed: bool,
+}
+
+impl TrusteeMigration {
+    /// btc still left on the last trustee addresses
+    pub fn remaining(&self) -> u64 {
+        self.to_migrate.saturating_sub(self.migrated)
+    }
+}
+
+/// signing statistics of a trustee for withdrawal proposals
+#[derive(PartialEq, Clone, Copy, Eq, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct TrusteeSignStats {
+    pub signed: u32,
+    pub rejected: u32,
+    pub missed: u32,
+    /// missed deadlines in a row, reset when the trustee signs or rejects
+    pub consecutive_missed: u32,
+}
+
 #[derive(PartialEq, Clone, Copy, Eq, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 pub enum VoteResult {
@@ -27,6 +27,8 @@ pub trait TrusteeSession<AccountId, TrusteeAddress: IntoVecu8> {
         number: u32,
     ) -> result::Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, &'static str>;
 
+    fn current_trustee_session_number() -> u32;
+
     fn current_trustee_session(
     ) -> result::Result<TrusteeSessionInfo<AccountId, TrusteeAddress>, &'static str>;
 
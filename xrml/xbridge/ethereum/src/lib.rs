@@ -0,0 +1,448 @@
+// Copyright 2018-2019 Chainpool.
+
+//! this module is for eth-bridge, relay ethereum headers and mint assets for
+//! erc20 deposits to the lock contract proved by receipt proofs
+//!
+//! fork model: headers below `MergeBlockNumber` are proof-of-work headers with non-zero
+//! difficulty, the best chain is the one with the most total difficulty. headers from
+//! `MergeBlockNumber` are proof-of-stake headers with zero difficulty, the total difficulty
+//! stops growing and the longest chain is the best. neither the ethash seal nor the beacon
+//! chain consensus is verified on chain, headers are trusted after k-of-n relayers submit them.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub mod rlp;
+mod tests;
+pub mod trie;
+pub mod trustee;
+pub mod types;
+
+// Substrate
+use rstd::{prelude::*, result};
+use substrate_primitives::{H256, U256};
+use support::{
+    decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue,
+};
+use system::ensure_signed;
+
+// ChainX
+use xassets::{Chain, Token};
+use xbridge_common::traits::{CrossChainBinding, Extractable};
+use xsupport::{debug, error, info, warn};
+
+pub use self::trustee::EthereumMultiSigConfig;
+pub use self::types::{
+    decode_receipt_logs, EthHeader, EthHeaderInfo, EthLog, EthereumAddress, LockEvent,
+};
+
+pub trait Trait: xsystem::Trait + xassets::Trait + xrecords::Trait {
+    type AccountExtractor: Extractable<Self::AccountId>;
+    type CrossChainProvider: CrossChainBinding<Self::AccountId, EthereumAddress>;
+    type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+}
+
+decl_event!(
+    pub enum Event<T> where
+        <T as system::Trait>::AccountId,
+        <T as xassets::Trait>::Balance {
+        /// block number, block hash, parent hash
+        InsertHeader(u64, H256, H256),
+        /// who, Token, balance, ethereum sender, block hash, tx index
+        Deposit(AccountId, Token, Balance, EthereumAddress, H256, u32),
+        /// ethereum sender, Token, balance
+        DepositPending(EthereumAddress, Token, Balance),
+        /// erc20 contract, Token, erc20 decimals
+        TokenMapping(EthereumAddress, Token, u8),
+    }
+);
+
+decl_storage! {
+    trait Store for Module<T: Trait> as XBridgeOfETH {
+        /// hash of the header with the most total difficulty
+        pub BestHeader get(best_header): H256;
+        /// all relayed headers (include fork headers)
+        pub HeaderFor get(header_for): map H256 => Option<EthHeaderInfo>;
+        /// header hash of the best chain for a block number
+        pub BlockHashFor get(block_hash_for): map u64 => Option<H256>;
+        /// receipt of (block hash, tx index) has been handled, in case re-handle it
+        pub HandledReceipt get(handled_receipt): map (H256, u32) => bool;
+
+        /// a receipt could be used after the block has such number of confirmations
+        pub ConfirmationNumber get(confirmation_number): u64 = 12;
+        /// the lock contract on ethereum which emits `Lock` events
+        pub LockContract get(lock_contract): EthereumAddress;
+        /// erc20 contract => (ChainX token, erc20 decimals)
+        pub TokenMappingFor get(token_mapping_for): map EthereumAddress => Option<(Token, u8)>;
+        /// accounts allowed to relay headers, the consensus of headers is not verified on chain
+        pub Relayers get(relayers): Vec<T::AccountId>;
+        /// a header is inserted after such number of relayers submit it
+        pub RelayerThreshold get(relayer_threshold): u32 = 1;
+        /// headers waiting for more relayers, header hash => (header, submitters)
+        pub PendingHeaderFor get(pending_header_for): map H256 => Option<(EthHeader, Vec<T::AccountId>)>;
+        /// hashes of the pending headers for a block number
+        pub PendingHeadersAt get(pending_headers_at): map u64 => Vec<H256>;
+        /// headers at or below this number could not be reorganized
+        pub FinalizedNumber get(finalized_number): u64;
+        /// headers from this number are proof-of-stake headers, default is the merge block of mainnet
+        pub MergeBlockNumber get(merge_block_number): u64 = 15537394;
+
+        /// deposits without a ChainX account, ethereum sender => (Token, balance)
+        pub PendingDepositMap get(pending_deposit): map EthereumAddress => Vec<(Token, T::Balance)>;
+    }
+}
+
+decl_module! {
+    pub struct Module<T: Trait> for enum Call where origin: T::Origin {
+        fn deposit_event<T>() = default;
+
+        /// relay a rlp encoded ethereum header
+        pub fn push_header(origin, header: Vec<u8>) -> Result {
+            let from = ensure_signed(origin)?;
+            let relayers = Self::relayers();
+            ensure!(relayers.contains(&from), "Only relayers could push ethereum headers");
+
+            let (hash, header) = EthHeader::decode_rlp(&header)?;
+            debug!("[push_header]|from:{:?}|number:{:}|hash:{:?}", from, header.number, hash);
+            let total_difficulty = Self::check_header(&hash, &header)?;
+
+            let pending = Self::pending_header_for(&hash);
+            let is_new = pending.is_none();
+            let (header, mut submitters) = pending.unwrap_or((header, Vec::new()));
+            ensure!(!submitters.contains(&from), "Relayer has submitted this header");
+            // submissions of the removed relayers are not counted
+            submitters.retain(|r| relayers.contains(r));
+            submitters.push(from);
+
+            let number = header.number;
+            if (submitters.len() as u32) < Self::relayer_threshold() {
+                if is_new {
+                    <PendingHeadersAt<T>>::mutate(number, |v| v.push(hash));
+                }
+                <PendingHeaderFor<T>>::insert(&hash, (header, submitters));
+                return Ok(());
+            }
+
+            if !is_new {
+                <PendingHeaderFor<T>>::remove(&hash);
+                <PendingHeadersAt<T>>::mutate(number, |v| v.retain(|h| h != &hash));
+            }
+            Self::insert_header(hash, header, total_difficulty)
+        }
+
+        /// deposit by the receipt of `tx_index` in block `block_hash`, `proof` is the
+        /// nodes from the receipt trie root to the receipt
+        pub fn push_receipt(origin, block_hash: H256, tx_index: u32, proof: Vec<Vec<u8>>) -> Result {
+            let from = ensure_signed(origin)?;
+            debug!("[push_receipt]|from:{:?}|block_hash:{:?}|tx_index:{:}", from, block_hash, tx_index);
+            Self::apply_receipt(block_hash, tx_index, &proof)
+        }
+
+        /// set the header the bridge starts from, could only be set once
+        pub fn set_genesis_header(header: Vec<u8>, total_difficulty: U256) -> Result {
+            ensure!(Self::best_header() == H256::default(), "Genesis header has been set");
+            let (hash, header) = EthHeader::decode_rlp(&header)?;
+            info!("[set_genesis_header]|number:{:}|hash:{:?}", header.number, hash);
+
+            <BlockHashFor<T>>::insert(header.number, hash);
+            <FinalizedNumber<T>>::put(header.number);
+            <HeaderFor<T>>::insert(&hash, EthHeaderInfo { header, total_difficulty });
+            <BestHeader<T>>::put(hash);
+            Ok(())
+        }
+
+        pub fn set_confirmation_number(confirmation_number: u64) -> Result {
+            ensure!(confirmation_number > 0, "Confirmation number should be greater than 0");
+            <ConfirmationNumber<T>>::put(confirmation_number);
+            Ok(())
+        }
+
+        pub fn set_merge_block_number(number: u64) {
+            <MergeBlockNumber<T>>::put(number);
+        }
+
+        pub fn set_lock_contract(contract: EthereumAddress) {
+            <LockContract<T>>::put(contract);
+        }
+
+        /// set the relayers, a header is inserted after `threshold` of them submit it
+        pub fn set_relayers(relayers: Vec<T::AccountId>, threshold: u32) -> Result {
+            ensure!(
+                threshold > 0 && threshold as usize <= relayers.len(),
+                "Relayer threshold should be in [1, relayers count]"
+            );
+            <Relayers<T>>::put(relayers);
+            <RelayerThreshold<T>>::put(threshold);
+            Ok(())
+        }
+
+        /// map an erc20 contract to a registered ChainX asset of ethereum chain
+        pub fn set_token_mapping(erc20: EthereumAddress, token: Token, decimals: u8) -> Result {
+            let asset = xassets::Module::<T>::get_asset(&token)?;
+            ensure!(asset.chain() == Chain::Ethereum, "The asset should belong to ethereum chain");
+
+            <TokenMappingFor<T>>::insert(&erc20, (token.clone(), decimals));
+            Self::deposit_event(RawEvent::TokenMapping(erc20, token, decimals));
+            Ok(())
+        }
+    }
+}
+
+impl<T: Trait> Module<T> {
+    /// check the header could be inserted, return its total difficulty
+    fn check_header(hash: &H256, header: &EthHeader) -> result::Result<U256, &'static str> {
+        ensure!(!<HeaderFor<T>>::exists(hash), "Header already exists");
+        let parent = Self::header_for(&header.parent_hash).ok_or("Parent header not found")?;
+
+        ensure!(
+            header.number == parent.header.number + 1,
+            "Header number should be parent number + 1"
+        );
+        ensure!(
+            header.timestamp > parent.header.timestamp,
+            "Header timestamp should be greater than parent"
+        );
+        ensure!(
+            header.gas_used <= header.gas_limit,
+            "Header gas used exceeds gas limit"
+        );
+        if header.number < Self::merge_block_number() {
+            ensure!(
+                !header.difficulty.is_zero(),
+                "Header difficulty should not be zero before the merge"
+            );
+        } else {
+            ensure!(
+                header.difficulty.is_zero(),
+                "Header difficulty should be zero after the merge"
+            );
+        }
+
+        let finalized = Self::finalized_number();
+        ensure!(
+            header.number > finalized,
+            "Header is below the finalized header"
+        );
+        // walk back to the best chain, the fork point should not be below the finalized header
+        let mut current = header.parent_hash;
+        let mut current_number = parent.header.number;
+        while Self::block_hash_for(current_number) != Some(current) {
+            ensure!(
+                current_number > finalized,
+                "Header forks below the finalized header"
+            );
+            current = Self::header_for(&current)
+                .ok_or("Parent header not found")?
+                .header
+                .parent_hash;
+            current_number -= 1;
+        }
+
+        parent
+            .total_difficulty
+            .checked_add(header.difficulty)
+            .ok_or("Total difficulty overflow")
+    }
+
+    fn insert_header(hash: H256, header: EthHeader, total_difficulty: U256) -> Result {
+        let best = Self::header_for(&Self::best_header()).ok_or("Best header not found")?;
+        let number = header.number;
+        let parent_hash = header.parent_hash;
+        <HeaderFor<T>>::insert(
+            &hash,
+            EthHeaderInfo {
+                header,
+                total_difficulty,
+            },
+        );
+        Self::deposit_event(RawEvent::InsertHeader(number, hash, parent_hash));
+
+        // the total difficulty doesn't grow after the merge, then the longer chain is the best
+        if (total_difficulty, number) > (best.total_difficulty, best.header.number) {
+            Self::update_best(hash, number, best.header.number);
+        }
+        Ok(())
+    }
+
+    /// switch the best chain to `hash`, rewrite the block number index back to the fork point
+    fn update_best(hash: H256, number: u64, old_best_number: u64) {
+        info!("[update_best]|new best|number:{:}|hash:{:?}", number, hash);
+        <BestHeader<T>>::put(hash);
+
+        for n in (number + 1)..=old_best_number {
+            <BlockHashFor<T>>::remove(n);
+        }
+
+        let mut current = hash;
+        let mut current_number = number;
+        while Self::block_hash_for(current_number) != Some(current) {
+            <BlockHashFor<T>>::insert(current_number, current);
+            let info = match Self::header_for(&current) {
+                Some(info) => info,
+                None => break,
+            };
+            if current_number == 0 {
+                break;
+            }
+            current = info.header.parent_hash;
+            current_number -= 1;
+        }
+
+        // blocks with enough confirmations are finalized
+        let finalized = (number + 1).saturating_sub(Self::confirmation_number());
+        let old_finalized = Self::finalized_number();
+        if finalized > old_finalized {
+            <FinalizedNumber<T>>::put(finalized);
+            for n in (old_finalized + 1)..=finalized {
+                for pending in <PendingHeadersAt<T>>::take(n) {
+                    <PendingHeaderFor<T>>::remove(&pending);
+                }
+            }
+        }
+    }
+
+    fn apply_receipt(block_hash: H256, tx_index: u32, proof: &[Vec<u8>]) -> Result {
+        ensure!(
+            !Self::handled_receipt((block_hash, tx_index)),
+            "Receipt has been handled"
+        );
+        let info = Self::header_for(&block_hash).ok_or("Block header not found")?;
+        ensure!(
+            Self::block_hash_for(info.header.number) == Some(block_hash),
+            "Block is not in the best chain"
+        );
+        ensure!(
+            info.header.number <= Self::finalized_number(),
+            "Block is not confirmed yet"
+        );
+
+        let key = rlp::encode_u64(tx_index as u64);
+        let receipt = trie::verify_proof(&info.header.receipts_root, &key, proof)?;
+        let logs = decode_receipt_logs(&receipt)?;
+
+        let contract = Self::lock_contract();
+        let mut events = Vec::new();
+        for log in logs.iter() {
+            if let Some(event) = LockEvent::from_log(log, &contract)? {
+                // check all the events before the receipt is marked as handled, so that it
+                // could be pushed again after the invalid one is fixed
+                let (token, balance) = Self::check_lock_event(&event)?;
+                events.push((event, token, balance));
+            }
+        }
+        ensure!(!events.is_empty(), "No lock event in receipt");
+
+        <HandledReceipt<T>>::insert((block_hash, tx_index), true);
+        for (event, token, balance) in events {
+            Self::handle_lock_event(event, token, balance, block_hash, tx_index);
+        }
+        Ok(())
+    }
+
+    /// return the ChainX token and balance of the lock event
+    fn check_lock_event(event: &LockEvent) -> result::Result<(Token, T::Balance), &'static str> {
+        let (token, decimals) = Self::token_mapping_for(&event.token).ok_or_else(|| {
+            warn!(
+                "[check_lock_event]|erc20 contract not mapped|contract:{:?}",
+                event.token
+            );
+            "Erc20 contract is not mapped"
+        })?;
+        let asset = xassets::Module::<T>::get_asset(&token)?;
+        let balance =
+            convert_value(event.value, decimals, asset.precision() as u8).map_err(|e| {
+                error!(
+                    "[check_lock_event]|invalid deposit value|value:{:?}|reason:{:}",
+                    event.value, e
+                );
+                e
+            })?;
+        ensure!(balance > 0, "Deposit value is too small");
+        Ok((token, T::Balance::from(balance)))
+    }
+
+    fn handle_lock_event(
+        event: LockEvent,
+        token: Token,
+        balance: T::Balance,
+        block_hash: H256,
+        tx_index: u32,
+    ) {
+        let addr_type = xsystem::Module::<T>::address_type();
+        let who = match T::AccountExtractor::account_info(&event.memo, addr_type) {
+            Some((who, channel_name)) => {
+                T::CrossChainProvider::update_binding(&who, event.sender, channel_name);
+                Self::apply_pending_deposit(&who, &event.sender);
+                Some(who)
+            }
+            None => T::CrossChainProvider::get_binding_info(&event.sender).map(|(who, _)| who),
+        };
+
+        match who {
+            Some(who) => {
+                Self::deposit_token(&who, &token, balance, event.sender, block_hash, tx_index)
+            }
+            None => {
+                info!(
+                    "[handle_lock_event]|no account for sender, deposit pending|sender:{:?}",
+                    event.sender
+                );
+                <PendingDepositMap<T>>::mutate(&event.sender, |v| v.push((token.clone(), balance)));
+                Self::deposit_event(RawEvent::DepositPending(event.sender, token, balance));
+            }
+        }
+    }
+
+    fn apply_pending_deposit(who: &T::AccountId, sender: &EthereumAddress) {
+        for (token, balance) in <PendingDepositMap<T>>::take(sender) {
+            Self::deposit_token(who, &token, balance, *sender, H256::default(), 0);
+        }
+    }
+
+    fn deposit_token(
+        who: &T::AccountId,
+        token: &Token,
+        balance: T::Balance,
+        sender: EthereumAddress,
+        block_hash: H256,
+        tx_index: u32,
+    ) {
+        match xrecords::Module::<T>::deposit(who, token, balance) {
+            Ok(()) => Self::deposit_event(RawEvent::Deposit(
+                who.clone(),
+                token.clone(),
+                balance,
+                sender,
+                block_hash,
+                tx_index,
+            )),
+            Err(e) => error!(
+                "call xrecords to deposit error!, must use root to fix this error. reason:{:?}",
+                e
+            ),
+        }
+    }
+}
+
+/// convert an erc20 value with `decimals` to a ChainX balance with `precision`,
+/// the dust below `precision` is dropped
+pub fn convert_value(
+    value: U256,
+    decimals: u8,
+    precision: u8,
+) -> result::Result<u64, &'static str> {
+    let ten = U256::from(10);
+    let mut value = value;
+    if decimals >= precision {
+        for _ in precision..decimals {
+            value = value / ten;
+        }
+    } else {
+        for _ in decimals..precision {
+            value = value.checked_mul(ten).ok_or("deposit value overflow")?;
+        }
+    }
+    if value > U256::from(u64::max_value()) {
+        return Err("deposit value overflow");
+    }
+    Ok(value.low_u64())
+}
@@ -0,0 +1,151 @@
+// Copyright 2018-2019 Chainpool.
+
+//! minimal rlp decoder, only what the header and receipt verification needs
+
+use rstd::prelude::Vec;
+use rstd::result;
+
+use substrate_primitives::{H256, U256};
+
+pub type DecodeResult<T> = result::Result<T, &'static str>;
+
+/// a view over one rlp item
+#[derive(Clone, Copy)]
+pub struct Rlp<'a> {
+    raw: &'a [u8],
+    is_list: bool,
+    offset: usize,
+}
+
+/// returns (is_list, header length, payload length) of the item at the start of `raw`
+fn payload_info(raw: &[u8]) -> DecodeResult<(bool, usize, usize)> {
+    let first = *raw.first().ok_or("rlp item is empty")?;
+    let (is_list, header_len, payload_len) = match first {
+        0x00..=0x7f => (false, 0, 1),
+        0x80..=0xb7 => (false, 1, (first - 0x80) as usize),
+        0xb8..=0xbf => {
+            let len_of_len = (first - 0xb7) as usize;
+            (false, 1 + len_of_len, decode_len(&raw[1..], len_of_len)?)
+        }
+        0xc0..=0xf7 => (true, 1, (first - 0xc0) as usize),
+        0xf8..=0xff => {
+            let len_of_len = (first - 0xf7) as usize;
+            (true, 1 + len_of_len, decode_len(&raw[1..], len_of_len)?)
+        }
+    };
+    if raw.len() < header_len + payload_len {
+        return Err("rlp item is shorter than its declared length");
+    }
+    Ok((is_list, header_len, payload_len))
+}
+
+fn decode_len(data: &[u8], len_of_len: usize) -> DecodeResult<usize> {
+    if data.len() < len_of_len || len_of_len > 4 {
+        return Err("rlp length prefix is invalid");
+    }
+    if data[0] == 0 {
+        return Err("rlp length prefix has leading zero");
+    }
+    let len = data[..len_of_len]
+        .iter()
+        .fold(0_usize, |acc, b| (acc << 8) | *b as usize);
+    if len < 56 {
+        return Err("rlp long length prefix used for short item");
+    }
+    Ok(len)
+}
+
+impl<'a> Rlp<'a> {
+    /// decode a whole buffer as exactly one rlp item
+    pub fn new(raw: &'a [u8]) -> DecodeResult<Self> {
+        let (is_list, header_len, payload_len) = payload_info(raw)?;
+        if raw.len() != header_len + payload_len {
+            return Err("rlp item has trailing bytes");
+        }
+        Ok(Rlp {
+            raw,
+            is_list,
+            offset: header_len,
+        })
+    }
+
+    pub fn is_list(&self) -> bool {
+        self.is_list
+    }
+
+    /// the whole encoding of this item, include the prefix
+    pub fn as_raw(&self) -> &'a [u8] {
+        self.raw
+    }
+
+    /// payload of a byte string item
+    pub fn data(&self) -> DecodeResult<&'a [u8]> {
+        if self.is_list {
+            return Err("rlp item is a list, expect a byte string");
+        }
+        Ok(&self.raw[self.offset..])
+    }
+
+    /// items of a list item
+    pub fn items(&self) -> DecodeResult<Vec<Rlp<'a>>> {
+        if !self.is_list {
+            return Err("rlp item is a byte string, expect a list");
+        }
+        let mut items = Vec::new();
+        let mut rest = &self.raw[self.offset..];
+        while !rest.is_empty() {
+            let (_, header_len, payload_len) = payload_info(rest)?;
+            let (item, remain) = rest.split_at(header_len + payload_len);
+            items.push(Rlp::new(item)?);
+            rest = remain;
+        }
+        Ok(items)
+    }
+
+    pub fn as_u64(&self) -> DecodeResult<u64> {
+        let data = self.data()?;
+        if data.len() > 8 {
+            return Err("rlp integer overflows u64");
+        }
+        Ok(data.iter().fold(0_u64, |acc, b| (acc << 8) | *b as u64))
+    }
+
+    pub fn as_u256(&self) -> DecodeResult<U256> {
+        let data = self.data()?;
+        if data.len() > 32 {
+            return Err("rlp integer overflows u256");
+        }
+        Ok(U256::from_big_endian(data))
+    }
+
+    pub fn as_h256(&self) -> DecodeResult<H256> {
+        let data = self.data()?;
+        if data.len() != 32 {
+            return Err("rlp hash should be 32 bytes");
+        }
+        Ok(H256::from_slice(data))
+    }
+
+    pub fn as_address(&self) -> DecodeResult<[u8; 20]> {
+        let data = self.data()?;
+        if data.len() != 20 {
+            return Err("rlp address should be 20 bytes");
+        }
+        let mut addr = [0_u8; 20];
+        addr.copy_from_slice(data);
+        Ok(addr)
+    }
+}
+
+/// rlp encoding of an unsigned integer, used as the key of receipt trie
+pub fn encode_u64(value: u64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let data = &bytes[bytes.iter().position(|b| *b != 0).unwrap_or(8)..];
+    if data.len() == 1 && data[0] < 0x80 {
+        return data.to_vec();
+    }
+    let mut out = Vec::with_capacity(data.len() + 1);
+    out.push(0x80 + data.len() as u8);
+    out.extend_from_slice(data);
+    out
+}
@@ -0,0 +1,619 @@
+// Copyright 2018-2019 Chainpool.
+
+#![cfg(test)]
+
+mod mock;
+
+use self::mock::*;
+use super::*;
+
+use hex_literal::hex;
+
+use runtime_io::with_externalities;
+use substrate_primitives::H256;
+use support::{assert_noop, assert_ok};
+
+use crate::rlp::Rlp;
+use crate::trie::verify_proof;
+
+// ethereum mainnet genesis block header,
+// hash = 0xd4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3
+fn genesis_header() -> Vec<u8> {
+    hex!("f90214a00000000000000000000000000000000000000000000000000000000000000000a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a0d7f8974fb5ac78d9ac099b9ad5018bedc2ce0a72dad1827a1709da30580f0544a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000850400000000808213888080a011bbe8db4e347b4e8c937c1c8370e4b5ed33adb3db69cbdb7a38e1e50b1b82faa00000000000000000000000000000000000000000000000000000000000000000880000000000000042").to_vec()
+}
+
+// ethereum mainnet block 1, 2 and 3, the hashes are checked in `test_decode_header`
+fn header_1() -> Vec<u8> {
+    hex!("f90211a0d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d493479405a56e2d52c817161883f50c441c3228cfe54d9fa0d67e4d450343046425ae4271474353857ab860dbc0a1dde64b41b5cd3a532bf3a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008503ff80000001821388808455ba422499476574682f76312e302e302f6c696e75782f676f312e342e32a0969b900de27b6ac6a67742365dd65f55a0526c41fd18e1b16f1a1215c2e66f5988539bd4979fef1ec4").to_vec()
+}
+
+fn header_2() -> Vec<u8> {
+    hex!("f90218a088e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d4934794dd2f1e6e498202e86d8f5442af596580a4f03c2ca04943d941637411107494da9ec8bc04359d731bfd08b72b4d0edcbd4cd2ecb341a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008503ff00100002821388808455ba4241a0476574682f76312e302e302d30636463373634372f6c696e75782f676f312e34a02f0790c5aa31ab94195e1f6443d645af5b75c46c04fbf9911711198a0ce8fdda88b853fa261a86aa9e").to_vec()
+}
+
+fn header_3() -> Vec<u8> {
+    hex!("f90218a0b495a1d7e6663152ae92708da4843337b958146015a2802f4193a410044698c9a06b17b938c6e4ef18b26ad81b9ca3515f27fd9c4e82aac56a1fd8eab288785e41945088d623ba0fcf0131e0897a91734a4d83596aa0a076ab0b899e8387436ff2658e2988f83cbf1af1590b9fe9feca3714f8d1824940a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008503fe802ffe03821388808455ba4260a0476574682f76312e302e302d66633739643332642f6c696e75782f676f312e34a065e12eec23fe6555e6bcdb47aa25269ae106e5f16b54e1e92dcee25e1c8ad037882e9344e0cbde83ce").to_vec()
+}
+
+// there is no recorded fork of the blocks above, and no mainnet receipt contains a `Lock` event
+// of the lock contract, so the headers below are built on the mainnet blocks by a local script.
+// `header_2b` is a fork of block 2 with triple difficulty.
+// `header_4` is a child of block 3, its receipts root commits to `receipts()`.
+// `header_4_pos` is a post-merge (london) child of block 3 with zero difficulty and a base fee,
+// its receipts root commits to `receipts()` as well.
+fn header_2b() -> Vec<u8> {
+    hex!("f901f8a088e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a01111111111111111111111111111111111111111111111111111111111111111a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000850bfd00300002821388808455ba424480a00000000000000000000000000000000000000000000000000000000000000000880000000000000042").to_vec()
+}
+
+fn header_4() -> Vec<u8> {
+    hex!("f901f8a03d6122660cc824376f11ee842f83addc3525e2dd6756b9bcf0affa6aa88cf741a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a01111111111111111111111111111111111111111111111111111111111111111a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0d9b080573ed6fa6d9cb18ad03c0d13b5ea3e94773f26bf4dfa00b94df8bae28bb90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008503fe00500004821388808455ba428080a00000000000000000000000000000000000000000000000000000000000000000880000000000000042").to_vec()
+}
+
+fn header_4_pos() -> Vec<u8> {
+    hex!("f901f4a03d6122660cc824376f11ee842f83addc3525e2dd6756b9bcf0affa6aa88cf741a01dcc4de8dec75d7aab85b567b6ccd41ad312451b948a7413f0a142fd40d49347940000000000000000000000000000000000000000a01111111111111111111111111111111111111111111111111111111111111111a056e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421a0d9b080573ed6fa6d9cb18ad03c0d13b5ea3e94773f26bf4dfa00b94df8bae28bb90100000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000008004821388808455ba428080a0222222222222222222222222222222222222222222222222222222222222222288000000000000000007").to_vec()
+}
+
+const HASH_1: [u8; 32] = hex!("88e96d4537bea4d9c05d12549907b32561d3bf31f45aae734cdc119f13406cb6");
+const HASH_2: [u8; 32] = hex!("b495a1d7e6663152ae92708da4843337b958146015a2802f4193a410044698c9");
+const HASH_3: [u8; 32] = hex!("3d6122660cc824376f11ee842f83addc3525e2dd6756b9bcf0affa6aa88cf741");
+const HASH_2B: [u8; 32] = hex!("3677037006c099d5ed0032282fa0c9d8c450b434b5cbe12c6ff2f6d2a7fce460");
+const HASH_4: [u8; 32] = hex!("535b9c464c9d85e4616ce520b7c6cb5820ca4f32865c84f60c3532716ce6238d");
+const HASH_4_POS: [u8; 32] =
+    hex!("bc69029b8709f5fb518ff4553eb9e2f7e5765f8ca3acf969ff303aa20e406c02");
+
+// difficulty of the mainnet genesis and block 1, 2 and 3
+const DIFFICULTIES: [u64; 4] = [17179869184, 17171480576, 17163096064, 17154715646];
+
+// lock contract and DAI erc20 contract
+const LOCK_CONTRACT: EthereumAddress = hex!("5d3a536e4d6dbd6114cc1ead35777bab948e3643");
+const DAI: EthereumAddress = hex!("6b175474e89094c44da98b954eedeac495271d0f");
+const SENDER: EthereumAddress = hex!("82e2b7d189a81a251eaa51ac31871f8c4b91dff4");
+
+// receipts of block 4
+// 0: legacy receipt without logs
+// 1: typed receipt, lock 1.5 DAI with memo `5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg`
+// 2: legacy receipt, lock 2 DAI with empty memo
+// 3: typed receipt of a failed tx, lock 2 DAI with the memo above
+fn receipts() -> Vec<Vec<u8>> {
+    vec![
+        hex!("f9010801825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c0").to_vec(),
+        hex!("02f9022a0183013880b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f9011ff9011c945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8a000000000000000000000000000000000000000000000000014d1120d7b1600000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000003035536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6700000000000000000000000000000000").to_vec(),
+        hex!("f901e8018301fbd0b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f8def8dc945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8600000000000000000000000000000000000000000000000001bc16d674ec8000000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000").to_vec(),
+        hex!("02f9022a80830249f0b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f9011ff9011c945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8a00000000000000000000000000000000000000000000000001bc16d674ec800000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000003035536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6700000000000000000000000000000000").to_vec(),
+    ]
+}
+
+fn receipt_proof(index: u32) -> Vec<Vec<u8>> {
+    match index {
+        0 => vec![hex!("f851a0bfa5f678308d56d02a89e4d286cac7e36d6798d8a362d569adf45359f8fc197980808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(), hex!("f9010f30b9010bf9010801825208b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000c0").to_vec()],
+        1 => vec![hex!("f851a0bfa5f678308d56d02a89e4d286cac7e36d6798d8a362d569adf45359f8fc197980808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(), hex!("f87180a0fb4e8e07a696f9833d1084be31e3127081a4e7431c740596d87225ff2f2ab1e1a057925c3c62b87b57a9e2bdfa9bd88fb7d8cb9142f7712fe2a619a270a0541530a01a9178633f4dc946d4ba28e2976bfbb231afa7d2f96fbd6f048af68e1832996180808080808080808080808080").to_vec(), hex!("f9023220b9022e02f9022a0183013880b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f9011ff9011c945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8a000000000000000000000000000000000000000000000000014d1120d7b1600000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000003035536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6700000000000000000000000000000000").to_vec()],
+        2 => vec![hex!("f851a0bfa5f678308d56d02a89e4d286cac7e36d6798d8a362d569adf45359f8fc197980808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(), hex!("f87180a0fb4e8e07a696f9833d1084be31e3127081a4e7431c740596d87225ff2f2ab1e1a057925c3c62b87b57a9e2bdfa9bd88fb7d8cb9142f7712fe2a619a270a0541530a01a9178633f4dc946d4ba28e2976bfbb231afa7d2f96fbd6f048af68e1832996180808080808080808080808080").to_vec(), hex!("f901ef20b901ebf901e8018301fbd0b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f8def8dc945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8600000000000000000000000000000000000000000000000001bc16d674ec8000000000000000000000000000000000000000000000000000000000000000000400000000000000000000000000000000000000000000000000000000000000000").to_vec()],
+        _ => vec![hex!("f851a0bfa5f678308d56d02a89e4d286cac7e36d6798d8a362d569adf45359f8fc197980808080808080a0e58215be848c1293dd381210359d84485553000a82b67410406d183b42adbbdd8080808080808080").to_vec(), hex!("f87180a0fb4e8e07a696f9833d1084be31e3127081a4e7431c740596d87225ff2f2ab1e1a057925c3c62b87b57a9e2bdfa9bd88fb7d8cb9142f7712fe2a619a270a0541530a01a9178633f4dc946d4ba28e2976bfbb231afa7d2f96fbd6f048af68e1832996180808080808080808080808080").to_vec(), hex!("f9023220b9022e02f9022a80830249f0b9010000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000f9011ff9011c945d3a536e4d6dbd6114cc1ead35777bab948e3643f863a060d5abc9979d2a9afa755fcf66639c3bd1ce0e51d5563a8387de773edfdb745fa00000000000000000000000006b175474e89094c44da98b954eedeac495271d0fa000000000000000000000000082e2b7d189a81a251eaa51ac31871f8c4b91dff4b8a00000000000000000000000000000000000000000000000001bc16d674ec800000000000000000000000000000000000000000000000000000000000000000040000000000000000000000000000000000000000000000000000000000000003035536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6700000000000000000000000000000000").to_vec()],
+    }
+}
+
+fn memo_account() -> AccountId {
+    // public key of `5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg`
+    AccountId::from_slice(&hex!(
+        "88bbde145f26797e27f495730c4782074ee802243ffe17a2c30a8148dac101a8"
+    ))
+}
+
+fn relayer() -> AccountId {
+    AccountId::from_slice(&[1; 32])
+}
+
+fn relayer2() -> AccountId {
+    AccountId::from_slice(&[2; 32])
+}
+
+fn init_bridge() {
+    assert_ok!(XBridgeOfETH::set_genesis_header(
+        genesis_header(),
+        U256::from(0x400000000_u64)
+    ));
+    assert_ok!(XBridgeOfETH::set_relayers(vec![relayer()], 1));
+}
+
+fn init_deposit() {
+    init_bridge();
+    let asset = xassets::Asset::new(
+        b"DAI".to_vec(),
+        b"Dai Stablecoin".to_vec(),
+        xassets::Chain::Ethereum,
+        8,
+        b"test".to_vec(),
+    )
+    .unwrap();
+    assert_ok!(XAssets::register_asset(asset, true, false));
+    assert_ok!(XBridgeOfETH::set_token_mapping(DAI, b"DAI".to_vec(), 18));
+    assert_ok!(XBridgeOfETH::set_lock_contract(LOCK_CONTRACT));
+    assert_ok!(XBridgeOfETH::set_confirmation_number(1));
+    for header in [header_1(), header_2(), header_3(), header_4()].iter() {
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header.clone()
+        ));
+    }
+}
+
+#[test]
+fn test_rlp() {
+    assert_eq!(rlp::encode_u64(0), vec![0x80]);
+    assert_eq!(rlp::encode_u64(1), vec![0x01]);
+    assert_eq!(rlp::encode_u64(0x7f), vec![0x7f]);
+    assert_eq!(rlp::encode_u64(0x80), vec![0x81, 0x80]);
+    assert_eq!(rlp::encode_u64(1024), vec![0x82, 0x04, 0x00]);
+
+    // ["cat", "dog"]
+    let raw = hex!("c88363617483646f67");
+    let items = Rlp::new(&raw).unwrap().items().unwrap();
+    assert_eq!(items.len(), 2);
+    assert_eq!(items[0].data(), Ok(&b"cat"[..]));
+    assert_eq!(items[1].data(), Ok(&b"dog"[..]));
+    assert_eq!(Rlp::new(&hex!("820400")).unwrap().as_u64(), Ok(1024));
+
+    assert!(Rlp::new(&hex!("c88363617483646f")).is_err());
+    assert!(Rlp::new(&hex!("c88363617483646f6700")).is_err());
+    assert!(Rlp::new(&raw).unwrap().data().is_err());
+}
+
+#[test]
+fn test_decode_header() {
+    let (hash, header) = EthHeader::decode_rlp(&genesis_header()).unwrap();
+    assert_eq!(
+        hash,
+        H256::from(hex!(
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        ))
+    );
+    assert_eq!(header.number, 0);
+    assert_eq!(header.difficulty, U256::from(0x400000000_u64));
+    assert_eq!(header.gas_limit, 5000);
+    assert_eq!(
+        header.receipts_root,
+        H256::from(hex!(
+            "56e81f171bcc55a6ff8345e692c0f86e5b48e01b996cadc001622fb5e363b421"
+        ))
+    );
+
+    let (hash, header) = EthHeader::decode_rlp(&header_1()).unwrap();
+    assert_eq!(hash, H256::from(HASH_1));
+    assert_eq!(header.number, 1);
+    assert_eq!(
+        header.parent_hash,
+        H256::from(hex!(
+            "d4e56740f876aef8c010b86a40d5f56745a118d0906a34e69aec8c0db1cb8fa3"
+        ))
+    );
+    assert_eq!(header.difficulty, U256::from(DIFFICULTIES[1]));
+    assert_eq!(header.timestamp, 1438269988);
+    assert_eq!(
+        EthHeader::decode_rlp(&header_2()).unwrap().0,
+        H256::from(HASH_2)
+    );
+    assert_eq!(
+        EthHeader::decode_rlp(&header_3()).unwrap().0,
+        H256::from(HASH_3)
+    );
+
+    let (hash, header) = EthHeader::decode_rlp(&header_4()).unwrap();
+    assert_eq!(hash, H256::from(HASH_4));
+    assert_eq!(header.parent_hash, H256::from(HASH_3));
+    assert_eq!(
+        header.receipts_root,
+        H256::from(hex!(
+            "d9b080573ed6fa6d9cb18ad03c0d13b5ea3e94773f26bf4dfa00b94df8bae28b"
+        ))
+    );
+
+    // london header with base fee
+    let (hash, header) = EthHeader::decode_rlp(&header_4_pos()).unwrap();
+    assert_eq!(hash, H256::from(HASH_4_POS));
+    assert_eq!(header.number, 4);
+    assert!(header.difficulty.is_zero());
+
+    let mut raw = genesis_header();
+    raw.pop();
+    assert!(EthHeader::decode_rlp(&raw).is_err());
+}
+
+#[test]
+fn test_verify_proof() {
+    let root = H256::from(hex!(
+        "d9b080573ed6fa6d9cb18ad03c0d13b5ea3e94773f26bf4dfa00b94df8bae28b"
+    ));
+    for (i, receipt) in receipts().into_iter().enumerate() {
+        let key = rlp::encode_u64(i as u64);
+        assert_eq!(
+            verify_proof(&root, &key, &receipt_proof(i as u32)),
+            Ok(receipt)
+        );
+    }
+
+    // proof for another key
+    assert!(verify_proof(&root, &rlp::encode_u64(1), &receipt_proof(2)).is_err());
+    assert!(verify_proof(&root, &rlp::encode_u64(4), &receipt_proof(2)).is_err());
+    // tampered node
+    let mut proof = receipt_proof(1);
+    let last = proof.len() - 1;
+    let len = proof[last].len();
+    proof[last][len - 1] ^= 1;
+    assert_eq!(
+        verify_proof(&root, &rlp::encode_u64(1), &proof),
+        Err("trie proof node hash mismatch")
+    );
+    // redundant node
+    let mut proof = receipt_proof(0);
+    proof.push(receipt_proof(1)[1].clone());
+    assert!(verify_proof(&root, &rlp::encode_u64(0), &proof).is_err());
+
+    // children shorter than 32 bytes are embedded in the root node
+    let root = H256::from(hex!(
+        "1ab238c7dbfab839ddfd96058578d5791e988a66f306e4715222a92fce3eaee6"
+    ));
+    let proof = vec![hex!(
+        "ead580c22001c22002808080808080808080808080808080808080808080c4823180038080808080808080"
+    )
+    .to_vec()];
+    assert_eq!(
+        verify_proof(&root, &rlp::encode_u64(2), &proof),
+        Ok(vec![0x02])
+    );
+}
+
+#[test]
+fn test_decode_receipt() {
+    let receipts = receipts();
+    assert_eq!(decode_receipt_logs(&receipts[0]), Ok(vec![]));
+
+    let logs = decode_receipt_logs(&receipts[1]).unwrap();
+    assert_eq!(logs.len(), 1);
+    assert_eq!(logs[0].topics[0], types::lock_event_topic());
+    let event = LockEvent::from_log(&logs[0], &LOCK_CONTRACT)
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.token, DAI);
+    assert_eq!(event.sender, SENDER);
+    assert_eq!(event.value, U256::from(1_500_000_000_000_000_000_u64));
+    assert_eq!(
+        event.memo,
+        b"5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg".to_vec()
+    );
+    // not the lock contract
+    assert_eq!(LockEvent::from_log(&logs[0], &DAI), Ok(None));
+
+    let logs = decode_receipt_logs(&receipts[2]).unwrap();
+    let event = LockEvent::from_log(&logs[0], &LOCK_CONTRACT)
+        .unwrap()
+        .unwrap();
+    assert_eq!(event.memo, b"".to_vec());
+
+    assert_eq!(
+        decode_receipt_logs(&receipts[3]),
+        Err("receipt status is failed")
+    );
+}
+
+#[test]
+fn test_convert_value() {
+    let value = U256::from(1_500_000_000_000_000_000_u64);
+    assert_eq!(convert_value(value, 18, 8), Ok(150_000_000));
+    assert_eq!(convert_value(value, 18, 18), Ok(1_500_000_000_000_000_000));
+    assert_eq!(convert_value(U256::from(15), 6, 8), Ok(1500));
+    assert_eq!(convert_value(U256::from(99), 18, 8), Ok(0));
+    assert_eq!(
+        convert_value(value * U256::from(100), 18, 18),
+        Err("deposit value overflow")
+    );
+}
+
+#[test]
+fn test_push_header() {
+    with_externalities(&mut new_test_ext(), || {
+        init_bridge();
+        assert_noop!(
+            XBridgeOfETH::set_genesis_header(genesis_header(), U256::zero()),
+            "Genesis header has been set"
+        );
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(Default::default()), header_1()),
+            "Only relayers could push ethereum headers"
+        );
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer()), header_2()),
+            "Parent header not found"
+        );
+
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_1()
+        ));
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer()), header_1()),
+            "Header already exists"
+        );
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_2()
+        ));
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_3()
+        ));
+        assert_eq!(XBridgeOfETH::best_header(), H256::from(HASH_3));
+        assert_eq!(XBridgeOfETH::block_hash_for(2), Some(H256::from(HASH_2)));
+        assert_eq!(
+            XBridgeOfETH::header_for(H256::from(HASH_3))
+                .unwrap()
+                .total_difficulty,
+            U256::from(DIFFICULTIES.iter().sum::<u64>())
+        );
+
+        // the fork has more total difficulty with less blocks
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_2b()
+        ));
+        assert_eq!(XBridgeOfETH::best_header(), H256::from(HASH_2B));
+        assert_eq!(XBridgeOfETH::block_hash_for(1), Some(H256::from(HASH_1)));
+        assert_eq!(XBridgeOfETH::block_hash_for(2), Some(H256::from(HASH_2B)));
+        assert_eq!(XBridgeOfETH::block_hash_for(3), None);
+        assert!(XBridgeOfETH::header_for(H256::from(HASH_3)).is_some());
+    })
+}
+
+#[test]
+fn test_relayer_threshold() {
+    with_externalities(&mut new_test_ext(), || {
+        init_bridge();
+        let relayers = vec![relayer(), relayer2(), AccountId::from_slice(&[3; 32])];
+        assert_noop!(
+            XBridgeOfETH::set_relayers(relayers.clone(), 0),
+            "Relayer threshold should be in [1, relayers count]"
+        );
+        assert_noop!(
+            XBridgeOfETH::set_relayers(relayers.clone(), 4),
+            "Relayer threshold should be in [1, relayers count]"
+        );
+        assert_ok!(XBridgeOfETH::set_relayers(relayers, 2));
+
+        // the header is pending until 2 relayers submit it
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_1()
+        ));
+        assert!(XBridgeOfETH::header_for(H256::from(HASH_1)).is_none());
+        assert_eq!(
+            XBridgeOfETH::pending_headers_at(1),
+            vec![H256::from(HASH_1)]
+        );
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer()), header_1()),
+            "Relayer has submitted this header"
+        );
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer2()),
+            header_1()
+        ));
+        assert_eq!(XBridgeOfETH::best_header(), H256::from(HASH_1));
+        assert!(XBridgeOfETH::pending_header_for(H256::from(HASH_1)).is_none());
+        assert_eq!(XBridgeOfETH::pending_headers_at(1), vec![]);
+    })
+}
+
+#[test]
+fn test_finalized_header() {
+    with_externalities(&mut new_test_ext(), || {
+        init_bridge();
+        assert_ok!(XBridgeOfETH::set_relayers(vec![relayer(), relayer2()], 2));
+        assert_ok!(XBridgeOfETH::set_confirmation_number(2));
+        for header in [header_1(), header_2()].iter() {
+            for r in [relayer(), relayer2()].iter() {
+                assert_ok!(XBridgeOfETH::push_header(
+                    Origin::signed(r.clone()),
+                    header.clone()
+                ));
+            }
+        }
+        assert_eq!(XBridgeOfETH::finalized_number(), 1);
+
+        // only submitted by one relayer, dropped when its number is finalized
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_2b()
+        ));
+        assert_eq!(
+            XBridgeOfETH::pending_headers_at(2),
+            vec![H256::from(HASH_2B)]
+        );
+        for r in [relayer(), relayer2()].iter() {
+            assert_ok!(XBridgeOfETH::push_header(
+                Origin::signed(r.clone()),
+                header_3()
+            ));
+        }
+        assert_eq!(XBridgeOfETH::finalized_number(), 2);
+        assert!(XBridgeOfETH::pending_header_for(H256::from(HASH_2B)).is_none());
+        assert_eq!(XBridgeOfETH::pending_headers_at(2), vec![]);
+
+        // the fork with more total difficulty could not revert the finalized header
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer2()), header_2b()),
+            "Header is below the finalized header"
+        );
+        assert_eq!(XBridgeOfETH::block_hash_for(2), Some(H256::from(HASH_2)));
+    })
+}
+
+#[test]
+fn test_push_receipt() {
+    with_externalities(&mut new_test_ext(), || {
+        init_bridge();
+        assert_ok!(XBridgeOfETH::set_confirmation_number(3));
+        for header in [header_1(), header_2(), header_3(), header_4()].iter() {
+            assert_ok!(XBridgeOfETH::push_header(
+                Origin::signed(relayer()),
+                header.clone()
+            ));
+        }
+        assert_noop!(
+            XBridgeOfETH::push_receipt(
+                Origin::signed(relayer()),
+                H256::from(HASH_4),
+                1,
+                receipt_proof(1)
+            ),
+            "Block is not confirmed yet"
+        );
+    });
+
+    with_externalities(&mut new_test_ext(), || {
+        init_deposit();
+        let who = memo_account();
+        let token = b"DAI".to_vec();
+        let block_hash = H256::from(HASH_4);
+        let origin = || Origin::signed(Default::default());
+
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 0, receipt_proof(0)),
+            "No lock event in receipt"
+        );
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 3, receipt_proof(3)),
+            "receipt status is failed"
+        );
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 2, receipt_proof(1)),
+            "trie proof node hash mismatch"
+        );
+
+        assert_ok!(XBridgeOfETH::push_receipt(
+            origin(),
+            block_hash,
+            1,
+            receipt_proof(1)
+        ));
+        assert_eq!(XAssets::free_balance_of(&who, &token), 150_000_000);
+        assert!(XBridgeOfETH::handled_receipt((block_hash, 1)));
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 1, receipt_proof(1)),
+            "Receipt has been handled"
+        );
+
+        // empty memo, deposit to the account bound by the former deposit
+        assert_ok!(XBridgeOfETH::push_receipt(
+            origin(),
+            block_hash,
+            2,
+            receipt_proof(2)
+        ));
+        assert_eq!(XAssets::free_balance_of(&who, &token), 350_000_000);
+        assert_eq!(XBridgeOfETH::pending_deposit(SENDER), vec![]);
+    })
+}
+
+#[test]
+fn test_pending_deposit() {
+    with_externalities(&mut new_test_ext(), || {
+        init_deposit();
+        let who = memo_account();
+        let token = b"DAI".to_vec();
+        let block_hash = H256::from(HASH_4);
+        let origin = || Origin::signed(Default::default());
+
+        assert_ok!(XBridgeOfETH::push_receipt(
+            origin(),
+            block_hash,
+            2,
+            receipt_proof(2)
+        ));
+        assert_eq!(
+            XBridgeOfETH::pending_deposit(SENDER),
+            vec![(token.clone(), 200_000_000)]
+        );
+        assert_eq!(XAssets::free_balance_of(&who, &token), 0);
+
+        // the memo binds the sender and releases the pending deposit
+        assert_ok!(XBridgeOfETH::push_receipt(
+            origin(),
+            block_hash,
+            1,
+            receipt_proof(1)
+        ));
+        assert_eq!(XBridgeOfETH::pending_deposit(SENDER), vec![]);
+        assert_eq!(XAssets::free_balance_of(&who, &token), 350_000_000);
+    })
+}
+
+#[test]
+fn test_merge() {
+    with_externalities(&mut new_test_ext(), || {
+        init_bridge();
+        for header in [header_1(), header_2(), header_3()].iter() {
+            assert_ok!(XBridgeOfETH::push_header(
+                Origin::signed(relayer()),
+                header.clone()
+            ));
+        }
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer()), header_4_pos()),
+            "Header difficulty should not be zero before the merge"
+        );
+
+        assert_ok!(XBridgeOfETH::set_merge_block_number(4));
+        assert_noop!(
+            XBridgeOfETH::push_header(Origin::signed(relayer()), header_4()),
+            "Header difficulty should be zero after the merge"
+        );
+        // the total difficulty stops growing, the longer chain is the best
+        assert_ok!(XBridgeOfETH::push_header(
+            Origin::signed(relayer()),
+            header_4_pos()
+        ));
+        assert_eq!(XBridgeOfETH::best_header(), H256::from(HASH_4_POS));
+        assert_eq!(
+            XBridgeOfETH::block_hash_for(4),
+            Some(H256::from(HASH_4_POS))
+        );
+        assert_eq!(
+            XBridgeOfETH::header_for(H256::from(HASH_4_POS))
+                .unwrap()
+                .total_difficulty,
+            U256::from(DIFFICULTIES.iter().sum::<u64>())
+        );
+    })
+}
+
+#[test]
+fn test_invalid_lock_event() {
+    with_externalities(&mut new_test_ext(), || {
+        init_deposit();
+        let who = memo_account();
+        let token = b"DAI".to_vec();
+        let block_hash = H256::from(HASH_4);
+        let origin = || Origin::signed(Default::default());
+
+        // the receipt is not marked as handled, so that it could be pushed again after fixing
+        assert_ok!(XBridgeOfETH::set_token_mapping(DAI, token.clone(), 30));
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 1, receipt_proof(1)),
+            "Deposit value is too small"
+        );
+        <TokenMappingFor<Test>>::remove(&DAI);
+        assert_noop!(
+            XBridgeOfETH::push_receipt(origin(), block_hash, 1, receipt_proof(1)),
+            "Erc20 contract is not mapped"
+        );
+        assert!(!XBridgeOfETH::handled_receipt((block_hash, 1)));
+
+        assert_ok!(XBridgeOfETH::set_token_mapping(DAI, token.clone(), 18));
+        assert_ok!(XBridgeOfETH::push_receipt(
+            origin(),
+            block_hash,
+            1,
+            receipt_proof(1)
+        ));
+        assert_eq!(XAssets::free_balance_of(&who, &token), 150_000_000);
+    })
+}
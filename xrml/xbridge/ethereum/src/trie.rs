@@ -0,0 +1,123 @@
+// Copyright 2018-2019 Chainpool.
+
+//! merkle patricia trie proof verification
+
+use rstd::prelude::Vec;
+use rstd::result;
+
+use substrate_primitives::H256;
+use tiny_keccak::keccak256;
+
+use crate::rlp::Rlp;
+
+enum NodeRef<'a> {
+    Hash(H256),
+    Inline(&'a [u8]),
+}
+
+fn to_nibbles(key: &[u8]) -> Vec<u8> {
+    let mut nibbles = Vec::with_capacity(key.len() * 2);
+    for b in key {
+        nibbles.push(b >> 4);
+        nibbles.push(b & 0x0f);
+    }
+    nibbles
+}
+
+/// decode hex-prefix encoded path, returns (is_leaf, nibbles)
+fn decode_path(encoded: &[u8]) -> result::Result<(bool, Vec<u8>), &'static str> {
+    let first = *encoded.first().ok_or("trie node path is empty")?;
+    let flag = first >> 4;
+    if flag > 3 {
+        return Err("trie node path has invalid flag");
+    }
+    let mut nibbles = to_nibbles(encoded);
+    if flag & 1 == 1 {
+        // odd length, the first nibble is the flag
+        nibbles.remove(0);
+    } else {
+        // even length, the first byte is the flag and padding
+        nibbles.drain(..2);
+    }
+    Ok((flag & 2 == 2, nibbles))
+}
+
+fn child_ref<'a>(item: &Rlp<'a>) -> result::Result<Option<NodeRef<'a>>, &'static str> {
+    if item.is_list() {
+        // nodes shorter than 32 bytes are embedded in their parent
+        return Ok(Some(NodeRef::Inline(item.as_raw())));
+    }
+    let data = item.data()?;
+    match data.len() {
+        0 => Ok(None),
+        32 => Ok(Some(NodeRef::Hash(H256::from_slice(data)))),
+        _ => Err("trie node reference is invalid"),
+    }
+}
+
+/// verify `proof` (the nodes on the path from root, in order) for `key` against `root`,
+/// return the value stored under `key`
+pub fn verify_proof(
+    root: &H256,
+    key: &[u8],
+    proof: &[Vec<u8>],
+) -> result::Result<Vec<u8>, &'static str> {
+    let key = to_nibbles(key);
+    let mut key_index = 0;
+    let mut proof_index = 0;
+    let mut wanted = NodeRef::Hash(*root);
+
+    loop {
+        let node_raw: &[u8] = match wanted {
+            NodeRef::Hash(hash) => {
+                let node = proof.get(proof_index).ok_or("trie proof is incomplete")?;
+                if H256::from(keccak256(node)) != hash {
+                    return Err("trie proof node hash mismatch");
+                }
+                proof_index += 1;
+                node
+            }
+            NodeRef::Inline(raw) => raw,
+        };
+
+        let items = Rlp::new(node_raw)?.items()?;
+        let next = match items.len() {
+            17 => {
+                if key_index == key.len() {
+                    let value = items[16].data()?;
+                    if value.is_empty() {
+                        return Err("key not found in trie");
+                    }
+                    return finish(value, proof_index, proof.len());
+                }
+                let child = child_ref(&items[key[key_index] as usize])?;
+                key_index += 1;
+                child
+            }
+            2 => {
+                let (is_leaf, path) = decode_path(items[0].data()?)?;
+                let rest = &key[key_index..];
+                if is_leaf {
+                    if rest != &path[..] {
+                        return Err("key not found in trie");
+                    }
+                    return finish(items[1].data()?, proof_index, proof.len());
+                }
+                if !rest.starts_with(&path) {
+                    return Err("key not found in trie");
+                }
+                key_index += path.len();
+                child_ref(&items[1])?
+            }
+            _ => return Err("trie node has invalid item count"),
+        };
+        wanted = next.ok_or("key not found in trie")?;
+    }
+}
+
+fn finish(value: &[u8], used: usize, total: usize) -> result::Result<Vec<u8>, &'static str> {
+    if used != total {
+        return Err("trie proof has redundant nodes");
+    }
+    Ok(value.to_vec())
+}
@@ -0,0 +1,104 @@
+// Copyright 2018-2019 Chainpool.
+
+//! trustee for ethereum, the trustees custody assets by a multisig contract
+//! which is configured by the owners (secp256k1 addresses) and the threshold
+
+use parity_codec::{Decode, Encode};
+use rstd::{prelude::Vec, result};
+#[cfg(feature = "std")]
+use serde_derive::{Deserialize, Serialize};
+
+use xbridge_common::{
+    traits::{IntoVecu8, TrusteeForChain},
+    types::{TrusteeInfoConfig, TrusteeIntentionProps, TrusteeSessionInfo},
+    utils::two_thirds_unsafe,
+};
+use xsupport::{error, info};
+
+use crate::types::EthereumAddress;
+use crate::{Module, Trait};
+
+/// configuration of the multisig contract for a trustee session
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthereumMultiSigConfig {
+    pub owners: Vec<EthereumAddress>,
+    pub required: u32,
+}
+
+impl IntoVecu8 for EthereumMultiSigConfig {
+    fn into_vecu8(self) -> Vec<u8> {
+        self.encode()
+    }
+
+    fn from_vecu8(src: &[u8]) -> Option<Self> {
+        Decode::decode(&mut &src[..])
+    }
+}
+
+fn check_addresses(addresses: &[EthereumAddress]) -> result::Result<(), &'static str> {
+    let has_duplicate = (1..addresses.len()).any(|i| addresses[i..].contains(&addresses[i - 1]));
+    if has_duplicate {
+        error!("[generate_trustee_session_info]|addresses contains duplicate address");
+        return Err("addresses contains duplicate address");
+    }
+    Ok(())
+}
+
+impl<T: Trait> TrusteeForChain<T::AccountId, EthereumAddress, EthereumMultiSigConfig>
+    for Module<T>
+{
+    fn check_trustee_entity(raw_addr: &[u8]) -> result::Result<EthereumAddress, &'static str> {
+        let addr = EthereumAddress::from_vecu8(raw_addr).ok_or("Invalid ethereum address")?;
+        if addr == EthereumAddress::default() {
+            return Err("not allow zero ethereum address");
+        }
+        Ok(addr)
+    }
+
+    fn generate_trustee_session_info(
+        props: Vec<(T::AccountId, TrusteeIntentionProps<EthereumAddress>)>,
+        config: TrusteeInfoConfig,
+    ) -> result::Result<TrusteeSessionInfo<T::AccountId, EthereumMultiSigConfig>, &'static str>
+    {
+        let (trustees, props_info): (
+            Vec<T::AccountId>,
+            Vec<TrusteeIntentionProps<EthereumAddress>>,
+        ) = props.into_iter().unzip();
+
+        let (hot_keys, cold_keys): (Vec<EthereumAddress>, Vec<EthereumAddress>) = props_info
+            .into_iter()
+            .map(|props| (props.hot_entity, props.cold_entity))
+            .unzip();
+
+        check_addresses(&hot_keys)?;
+        check_addresses(&cold_keys)?;
+
+        if (trustees.len() as u32) < config.min_trustee_count
+            || (trustees.len() as u32) > config.max_trustee_count
+        {
+            error!("[generate_trustee_session_info]|trustees is less/more than {{min:[{:}], max:[{:}]}} people, can't generate trustee addr|trustees:{:?}",
+                   config.min_trustee_count, config.max_trustee_count, trustees);
+            return Err("trustees is less/more than required people, can't generate trustee addr");
+        }
+
+        let required = two_thirds_unsafe(trustees.len() as u32);
+        info!(
+            "[generate_trustee_session_info]|hot_keys:{:?}|cold_keys:{:?}|required:{:}",
+            hot_keys, cold_keys, required
+        );
+
+        Ok(TrusteeSessionInfo {
+            trustee_list: trustees,
+            hot_address: EthereumMultiSigConfig {
+                owners: hot_keys,
+                required,
+            },
+            cold_address: EthereumMultiSigConfig {
+                owners: cold_keys,
+                required,
+            },
+        })
+    }
+}
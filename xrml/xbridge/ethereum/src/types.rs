@@ -0,0 +1,172 @@
+// Copyright 2018-2019 Chainpool.
+
+use parity_codec::{Decode, Encode};
+use rstd::prelude::Vec;
+use rstd::result;
+#[cfg(feature = "std")]
+use serde_derive::{Deserialize, Serialize};
+
+use substrate_primitives::{H256, U256};
+use tiny_keccak::keccak256;
+
+use crate::rlp::Rlp;
+
+pub type EthereumAddress = [u8; 20];
+
+/// fields of an ethereum block header the bridge keeps
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthHeader {
+    pub parent_hash: H256,
+    pub number: u64,
+    pub timestamp: u64,
+    pub receipts_root: H256,
+    pub difficulty: U256,
+    pub gas_limit: u64,
+    pub gas_used: u64,
+}
+
+impl EthHeader {
+    /// decode a rlp encoded header, return it with its hash
+    pub fn decode_rlp(raw: &[u8]) -> result::Result<(H256, Self), &'static str> {
+        let items = Rlp::new(raw)?.items()?;
+        // post-london headers carry more fields, the first 15 are always there
+        if items.len() < 15 {
+            return Err("ethereum header should have at least 15 fields");
+        }
+        if items[12].data()?.len() > 32 {
+            return Err("ethereum header extra data is too long");
+        }
+        let header = EthHeader {
+            parent_hash: items[0].as_h256()?,
+            receipts_root: items[5].as_h256()?,
+            difficulty: items[7].as_u256()?,
+            number: items[8].as_u64()?,
+            gas_limit: items[9].as_u64()?,
+            gas_used: items[10].as_u64()?,
+            timestamp: items[11].as_u64()?,
+        };
+        Ok((H256::from(keccak256(raw)), header))
+    }
+}
+
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct EthHeaderInfo {
+    pub header: EthHeader,
+    pub total_difficulty: U256,
+}
+
+/// an event log in a transaction receipt
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct EthLog {
+    pub address: EthereumAddress,
+    pub topics: Vec<H256>,
+    pub data: Vec<u8>,
+}
+
+/// decode the logs of a successful receipt, both legacy and typed (EIP-2718) receipts are accepted
+pub fn decode_receipt_logs(raw: &[u8]) -> result::Result<Vec<EthLog>, &'static str> {
+    let first = *raw.first().ok_or("receipt is empty")?;
+    // a typed receipt is `type || rlp(receipt)`, type is in [0, 0x7f]
+    let raw = if first <= 0x7f { &raw[1..] } else { raw };
+    let items = Rlp::new(raw)?.items()?;
+    if items.len() != 4 {
+        return Err("receipt should have 4 fields");
+    }
+    // after byzantium the first field is the status code, before it is the state root
+    let status = items[0].data()?;
+    if status.len() != 32 && status != &[1][..] {
+        return Err("receipt status is failed");
+    }
+
+    let mut logs = Vec::new();
+    for log in items[3].items()? {
+        let fields = log.items()?;
+        if fields.len() != 3 {
+            return Err("receipt log should have 3 fields");
+        }
+        let topics = fields[1]
+            .items()?
+            .iter()
+            .map(|t| t.as_h256())
+            .collect::<result::Result<Vec<_>, _>>()?;
+        logs.push(EthLog {
+            address: fields[0].as_address()?,
+            topics,
+            data: fields[2].data()?.to_vec(),
+        });
+    }
+    Ok(logs)
+}
+
+/// a deposit made through the lock contract, decoded from
+/// `Lock(address indexed token, address indexed sender, uint256 value, bytes memo)`
+#[derive(PartialEq, Eq, Clone)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct LockEvent {
+    pub token: EthereumAddress,
+    pub sender: EthereumAddress,
+    pub value: U256,
+    pub memo: Vec<u8>,
+}
+
+pub fn lock_event_topic() -> H256 {
+    H256::from(keccak256(b"Lock(address,address,uint256,bytes)"))
+}
+
+fn topic_to_address(topic: &H256) -> EthereumAddress {
+    let mut addr = EthereumAddress::default();
+    addr.copy_from_slice(&topic.as_bytes()[12..]);
+    addr
+}
+
+fn abi_word_to_usize(word: &[u8]) -> result::Result<usize, &'static str> {
+    if word[..28].iter().any(|b| *b != 0) {
+        return Err("lock event data offset is too large");
+    }
+    Ok(word[28..]
+        .iter()
+        .fold(0_usize, |acc, b| (acc << 8) | *b as usize))
+}
+
+impl LockEvent {
+    /// return None if the log is not a lock event of `contract`
+    pub fn from_log(
+        log: &EthLog,
+        contract: &EthereumAddress,
+    ) -> result::Result<Option<Self>, &'static str> {
+        if &log.address != contract || log.topics.first() != Some(&lock_event_topic()) {
+            return Ok(None);
+        }
+        if log.topics.len() != 3 {
+            return Err("lock event should have 3 topics");
+        }
+        let data = &log.data;
+        if data.len() < 96 {
+            return Err("lock event data is too short");
+        }
+        let value = U256::from_big_endian(&data[..32]);
+        let offset = abi_word_to_usize(&data[32..64])?;
+        let len_end = offset.checked_add(32).ok_or("lock event data is invalid")?;
+        if data.len() < len_end {
+            return Err("lock event data is too short");
+        }
+        let len = abi_word_to_usize(&data[offset..len_end])?;
+        let memo_end = len_end
+            .checked_add(len)
+            .ok_or("lock event data is invalid")?;
+        if data.len() < memo_end {
+            return Err("lock event memo is too short");
+        }
+        Ok(Some(LockEvent {
+            token: topic_to_address(&log.topics[1]),
+            sender: topic_to_address(&log.topics[2]),
+            value,
+            memo: data[len_end..memo_end].to_vec(),
+        }))
+    }
+}
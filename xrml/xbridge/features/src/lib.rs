@@ -11,6 +11,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(test)]
+mod mock;
 #[cfg(test)]
 mod tests;
 
@@ -31,7 +33,7 @@ use system::ensure_signed;
 use xassets::Chain;
 use xmultisig::MultiSigPermission;
 use xr_primitives::XString;
-use xsupport::{error, info};
+use xsupport::{error, info, warn};
 
 use xbridge_common::{
     traits::{TrusteeForChain, TrusteeMultiSig, TrusteeSession},
@@ -47,7 +49,8 @@ pub use xbitcoin::H264;
 
 pub use trustees::{
     BitcoinTrusteeAddrInfo, BitcoinTrusteeIntentionProps, BitcoinTrusteeMultiSig,
-    BitcoinTrusteeSessionInfo,
+    BitcoinTrusteeSessionInfo, EthereumTrusteeAddrInfo, EthereumTrusteeIntentionProps,
+    EthereumTrusteeMultiSig, EthereumTrusteeSessionInfo,
 };
 
 pub use crosschain_binding::{BitcoinAddress, EthereumAddress};
@@ -71,7 +74,7 @@ where
     }
 }
 
-pub trait Trait: system::Trait + xmultisig::Trait + xbitcoin::Trait {
+pub trait Trait: system::Trait + xmultisig::Trait + xbitcoin::Trait + xethereum::Trait {
     type TrusteeMultiSig: TrusteeMultiSigFor<Self::AccountId>;
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 }
@@ -82,6 +85,8 @@ decl_event!(
     {
         SetBitcoinTrusteeProps(AccountId, BitcoinTrusteeIntentionProps),
         BitcoinNewTrustees(u32, BitcoinTrusteeSessionInfo<AccountId>),
+        SetEthereumTrusteeProps(AccountId, EthereumTrusteeIntentionProps),
+        EthereumNewTrustees(u32, EthereumTrusteeSessionInfo<AccountId>),
         //crosschain binding
         /// Record binding info for bitcoin addr(channel) and ChainX AccountId, params: new binding accountid, old binding accountid, crosschain addr, channel
         BitcoinBinding(AccountId, Option<AccountId>, BitcoinAddress, Option<AccountId>),
@@ -100,6 +105,11 @@ decl_module! {
             Self::setup_bitcoin_trustee_impl(who, about, hot_entity, cold_entity)
         }
 
+        pub fn setup_ethereum_trustee(origin, about: XString, hot_entity: EthereumAddress, cold_entity: EthereumAddress) -> Result {
+            let who = ensure_signed(origin)?;
+            Self::setup_ethereum_trustee_impl(who, about, hot_entity, cold_entity)
+        }
+
         /// use for trustee multisig addr
         pub fn transition_trustee_session(origin, chain: Chain, new_trustees: Vec<T::AccountId>) -> Result {
             let who = ensure_signed(origin)?;
@@ -108,6 +118,16 @@ decl_module! {
                 Chain::Bitcoin => {
                     BitcoinTrusteeMultiSig::<T>::check_multisig(&who)?;
                     info!("[transition_trustee_session]|try to transition trustee|from multisig addr:{:?}|chain:{:?}|new_trustees:{:?}", who, chain, new_trustees);
+                    // the absent trustees are dropped as the election does, only root could keep them
+                    if let Some(absent) = new_trustees.iter().find(|who| xbitcoin::Module::<T>::is_absent_trustee(who)) {
+                        error!("[transition_trustee_session]|the trustee missed too many signing deadlines|who:{:?}", absent);
+                        return Err("the trustee missed too many signing deadlines");
+                    }
+                    Self::transition_trustee_session_impl(chain, new_trustees)
+                }
+                Chain::Ethereum => {
+                    EthereumTrusteeMultiSig::<T>::check_multisig(&who)?;
+                    info!("[transition_trustee_session]|try to transition trustee|from multisig addr:{:?}|chain:{:?}|new_trustees:{:?}", who, chain, new_trustees);
                     Self::transition_trustee_session_impl(chain, new_trustees)
                 }
                 _ => {
@@ -144,6 +164,11 @@ decl_storage! {
         pub BitcoinTrusteeSessionInfoOf get(bitcoin_trustee_session_info_of): map u32 => Option<BitcoinTrusteeSessionInfo<T::AccountId>>;
         /// properties for bitcoin trustees
         pub BitcoinTrusteeIntentionPropertiesOf get(bitcoin_trustee_intention_props_of): map T::AccountId => Option<BitcoinTrusteeIntentionProps>;
+        // for ethereum
+        /// all ethereum session trustee multisig contract config
+        pub EthereumTrusteeSessionInfoOf get(ethereum_trustee_session_info_of): map u32 => Option<EthereumTrusteeSessionInfo<T::AccountId>>;
+        /// properties for ethereum trustees
+        pub EthereumTrusteeIntentionPropertiesOf get(ethereum_trustee_intention_props_of): map T::AccountId => Option<EthereumTrusteeIntentionProps>;
         // for other chain
 
         // for crosschain
@@ -187,6 +212,32 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    pub fn setup_ethereum_trustee_impl(
+        who: T::AccountId,
+        about: XString,
+        hot_entity: EthereumAddress,
+        cold_entity: EthereumAddress,
+    ) -> Result {
+        ensure!(
+            xaccounts::Module::<T>::is_intention(&who),
+            "Transactor is not an intention."
+        );
+        xaccounts::is_valid_about(&about)?;
+
+        let hot_addr = xethereum::Module::<T>::check_trustee_entity(&hot_entity)?;
+        let cold_addr = xethereum::Module::<T>::check_trustee_entity(&cold_entity)?;
+
+        let props = EthereumTrusteeIntentionProps {
+            about,
+            hot_entity: hot_addr,
+            cold_entity: cold_addr,
+        };
+
+        EthereumTrusteeIntentionPropertiesOf::<T>::insert(&who, props.clone());
+        Self::deposit_event(RawEvent::SetEthereumTrusteeProps(who, props));
+        Ok(())
+    }
+
     #[inline]
     pub fn current_session_number(chain: Chain) -> u32 {
         match Self::trustee_session_info_len(chain).checked_sub(1) {
@@ -284,6 +335,10 @@ impl<T: Trait> Module<T> {
         let config = Self::trustee_info_config(chain);
         let trustees = match chain {
             Chain::Bitcoin => {
+                // the last session would be retired, funds must have been moved out of it
+                xbitcoin::Module::<T>::ensure_trustee_migration_confirmed(
+                    Self::current_session_number(chain),
+                )?;
                 let session_info = Self::transition_new_session(
                     chain,
                     new_trustees,
@@ -292,6 +347,16 @@ impl<T: Trait> Module<T> {
                     xbitcoin::Module::<T>::generate_trustee_session_info,
                     |session_number, session_info| {
                         BitcoinTrusteeSessionInfoOf::<T>::insert(session_number, &session_info);
+                        if session_number > 0 {
+                            let last_addrs =
+                                Self::bitcoin_trustee_session_info_of(session_number - 1)
+                                    .map(|info| vec![info.hot_address.addr, info.cold_address.addr])
+                                    .unwrap_or_default();
+                            xbitcoin::Module::<T>::start_trustee_migration(
+                                session_number,
+                                &last_addrs,
+                            );
+                        }
                         Module::<T>::deposit_event(RawEvent::BitcoinNewTrustees(
                             session_number,
                             session_info.clone(),
@@ -300,6 +365,23 @@ impl<T: Trait> Module<T> {
                 )?;
                 session_info.trustee_list
             }
+            Chain::Ethereum => {
+                let session_info = Self::transition_new_session(
+                    chain,
+                    new_trustees,
+                    config,
+                    |accountid: &T::AccountId| Self::ethereum_trustee_intention_props_of(accountid),
+                    xethereum::Module::<T>::generate_trustee_session_info,
+                    |session_number, session_info| {
+                        EthereumTrusteeSessionInfoOf::<T>::insert(session_number, &session_info);
+                        Module::<T>::deposit_event(RawEvent::EthereumNewTrustees(
+                            session_number,
+                            session_info.clone(),
+                        ));
+                    },
+                )?;
+                session_info.trustee_list
+            }
             _ => return Err("no transition trustee support for this chain"),
         };
 
@@ -324,6 +406,17 @@ impl<T: Trait> Module<T> {
                     Self::bitcoin_trustee_intention_props_of(accountid)
                 })
             }
+            Chain::Ethereum => {
+                let session_info = Self::try_generate_session_info(
+                    new_trustees,
+                    config,
+                    |accountid: &T::AccountId| Self::ethereum_trustee_intention_props_of(accountid),
+                    xethereum::Module::<T>::generate_trustee_session_info,
+                )?;
+                into_generic_all_info(session_info, |accountid: &T::AccountId| {
+                    Self::ethereum_trustee_intention_props_of(accountid)
+                })
+            }
             _ => return Err("no transition trustee support for this chain"),
         };
 
@@ -351,6 +444,22 @@ impl<T: Trait> Module<T> {
                     })
                 })
             }
+            Chain::Ethereum => {
+                let session_info = if let Some(num) = number {
+                    <Self as TrusteeSession<T::AccountId, EthereumTrusteeAddrInfo>>::trustee_session(
+                        num,
+                    )
+                    .ok()
+                } else {
+                    <Self as TrusteeSession<T::AccountId, EthereumTrusteeAddrInfo>>::current_trustee_session().ok()
+                };
+
+                session_info.map(|info| {
+                    into_generic_all_info(info, |accountid: &T::AccountId| {
+                        Self::ethereum_trustee_intention_props_of(accountid)
+                    })
+                })
+            }
             _ => None,
         }
     }
@@ -361,9 +470,70 @@ impl<T: Trait> Module<T> {
         if let Some(props) = Self::bitcoin_trustee_intention_props_of(who) {
             m.insert(Chain::Bitcoin, props.into());
         }
+        if let Some(props) = Self::ethereum_trustee_intention_props_of(who) {
+            m.insert(Chain::Ethereum, props.into());
+        }
         m
     }
 
+    /// elect the trustees of `chain` from `candidates` which are sorted by stake in descending
+    /// order, only the candidates that have registered trustee properties for `chain` count,
+    /// bitcoin trustees who missed too many signing deadlines are dropped for this election
+    pub fn elect_trustees(chain: Chain, candidates: Vec<T::AccountId>) -> Result {
+        let config = Self::trustee_info_config(chain);
+        let (absent, candidates): (Vec<_>, Vec<_>) = candidates.into_iter().partition(|who| {
+            chain == Chain::Bitcoin && xbitcoin::Module::<T>::is_absent_trustee(who)
+        });
+        if !absent.is_empty() {
+            warn!(
+                "[elect_trustees]|drop the trustees missed too many signing deadlines|absent:{:?}",
+                absent
+            );
+        }
+
+        let elected = candidates
+            .into_iter()
+            .filter(|who| Self::trustee_props_for(who).contains_key(&chain))
+            .take(config.max_trustee_count as usize)
+            .collect::<Vec<_>>();
+
+        if (elected.len() as u32) < config.min_trustee_count {
+            error!(
+                "[elect_trustees]|not enough candidates for trustee election|chain:{:?}|min:{:}|elected:{:?}",
+                chain, config.min_trustee_count, elected
+            );
+            return Err("not enough candidates for trustee election");
+        }
+
+        let is_current = Self::trustee_session_info_for(chain, None)
+            .map(|info| {
+                info.trustees_info.len() == elected.len()
+                    && info
+                        .trustees_info
+                        .iter()
+                        .all(|(who, _)| elected.contains(who))
+            })
+            .unwrap_or(false);
+        if is_current {
+            info!(
+                "[elect_trustees]|elected trustees are the same as current, no need to transition|chain:{:?}",
+                chain
+            );
+        } else {
+            info!(
+                "[elect_trustees]|try to transition trustee|chain:{:?}|new_trustees:{:?}",
+                chain, elected
+            );
+            Self::transition_trustee_session_impl(chain, elected)?;
+        }
+
+        // the absent trustees have been dropped by this election
+        for who in absent.iter() {
+            xbitcoin::Module::<T>::clear_consecutive_missed(who);
+        }
+        Ok(())
+    }
+
     fn deploy_trustee_addr_unsafe(chain: Chain, trustee_list: Vec<T::AccountId>) {
         // generate new addr
         let addr = T::TrusteeMultiSig::multi_sig_addr_for_trustees(chain, &trustee_list);
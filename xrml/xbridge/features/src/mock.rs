@@ -0,0 +1,175 @@
+// Copyright 2018-2019 Chainpool.
+
+use parity_codec::{Decode, Encode};
+
+// Substrate
+use primitives::testing::{Digest, DigestItem, Header, UintAuthorityId};
+use primitives::traits::{BlakeTwo256, IdentityLookup};
+use primitives::BuildStorage;
+use substrate_primitives::ed25519::Public;
+use substrate_primitives::{Blake2Hasher, H256};
+use support::{dispatch::Dispatchable, impl_outer_origin};
+
+use crate::*;
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+pub type AccountId = Public;
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+impl system::Trait for Test {
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Digest = Digest;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type Header = Header;
+    type Event = ();
+    type Log = DigestItem;
+}
+
+impl consensus::Trait for Test {
+    type Log = DigestItem;
+    type SessionKey = UintAuthorityId;
+    type InherentOfflineReport = ();
+}
+
+impl timestamp::Trait for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+}
+
+impl xsystem::Trait for Test {
+    type ValidatorList = MockValidatorList;
+    type Validator = MockValidator;
+}
+
+pub struct MockValidatorList;
+impl xsystem::ValidatorList<AccountId> for MockValidatorList {
+    fn validator_list() -> Vec<AccountId> {
+        vec![]
+    }
+}
+
+pub struct MockValidator;
+impl xsystem::Validator<AccountId> for MockValidator {
+    fn get_validator_by_name(_name: &[u8]) -> Option<AccountId> {
+        Some(AccountId::default())
+    }
+    fn get_validator_name(_: &AccountId) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+impl xaccounts::Trait for Test {
+    type DetermineIntentionJackpotAccountId = MockDeterminator;
+}
+
+pub struct MockDeterminator;
+impl xaccounts::IntentionJackpotAccountIdFor<AccountId> for MockDeterminator {
+    fn accountid_for_unsafe(_: &AccountId) -> AccountId {
+        AccountId::default()
+    }
+    fn accountid_for_safe(_: &AccountId) -> Option<AccountId> {
+        Some(AccountId::default())
+    }
+}
+
+impl xassets::Trait for Test {
+    type Balance = u64;
+    type OnNewAccount = ();
+    type OnAssetChanged = ();
+    type OnAssetRegisterOrRevoke = ();
+    type DetermineTokenJackpotAccountId = ();
+    type Event = ();
+}
+
+impl xrecords::Trait for Test {
+    type Event = ();
+}
+
+impl xfee_manager::Trait for Test {
+    type Event = ();
+    type AssetPrice = ();
+}
+
+impl xbridge_common::Trait for Test {
+    type Event = ();
+}
+
+impl xbitcoin::Trait for Test {
+    type XBitcoinLockup = Self;
+    type AccountExtractor = xbridge_common::extractor::Extractor<AccountId>;
+    type TrusteeSessionProvider = XBridgeFeatures;
+    type TrusteeMultiSigProvider = trustees::BitcoinTrusteeMultiSig<Test>;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
+impl xbitcoin::lockup::Trait for Test {
+    type Event = ();
+}
+
+impl xethereum::Trait for Test {
+    type AccountExtractor = xbridge_common::extractor::Extractor<AccountId>;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, Debug)]
+pub struct TestCall;
+impl Dispatchable for TestCall {
+    type Origin = Origin;
+    type Trait = ();
+
+    fn dispatch(self, _origin: Self::Origin) -> Result {
+        Ok(())
+    }
+}
+
+pub struct TrusteeCall(TestCall);
+impl From<TestCall> for TrusteeCall {
+    fn from(call: TestCall) -> Self {
+        TrusteeCall(call)
+    }
+}
+impl xmultisig::LimitedCall<AccountId> for TrusteeCall {
+    fn allow(&self) -> bool {
+        false
+    }
+
+    fn exec(&self, _exerciser: &AccountId) -> Result {
+        Err("not allow")
+    }
+}
+
+impl xmultisig::Trait for Test {
+    type MultiSig = xmultisig::SimpleMultiSigIdFor<Test>;
+    type GenesisMultiSig = xmultisig::ChainXGenesisMultisig<Test>;
+    type Proposal = TestCall;
+    type TrusteeCall = TrusteeCall;
+    type ProposalHook = ();
+    type Event = ();
+}
+
+impl Trait for Test {
+    type TrusteeMultiSig = SimpleTrusteeMultiSigIdFor<Test>;
+    type Event = ();
+}
+
+pub type XBridgeFeatures = Module<Test>;
+
+pub fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
+    let r = system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap()
+        .0;
+    r.into()
+}
@@ -1,6 +1,181 @@
 // Copyright 2018-2019 Chainpool.
 
+use runtime_io::with_externalities;
+use support::{assert_noop, assert_ok};
+
+use super::mock::*;
+use super::*;
+
 #[test]
 fn it_works() {
     assert_eq!(2 + 2, 4);
 }
+
+fn trustees() -> Vec<AccountId> {
+    (1..=3).map(|i| AccountId::from_raw([i; 32])).collect()
+}
+
+fn register_intention(who: &AccountId) {
+    xaccounts::IntentionNameOf::<Test>::insert(who, who.as_ref()[..4].to_vec());
+}
+
+fn setup_ethereum_trustees() {
+    for (i, who) in trustees().iter().enumerate() {
+        register_intention(who);
+        assert_ok!(XBridgeFeatures::setup_ethereum_trustee(
+            Origin::signed(who.clone()),
+            b"about".to_vec(),
+            [i as u8 + 1; 20],
+            [i as u8 + 11; 20],
+        ));
+    }
+}
+
+#[test]
+fn test_setup_ethereum_trustee() {
+    with_externalities(&mut new_test_ext(), || {
+        let who = AccountId::from_raw([1; 32]);
+        let origin = || Origin::signed(who.clone());
+        assert_noop!(
+            XBridgeFeatures::setup_ethereum_trustee(origin(), b"about".to_vec(), [1; 20], [2; 20]),
+            "Transactor is not an intention."
+        );
+
+        register_intention(&who);
+        assert_noop!(
+            XBridgeFeatures::setup_ethereum_trustee(
+                origin(),
+                b"<about>".to_vec(),
+                [1; 20],
+                [2; 20]
+            ),
+            "'<' and '>' are not allowed, which could be abused off-chain."
+        );
+        assert_noop!(
+            XBridgeFeatures::setup_ethereum_trustee(origin(), b"about".to_vec(), [0; 20], [2; 20]),
+            "not allow zero ethereum address"
+        );
+
+        assert_ok!(XBridgeFeatures::setup_ethereum_trustee(
+            origin(),
+            b"about".to_vec(),
+            [1; 20],
+            [2; 20]
+        ));
+        assert_eq!(
+            XBridgeFeatures::ethereum_trustee_intention_props_of(&who),
+            Some(EthereumTrusteeIntentionProps {
+                about: b"about".to_vec(),
+                hot_entity: [1; 20],
+                cold_entity: [2; 20],
+            })
+        );
+        let props = XBridgeFeatures::trustee_props_for(&who);
+        assert!(props.contains_key(&Chain::Ethereum));
+        assert!(!props.contains_key(&Chain::Bitcoin));
+    })
+}
+
+#[test]
+fn test_ethereum_trustee_session() {
+    with_externalities(&mut new_test_ext(), || {
+        assert_ok!(XBridgeFeatures::set_trustee_info_config(
+            Chain::Ethereum,
+            TrusteeInfoConfig {
+                min_trustee_count: 3,
+                max_trustee_count: 15,
+            }
+        ));
+        setup_ethereum_trustees();
+        let trustees = trustees();
+        let transition = |new_trustees: Vec<AccountId>| {
+            XBridgeFeatures::transition_trustee_session_by_root(Chain::Ethereum, new_trustees)
+        };
+
+        let mut unregistered = trustees.clone();
+        unregistered.push(AccountId::from_raw([4; 32]));
+        assert_noop!(
+            transition(unregistered),
+            "not all candidate has registered as a trustee yet"
+        );
+        assert_noop!(
+            transition(vec![
+                trustees[0].clone(),
+                trustees[0].clone(),
+                trustees[1].clone()
+            ]),
+            "existing duplicate account"
+        );
+        assert_noop!(
+            transition(trustees[..2].to_vec()),
+            "trustees is less/more than required people, can't generate trustee addr"
+        );
+
+        assert_ok!(transition(trustees.clone()));
+        assert_eq!(XBridgeFeatures::current_session_number(Chain::Ethereum), 0);
+        let info = XBridgeFeatures::ethereum_trustee_session_info_of(0).unwrap();
+        assert_eq!(info.trustee_list, trustees);
+        assert_eq!(
+            info.hot_address,
+            EthereumTrusteeAddrInfo {
+                owners: vec![[1; 20], [2; 20], [3; 20]],
+                required: 2,
+            }
+        );
+        assert_eq!(
+            info.cold_address,
+            EthereumTrusteeAddrInfo {
+                owners: vec![[11; 20], [12; 20], [13; 20]],
+                required: 2,
+            }
+        );
+        assert_eq!(
+            XBridgeFeatures::trustee_multisig_addr(Chain::Ethereum),
+            SimpleTrusteeMultiSigIdFor::<Test>::multi_sig_addr_for_trustees(
+                Chain::Ethereum,
+                &trustees
+            )
+        );
+        let all_info = XBridgeFeatures::trustee_session_info_for(Chain::Ethereum, None).unwrap();
+        assert_eq!(all_info.trustees_info.len(), 3);
+
+        // the hot addresses of the trustees should be different
+        assert_ok!(XBridgeFeatures::setup_ethereum_trustee(
+            Origin::signed(trustees[2].clone()),
+            b"about".to_vec(),
+            [1; 20],
+            [13; 20]
+        ));
+        assert_noop!(
+            transition(trustees.clone()),
+            "addresses contains duplicate address"
+        );
+        assert!(XBridgeFeatures::ethereum_trustee_session_info_of(1).is_none());
+    })
+}
+
+#[test]
+fn test_absent_trustee_transition() {
+    with_externalities(&mut new_test_ext(), || {
+        let trustees = trustees();
+        let multisig = AccountId::from_raw([9; 32]);
+        TrusteeMultiSigAddr::<Test>::insert(Chain::Bitcoin, multisig.clone());
+        xbitcoin::TrusteeSignStatsOf::<Test>::mutate(&trustees[1], |stats| {
+            stats.consecutive_missed = xbitcoin::Module::<Test>::max_consecutive_missed()
+        });
+
+        // the trustees could not keep an absent one, but root could
+        assert_noop!(
+            XBridgeFeatures::transition_trustee_session(
+                Origin::signed(multisig),
+                Chain::Bitcoin,
+                trustees.clone()
+            ),
+            "the trustee missed too many signing deadlines"
+        );
+        assert_noop!(
+            XBridgeFeatures::transition_trustee_session_by_root(Chain::Bitcoin, trustees),
+            "not all candidate has registered as a trustee yet"
+        );
+    })
+}
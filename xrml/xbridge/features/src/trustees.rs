@@ -10,6 +10,9 @@ use xsupport::{error, warn};
 // for bitcoin trustee
 pub use btc_keys::Public as BitcoinPublic;
 pub use xbitcoin::TrusteeAddrInfo as BitcoinTrusteeAddrInfo;
+// for ethereum trustee
+pub use xethereum::EthereumAddress as EthereumTrusteeType;
+pub use xethereum::EthereumMultiSigConfig as EthereumTrusteeAddrInfo;
 
 use super::{Module, Trait};
 
@@ -21,6 +24,11 @@ pub type BitcoinTrusteeIntentionProps = TrusteeIntentionProps<BitcoinTrusteeType
 pub type BitcoinTrusteeSessionInfo<AccountId> =
     TrusteeSessionInfo<AccountId, BitcoinTrusteeAddrInfo>;
 
+pub type EthereumTrusteeIntentionProps = TrusteeIntentionProps<EthereumTrusteeType>;
+
+pub type EthereumTrusteeSessionInfo<AccountId> =
+    TrusteeSessionInfo<AccountId, EthereumTrusteeAddrInfo>;
+
 /// for bitcoin
 impl<T: Trait> TrusteeSession<T::AccountId, BitcoinTrusteeAddrInfo> for Module<T> {
     fn trustee_session(
@@ -37,6 +45,10 @@ impl<T: Trait> TrusteeSession<T::AccountId, BitcoinTrusteeAddrInfo> for Module<T
         })
     }
 
+    fn current_trustee_session_number() -> u32 {
+        Self::current_session_number(Chain::Bitcoin)
+    }
+
     fn current_trustee_session(
     ) -> result::Result<TrusteeSessionInfo<T::AccountId, BitcoinTrusteeAddrInfo>, &'static str>
     {
@@ -65,3 +77,52 @@ impl<T: Trait> TrusteeMultiSig<T::AccountId> for BitcoinTrusteeMultiSig<T> {
         Module::<T>::trustee_multisig_addr(Chain::Bitcoin)
     }
 }
+
+/// for ethereum
+impl<T: Trait> TrusteeSession<T::AccountId, EthereumTrusteeAddrInfo> for Module<T> {
+    fn trustee_session(
+        number: u32,
+    ) -> result::Result<TrusteeSessionInfo<T::AccountId, EthereumTrusteeAddrInfo>, &'static str>
+    {
+        Self::ethereum_trustee_session_info_of(number).ok_or_else(|| {
+            error!(
+                "[trustee_session]|not found info for this session|chain:{:?}|number:{:}",
+                Chain::Ethereum,
+                number
+            );
+            "not found info for this session"
+        })
+    }
+
+    fn current_trustee_session_number() -> u32 {
+        Self::current_session_number(Chain::Ethereum)
+    }
+
+    fn current_trustee_session(
+    ) -> result::Result<TrusteeSessionInfo<T::AccountId, EthereumTrusteeAddrInfo>, &'static str>
+    {
+        let number = Self::current_session_number(Chain::Ethereum);
+        Self::trustee_session(number)
+    }
+
+    fn last_trustee_session(
+    ) -> result::Result<TrusteeSessionInfo<T::AccountId, EthereumTrusteeAddrInfo>, &'static str>
+    {
+        let number = Self::last_session_number(Chain::Ethereum);
+        Self::trustee_session(number).map_err(|e| {
+            warn!(
+                "[last_trustee_session]|last trustee session not exist yet for this chain|Chain:{:?}",
+                Chain::Ethereum
+            );
+            e
+        })
+    }
+}
+
+pub struct EthereumTrusteeMultiSig<T: Trait>(::rstd::marker::PhantomData<T>);
+
+impl<T: Trait> TrusteeMultiSig<T::AccountId> for EthereumTrusteeMultiSig<T> {
+    fn multisig_for_trustees() -> T::AccountId {
+        Module::<T>::trustee_multisig_addr(Chain::Ethereum)
+    }
+}
@@ -0,0 +1,55 @@
+// Copyright 2019 Chainpool
+
+//! EIP-712 typed structured data for SDOT claims
+//!
+//! the signed message is
+//! ```text
+//! EIP712Domain(string name,string version,bytes32 salt)
+//! Claim(string account,string network)
+//! ```
+//! `salt` is the genesis hash of ChainX and `network` is `mainnet` or `testnet`,
+//! so a claim signed for one network could not be replayed on another.
+
+use tiny_keccak::keccak256;
+
+use xsystem::NetworkType;
+
+pub const DOMAIN_NAME: &[u8] = b"ChainX SDOT Claim";
+pub const DOMAIN_VERSION: &[u8] = b"1";
+
+const DOMAIN_TYPE: &[u8] = b"EIP712Domain(string name,string version,bytes32 salt)";
+const CLAIM_TYPE: &[u8] = b"Claim(string account,string network)";
+
+pub fn network_name(network: &NetworkType) -> &'static [u8] {
+    match network {
+        NetworkType::Mainnet => b"mainnet",
+        NetworkType::Testnet => b"testnet",
+    }
+}
+
+fn hash_concat(items: &[&[u8]]) -> [u8; 32] {
+    let mut buf = rstd::prelude::Vec::with_capacity(items.len() * 32);
+    for item in items {
+        buf.extend_from_slice(item);
+    }
+    keccak256(&buf)
+}
+
+pub fn domain_separator(genesis_hash: &[u8]) -> [u8; 32] {
+    hash_concat(&[
+        &keccak256(DOMAIN_TYPE),
+        &keccak256(DOMAIN_NAME),
+        &keccak256(DOMAIN_VERSION),
+        genesis_hash,
+    ])
+}
+
+/// the hash to be signed, `\x19\x01 ‖ domainSeparator ‖ hashStruct(claim)`
+pub fn claim_digest(account: &[u8], network: &[u8], genesis_hash: &[u8]) -> [u8; 32] {
+    let claim = hash_concat(&[
+        &keccak256(CLAIM_TYPE),
+        &keccak256(account),
+        &keccak256(network),
+    ]);
+    hash_concat(&[b"\x19\x01", &domain_separator(genesis_hash), &claim])
+}
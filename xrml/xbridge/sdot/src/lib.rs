@@ -2,6 +2,8 @@
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
+pub mod eip712;
+mod mock;
 mod tests;
 pub mod types;
 
@@ -9,9 +11,8 @@ use parity_codec::Encode;
 use tiny_keccak::keccak256;
 
 // Substrate
-#[cfg(feature = "std")]
 use primitives::traits::Zero;
-use rstd::prelude::*;
+use rstd::{prelude::*, result};
 use support::{
     decl_event, decl_module, decl_storage, dispatch::Result, ensure, StorageMap, StorageValue,
 };
@@ -26,7 +27,7 @@ use xsupport::{error, warn};
 pub use self::types::{EcdsaSignature, EthereumAddress};
 
 /// Configuration trait.
-pub trait Trait: xsystem::Trait + xassets::Trait + xrecords::Trait {
+pub trait Trait: xsystem::Trait + xaccounts::Trait + xassets::Trait + xrecords::Trait {
     type AccountExtractor: Extractable<Self::AccountId>;
     type CrossChainProvider: CrossChainBinding<Self::AccountId, EthereumAddress>;
     /// The overarching event type.
@@ -37,10 +38,17 @@ decl_event!(
     pub enum Event<T>
     where
         <T as system::Trait>::AccountId,
+        <T as system::Trait>::BlockNumber,
         <T as xassets::Trait>::Balance
     {
         /// Someone claimed some DOTs.
         Claimed(AccountId, EthereumAddress, Balance),
+        /// Someone claimed some DOTs with an EIP-712 typed signature.
+        TypedClaimed(AccountId, EthereumAddress, Balance),
+        /// The claim deadline has been set.
+        ClaimDeadlineSet(BlockNumber),
+        /// Unclaimed DOTs of an Ethereum address have been swept into the account.
+        Swept(AccountId, EthereumAddress, Balance),
     }
 );
 
@@ -52,6 +60,12 @@ decl_storage! {
         pub Total get(total) build(|config: &GenesisConfig<T>| {
             config.claims.iter().fold(Zero::zero(), |acc: T::Balance, &(_, n)| acc + n)
         }): T::Balance;
+        /// Ethereum addresses in the genesis claims, used for listing the unclaimed.
+        pub ClaimAddresses get(claim_addresses) build(|config: &GenesisConfig<T>| {
+            config.claims.iter().map(|(a, _)| a.clone()).collect::<Vec<_>>()
+        }): Vec<EthereumAddress>;
+        /// No claim is accepted after this block, the unclaimed could be swept then.
+        pub ClaimDeadline get(claim_deadline): Option<T::BlockNumber>;
     }
     add_extra_genesis {
         config(claims): Vec<(EthereumAddress, T::Balance)>;
@@ -77,33 +91,124 @@ decl_module! {
                 }
             };
 
-            let addr_type = xsystem::Module::<T>::address_type();
-            let (account_id, channel_name) = handle_input_data::<T>(&input_data, addr_type).ok_or("Extract account info error")?;
+            let balance = Self::do_claim(signer, &input_data)?;
 
-            let balance = match <Claims<T>>::take(&signer) {
-                Some(balance) => balance,
+            // Let's deposit an event to let the outside world know this happened.
+            Self::deposit_event(RawEvent::Claimed(sender, signer, balance));
+
+            Ok(())
+        }
+
+        /// Make a claim with an EIP-712 typed signature of `Claim(string account,string network)`,
+        /// `account` is the input data, see `eip712` for the domain.
+        fn claim_typed(origin, ethereum_signature: EcdsaSignature, input_data: Vec<u8>) -> Result {
+            let sender = ensure_signed(origin)?;
+
+            let (network, _) = xsystem::Module::<T>::network_props();
+            // the genesis hash of ChainX is the salt of the EIP-712 domain, `BlockHash(0)` is only
+            // a placeholder in the genesis storage and replaced when block 1 is initialized
+            let genesis_hash = system::Module::<T>::block_hash(T::BlockNumber::zero());
+            let digest = eip712::claim_digest(&input_data, eip712::network_name(&network), genesis_hash.as_ref());
+
+            let signer = match eth_recover_hash(&ethereum_signature, &digest) {
+                Some(eth_address) => eth_address,
                 None => {
-                    warn!("[sdot_claim]|The Ethereum address `{:?}` has no SDOT claims", signer);
-                    return Err("The Ethereum address has no SDOT claims");
+                    error!("[sdot_claim_typed]|Invalid Ethereum typed signature|signature:{:?}|data:{:?}", ethereum_signature, input_data);
+                    return Err("Invalid Ethereum typed signature");
                 }
             };
 
-            let total = Self::total();
-            ensure!(total >= balance, "Balance is less than the total amount of SDOT");
-            <Total<T>>::mutate(|t| *t -= balance);
+            let balance = Self::do_claim(signer, &input_data)?;
 
-            deposit_token::<T>(&account_id, balance);
+            Self::deposit_event(RawEvent::TypedClaimed(sender, signer, balance));
 
-            update_binding::<T>(&account_id, signer, channel_name);
+            Ok(())
+        }
 
-            // Let's deposit an event to let the outside world know this happened.
-            Self::deposit_event(RawEvent::Claimed(sender, signer, balance));
+        /// Set the block after which no claim is accepted, it can't be changed once passed, since
+        /// the unclaimed could have been swept then.
+        fn set_claim_deadline(deadline: T::BlockNumber) -> Result {
+            ensure!(!Self::is_claim_expired(), "The claim deadline has passed");
+            ensure!(deadline > system::Module::<T>::block_number(), "The claim deadline should be in the future");
+            <ClaimDeadline<T>>::put(deadline);
+            Self::deposit_event(RawEvent::ClaimDeadlineSet(deadline));
+            Ok(())
+        }
+
+        /// Backfill the Ethereum addresses of the genesis claims.
+        fn set_claim_addresses(addresses: Vec<EthereumAddress>) {
+            <ClaimAddresses<T>>::put(addresses);
+        }
 
+        /// Sweep the unclaimed DOTs of `addresses` into the council account after the claim deadline.
+        fn sweep_unclaimed(addresses: Vec<EthereumAddress>) -> Result {
+            ensure!(Self::is_claim_expired(), "The claim deadline has not passed");
+
+            let council = xaccounts::Module::<T>::council_account();
+            for address in addresses {
+                if let Some(balance) = Self::claims(&address) {
+                    if Self::total() < balance {
+                        error!("[sweep_unclaimed]|Balance is less than the total amount of SDOT|address:{:?}|balance:{:}", address, balance);
+                        continue;
+                    }
+                    <Claims<T>>::remove(&address);
+                    <Total<T>>::mutate(|t| *t -= balance);
+                    deposit_token::<T>(&council, balance);
+                    Self::deposit_event(RawEvent::Swept(council.clone(), address, balance));
+                }
+            }
             Ok(())
         }
     }
 }
 
+impl<T: Trait> Module<T> {
+    fn is_claim_expired() -> bool {
+        match Self::claim_deadline() {
+            Some(deadline) => system::Module::<T>::block_number() > deadline,
+            None => false,
+        }
+    }
+
+    fn do_claim(
+        signer: EthereumAddress,
+        input_data: &[u8],
+    ) -> result::Result<T::Balance, &'static str> {
+        ensure!(
+            !Self::is_claim_expired(),
+            "The SDOT claim deadline has passed"
+        );
+
+        let addr_type = xsystem::Module::<T>::address_type();
+        let (account_id, channel_name) =
+            handle_input_data::<T>(input_data, addr_type).ok_or("Extract account info error")?;
+
+        let balance = match <Claims<T>>::take(&signer) {
+            Some(balance) => balance,
+            None => {
+                warn!(
+                    "[sdot_claim]|The Ethereum address `{:?}` has no SDOT claims",
+                    signer
+                );
+                return Err("The Ethereum address has no SDOT claims");
+            }
+        };
+
+        let total = Self::total();
+        ensure!(
+            total >= balance,
+            "Balance is less than the total amount of SDOT"
+        );
+        <Total<T>>::mutate(|t| *t -= balance);
+
+        deposit_token::<T>(&account_id, balance);
+
+        update_binding::<T>(&account_id, signer, channel_name);
+
+        Ok(balance)
+    }
+}
+
 impl<T: Trait> ChainT for Module<T> {
     const TOKEN: &'static [u8] = b"SDOT";
 
@@ -146,9 +251,12 @@ fn ecdsa_recover(sig: &EcdsaSignature, msg: &[u8; 32]) -> Option<[u8; 64]> {
 }
 
 fn eth_recover(s: &EcdsaSignature, sign_data: &[u8]) -> Option<EthereumAddress> {
-    let msg = keccak256(sign_data);
+    eth_recover_hash(s, &keccak256(sign_data))
+}
+
+fn eth_recover_hash(s: &EcdsaSignature, msg: &[u8; 32]) -> Option<EthereumAddress> {
     let mut res = EthereumAddress::default();
-    res.copy_from_slice(&keccak256(&ecdsa_recover(s, &msg)?[..])[12..]);
+    res.copy_from_slice(&keccak256(&ecdsa_recover(s, msg)?[..])[12..]);
     Some(res)
 }
 
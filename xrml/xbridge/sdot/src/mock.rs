@@ -0,0 +1,147 @@
+// Copyright 2018-2019 Chainpool.
+
+#![cfg(test)]
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+
+use crate::*;
+
+// Substrate
+use primitives::testing::{Digest, DigestItem, Header, UintAuthorityId};
+use primitives::traits::{BlakeTwo256, IdentityLookup};
+use primitives::BuildStorage;
+use substrate_primitives::ed25519::Public;
+use substrate_primitives::{Blake2Hasher, H256};
+use support::impl_outer_origin;
+
+impl_outer_origin! {
+    pub enum Origin for Test {}
+}
+
+pub type AccountId = Public;
+
+#[derive(Clone, Eq, PartialEq)]
+pub struct Test;
+
+impl system::Trait for Test {
+    type Origin = Origin;
+    type Index = u64;
+    type BlockNumber = u64;
+    type Hash = H256;
+    type Hashing = BlakeTwo256;
+    type Digest = Digest;
+    type AccountId = AccountId;
+    type Lookup = IdentityLookup<AccountId>;
+    type Header = Header;
+    type Event = ();
+    type Log = DigestItem;
+}
+
+impl consensus::Trait for Test {
+    type Log = DigestItem;
+    type SessionKey = UintAuthorityId;
+    type InherentOfflineReport = ();
+}
+
+impl timestamp::Trait for Test {
+    type Moment = u64;
+    type OnTimestampSet = ();
+}
+
+impl xsystem::Trait for Test {
+    type ValidatorList = MockValidatorList;
+    type Validator = MockValidator;
+}
+
+pub struct MockValidatorList;
+impl xsystem::ValidatorList<AccountId> for MockValidatorList {
+    fn validator_list() -> Vec<AccountId> {
+        vec![]
+    }
+}
+
+pub struct MockValidator;
+impl xsystem::Validator<AccountId> for MockValidator {
+    fn get_validator_by_name(_name: &[u8]) -> Option<AccountId> {
+        Some(AccountId::default())
+    }
+    fn get_validator_name(_: &AccountId) -> Option<Vec<u8>> {
+        None
+    }
+}
+
+impl xaccounts::Trait for Test {
+    type DetermineIntentionJackpotAccountId = MockDeterminator;
+}
+
+pub struct MockDeterminator;
+impl xaccounts::IntentionJackpotAccountIdFor<AccountId> for MockDeterminator {
+    fn accountid_for_unsafe(_: &AccountId) -> AccountId {
+        AccountId::default()
+    }
+    fn accountid_for_safe(_: &AccountId) -> Option<AccountId> {
+        Some(AccountId::default())
+    }
+}
+
+impl xassets::Trait for Test {
+    type Balance = u64;
+    type OnNewAccount = ();
+    type OnAssetChanged = ();
+    type OnAssetRegisterOrRevoke = ();
+    type DetermineTokenJackpotAccountId = ();
+    type Event = ();
+}
+
+impl xrecords::Trait for Test {
+    type Event = ();
+}
+
+impl Trait for Test {
+    type AccountExtractor = xbridge_common::extractor::Extractor<AccountId>;
+    type CrossChainProvider = MockCrossChain;
+    type Event = ();
+}
+
+thread_local! {
+    static BINDING: RefCell<BTreeMap<EthereumAddress, AccountId>> = RefCell::new(BTreeMap::new());
+}
+
+pub struct MockCrossChain;
+impl xbridge_common::traits::CrossChainBinding<AccountId, EthereumAddress> for MockCrossChain {
+    fn update_binding(who: &AccountId, addr: EthereumAddress, _: Option<Vec<u8>>) {
+        BINDING.with(|b| b.borrow_mut().insert(addr, who.clone()));
+    }
+
+    fn get_binding_info(addr: &EthereumAddress) -> Option<(AccountId, Option<AccountId>)> {
+        BINDING.with(|b| b.borrow().get(addr).map(|who| (who.clone(), None)))
+    }
+}
+
+pub type System = system::Module<Test>;
+pub type XAssets = xassets::Module<Test>;
+pub type XBridgeOfSDOT = Module<Test>;
+
+pub fn new_test_ext(
+    claims: Vec<(EthereumAddress, u64)>,
+) -> runtime_io::TestExternalities<Blake2Hasher> {
+    BINDING.with(|b| b.borrow_mut().clear());
+
+    let mut r = system::GenesisConfig::<Test>::default()
+        .build_storage()
+        .unwrap()
+        .0;
+
+    r.extend(
+        xsystem::GenesisConfig::<Test> {
+            network_props: (xsystem::NetworkType::Mainnet, 44),
+            _genesis_phantom_data: Default::default(),
+        }
+        .build_storage()
+        .unwrap()
+        .0,
+    );
+    r.extend(GenesisConfig::<Test> { claims }.build_storage().unwrap().0);
+    r.into()
+}
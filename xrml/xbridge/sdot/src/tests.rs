@@ -5,8 +5,47 @@
 use ethereum_types::H160;
 use hex_literal::hex;
 
+use runtime_io::with_externalities;
+use substrate_primitives::H256;
+use support::{assert_noop, assert_ok};
+
+use super::mock::*;
 use super::*;
 
+const GENESIS_HASH: [u8; 32] =
+    hex!("1b36d8f0a5b3d2e5b5d0c0a46a5b7e2f0ea0f1eae1d28e6d7f41f1e1a5f2c0e3");
+// the private key is 0x4c0883a69102937d6231471b5dbb6204fe5129617082792ae468d01a3f362318
+const TYPED_SIGNER: EthereumAddress = hex!("2c7536e3605d9c16a7a3d7b1898e529396a65c23");
+const TYPED_ACCOUNT: &[u8] = b"5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg@DeVal";
+
+// typed signature of `TYPED_ACCOUNT` on mainnet with `GENESIS_HASH`
+fn typed_signature() -> EcdsaSignature {
+    EcdsaSignature(
+        hex!("6e6b3ffa2f949b543a1a828f4138f801ff54c2cb698359c9b38f93329e520e4a"),
+        hex!("4a07fef26277587e2435fb5f275c565a1eb1d543037e6319691aaab1882f80b5"),
+        0,
+    )
+}
+
+fn register_sdot() {
+    let asset = xassets::Asset::new(
+        b"SDOT".to_vec(),
+        b"Shadow DOT".to_vec(),
+        Chain::Ethereum,
+        3,
+        b"ChainX's Shadow Polkadot from Ethereum".to_vec(),
+    )
+    .unwrap();
+    assert_ok!(XAssets::register_asset(asset, true, false));
+}
+
+fn typed_account() -> AccountId {
+    // public key of `5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg`
+    AccountId::from_slice(&hex!(
+        "88bbde145f26797e27f495730c4782074ee802243ffe17a2c30a8148dac101a8"
+    ))
+}
+
 #[test]
 fn test_recover_eth_address() {
     // ethereum tx hash = 0xb81680f5224bdd2e7b55075f1332af1e85026b448ff38d3ae2e8e014188ddec0
@@ -117,3 +156,173 @@ fn test_recover_eth_address() {
         Some(from.to_fixed_bytes())
     );
 }
+
+#[test]
+fn test_recover_typed_claim() {
+    let from = H160::from_slice(&TYPED_SIGNER);
+    let account = TYPED_ACCOUNT;
+    let genesis_hash = GENESIS_HASH;
+
+    assert_eq!(
+        eip712::domain_separator(&genesis_hash),
+        hex!("4965c857ccdd05aa7aec36699ad2a6f9c68f06a2c0b1c1a45d3700ab9cb1257c")
+    );
+    let digest = eip712::claim_digest(
+        account,
+        eip712::network_name(&xsystem::NetworkType::Mainnet),
+        &genesis_hash,
+    );
+    assert_eq!(
+        digest,
+        hex!("8e6f14f3b3e45d75387bf9d37c9902c019c9697db490607ac9bd93fe0f91e8ed")
+    );
+
+    let signature = typed_signature();
+    assert_eq!(
+        eth_recover_hash(&signature, &digest),
+        Some(from.to_fixed_bytes())
+    );
+
+    // the same signature is not valid on another network or chain
+    let digest = eip712::claim_digest(
+        account,
+        eip712::network_name(&xsystem::NetworkType::Testnet),
+        &genesis_hash,
+    );
+    assert_ne!(
+        eth_recover_hash(&signature, &digest),
+        Some(from.to_fixed_bytes())
+    );
+    let digest = eip712::claim_digest(
+        account,
+        eip712::network_name(&xsystem::NetworkType::Mainnet),
+        &[0; 32],
+    );
+    assert_ne!(
+        eth_recover_hash(&signature, &digest),
+        Some(from.to_fixed_bytes())
+    );
+}
+
+#[test]
+fn test_claim_typed() {
+    with_externalities(&mut new_test_ext(vec![(TYPED_SIGNER, 1000)]), || {
+        register_sdot();
+        let origin = || Origin::signed(Default::default());
+        let token = b"SDOT".to_vec();
+
+        // the real genesis hash is the parent hash of block 1
+        System::initialize(&1, &H256::from(GENESIS_HASH), &Default::default());
+        assert_eq!(System::block_hash(0), H256::from(GENESIS_HASH));
+        // kept in the later blocks
+        System::initialize(&2, &[2u8; 32].into(), &Default::default());
+        assert_eq!(System::block_hash(0), H256::from(GENESIS_HASH));
+
+        // signed for another account
+        assert_noop!(
+            XBridgeOfSDOT::claim_typed(
+                origin(),
+                typed_signature(),
+                b"5SjJhFQKTVTLbdcYgV5eZEhxSCo4qqYNMG5LrBanAWCG1fZg".to_vec()
+            ),
+            "The Ethereum address has no SDOT claims"
+        );
+
+        assert_ok!(XBridgeOfSDOT::claim_typed(
+            origin(),
+            typed_signature(),
+            TYPED_ACCOUNT.to_vec()
+        ));
+        assert_eq!(XAssets::free_balance_of(&typed_account(), &token), 1000);
+        assert_eq!(XBridgeOfSDOT::claims(&TYPED_SIGNER), None);
+        assert_eq!(XBridgeOfSDOT::total(), 0);
+
+        assert_noop!(
+            XBridgeOfSDOT::claim_typed(origin(), typed_signature(), TYPED_ACCOUNT.to_vec()),
+            "The Ethereum address has no SDOT claims"
+        );
+    })
+}
+
+#[test]
+fn test_claim_deadline_and_sweep() {
+    // signer of the first ethereum tx in `test_recover_eth_address`
+    let signer = hex!("82e2b7d189a81a251eaa51ac31871f8c4b91dff4");
+    let other = [3; 20];
+    let claims = vec![(signer, 500), (TYPED_SIGNER, 300), (other, 200)];
+    with_externalities(&mut new_test_ext(claims), || {
+        register_sdot();
+        System::initialize(&1, &H256::from(GENESIS_HASH), &Default::default());
+        let origin = || Origin::signed(Default::default());
+        let token = b"SDOT".to_vec();
+        let council = xaccounts::Module::<Test>::council_account();
+
+        assert_eq!(
+            XBridgeOfSDOT::claim_addresses(),
+            vec![signer, TYPED_SIGNER, other]
+        );
+        assert_ok!(XBridgeOfSDOT::set_claim_addresses(vec![other]));
+        assert_eq!(XBridgeOfSDOT::claim_addresses(), vec![other]);
+
+        assert_noop!(
+            XBridgeOfSDOT::set_claim_deadline(0),
+            "The claim deadline should be in the future"
+        );
+        assert_noop!(
+            XBridgeOfSDOT::sweep_unclaimed(vec![other]),
+            "The claim deadline has not passed"
+        );
+        assert_ok!(XBridgeOfSDOT::set_claim_deadline(20));
+        // could be changed before it passes
+        assert_ok!(XBridgeOfSDOT::set_claim_deadline(10));
+        assert_eq!(XBridgeOfSDOT::claim_deadline(), Some(10));
+        assert_noop!(
+            XBridgeOfSDOT::sweep_unclaimed(vec![other]),
+            "The claim deadline has not passed"
+        );
+
+        // the deadline block still accepts claims
+        System::set_block_number(10);
+        let raw = hex!("f8571d8501dcd6500082ae809482e2b7d189a81a251eaa51ac31871f8c4b91dff480b635536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6740446556616c");
+        let data = hex!("35536a4a6846514b5456544c62646359675635655a45687853436f347171594e4d47354c7242616e4157434731665a6740446556616c");
+        let signature = EcdsaSignature(
+            hex!("2105f2b5b8476d4b4494da61565b053b5b59d50d5b8162cd67f0617ecf30ff86"),
+            hex!("7d34deb9e0396f1addee0ac1ccde1e50c6bcacd34e667efd832f05ccdc4a92de"),
+            0,
+        );
+        assert_ok!(XBridgeOfSDOT::claim(
+            origin(),
+            signature,
+            raw.to_vec(),
+            data.to_vec()
+        ));
+        assert_eq!(XAssets::free_balance_of(&typed_account(), &token), 500);
+        assert_eq!(XBridgeOfSDOT::total(), 500);
+
+        System::set_block_number(11);
+        assert_noop!(
+            XBridgeOfSDOT::claim_typed(origin(), typed_signature(), TYPED_ACCOUNT.to_vec()),
+            "The SDOT claim deadline has passed"
+        );
+        // the claims can't be reopened after the deadline
+        assert_noop!(
+            XBridgeOfSDOT::set_claim_deadline(20),
+            "The claim deadline has passed"
+        );
+
+        // the claimed and unknown addresses are skipped
+        assert_ok!(XBridgeOfSDOT::sweep_unclaimed(vec![
+            signer,
+            TYPED_SIGNER,
+            [4; 20]
+        ]));
+        assert_eq!(XAssets::free_balance_of(&council, &token), 300);
+        assert_eq!(XBridgeOfSDOT::claims(&TYPED_SIGNER), None);
+        assert_eq!(XBridgeOfSDOT::total(), 200);
+
+        assert_ok!(XBridgeOfSDOT::sweep_unclaimed(vec![other, other]));
+        assert_eq!(XAssets::free_balance_of(&council, &token), 500);
+        assert_eq!(XBridgeOfSDOT::claims(&other), None);
+        assert_eq!(XBridgeOfSDOT::total(), 0);
+    })
+}
@@ -78,25 +78,14 @@ decl_module! {
         ) -> Result {
             let who = ensure_signed(origin)?;
 
-            ensure!(!price.is_zero(), "Price can't be zero");
-            ensure!(!amount.is_zero(), "Amount can't be zero");
-            ensure!(order_type == OrderType::Limit, "Only support Limit order for now");
-
-            let pair = Self::trading_pair(pair_index)?;
-
-            ensure!(pair.online, "The trading pair must be online");
-            ensure!(
-                (price.into() % 10_u64.pow(pair.tick_precision)).is_zero(),
-                "Price must be an integer multiple of the tick precision"
-            );
-
-            Self::is_within_quotation_range(price, &side, pair_index)?;
-            Self::has_too_many_backlog_orders(pair_index, price, side)?;
+            let (pair, reserve_amount) = xfee_manager::Module::<T>::check_call(
+                Self::check_put_order(pair_index, order_type, side, amount, price)
+            )?;
 
             // Reserve the token according to the order side.
-            let (reserve_token, reserve_amount) = match side {
-                Buy => (pair.quote_as_ref(), Self::convert_base_to_quote(amount, price, &pair)?),
-                Sell => (pair.base_as_ref(), amount),
+            let reserve_token = match side {
+                Buy => pair.quote_as_ref(),
+                Sell => pair.base_as_ref(),
             };
 
             Self::put_order_reserve(&who, reserve_token, reserve_amount)?;
@@ -416,6 +405,39 @@ impl<T: Trait> Module<T> {
     }
 
     /// In order to get trading pair easier.
+    /// Check the order and return the trading pair and the amount to reserve.
+    fn check_put_order(
+        pair_index: TradingPairIndex,
+        order_type: OrderType,
+        side: Side,
+        amount: T::Balance,
+        price: T::Price,
+    ) -> result::Result<(TradingPair, T::Balance), &'static str> {
+        ensure!(!price.is_zero(), "Price can't be zero");
+        ensure!(!amount.is_zero(), "Amount can't be zero");
+        ensure!(
+            order_type == OrderType::Limit,
+            "Only support Limit order for now"
+        );
+
+        let pair = Self::trading_pair(pair_index)?;
+
+        ensure!(pair.online, "The trading pair must be online");
+        ensure!(
+            (price.into() % 10_u64.pow(pair.tick_precision)).is_zero(),
+            "Price must be an integer multiple of the tick precision"
+        );
+
+        Self::is_within_quotation_range(price, &side, pair_index)?;
+        Self::has_too_many_backlog_orders(pair_index, price, side)?;
+
+        let reserve_amount = match side {
+            Buy => Self::convert_base_to_quote(amount, price, &pair)?,
+            Sell => amount,
+        };
+        Ok((pair, reserve_amount))
+    }
+
     fn trading_pair(pair_index: TradingPairIndex) -> result::Result<TradingPair, &'static str> {
         <TradingPairOf<T>>::get(pair_index).ok_or("The order pair doesn't exist.")
     }
@@ -3,6 +3,7 @@
 
 use super::*;
 use primitives::traits::CheckedAdd;
+use xsupport::info;
 
 impl<T: Trait> Module<T> {
     /// When the price is far from the current handicap, i.e.,
@@ -177,6 +178,12 @@ impl<T: Trait> Module<T> {
                     "Opponent side should match the side of maker order."
                 );
 
+                // The frozen maker can't deliver the reserved asset, cancel the order instead.
+                if Self::is_reserve_frozen(pair, &maker_order) {
+                    Self::cancel_frozen_order(pair, &mut maker_order);
+                    continue;
+                }
+
                 let turnover = cmp::min(
                     taker_order.remaining_in_base(),
                     maker_order.remaining_in_base(),
@@ -275,6 +282,35 @@ impl<T: Trait> Module<T> {
         Self::update_handicap(&pair, price, order_side);
     }
 
+    /// Whether the submitter of order is frozen for the asset reserved by it.
+    fn is_reserve_frozen(pair: &TradingPair, order: &OrderInfo<T>) -> bool {
+        let reserved_token = match order.side() {
+            Buy => pair.quote(),
+            Sell => pair.base(),
+        };
+        <xassets::Module<T>>::is_frozen(&order.submitter(), &reserved_token)
+    }
+
+    /// Cancel the order of a frozen maker, the reserved asset is returned to its free.
+    fn cancel_frozen_order(pair: &TradingPair, order: &mut OrderInfo<T>) {
+        let who = order.submitter();
+        info!(
+            "[cancel_frozen_order] submitter is frozen, cancel the order: {:?}",
+            order
+        );
+
+        if Self::update_order_and_unreserve_on_cancel(order, pair, &who).is_ok() {
+            Self::kill_order(
+                pair.index,
+                order.price(),
+                who,
+                order.index(),
+                pair.clone(),
+                order.side(),
+            );
+        }
+    }
+
     /// Update the status of order after the turnover is calculated.
     fn update_order_on_execute(
         order: &mut OrderInfo<T>,
@@ -65,6 +65,12 @@ impl xbitcoin::Trait for Test {
     type Event = ();
 }
 
+impl xethereum::Trait for Test {
+    type AccountExtractor = DummyExtractor;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
 pub struct DummyExtractor;
 impl xbridge_common::traits::Extractable<u64> for DummyExtractor {
     fn account_info(_data: &[u8], _: u8) -> Option<(u64, Option<Vec<u8>>)> {
@@ -200,6 +206,7 @@ impl xbridge_features::TrusteeMultiSigFor<u64> for DummyMultiSigIdFor {
 
 impl xfee_manager::Trait for Test {
     type Event = ();
+    type AssetPrice = ();
 }
 
 impl Trait for Test {
@@ -336,6 +336,57 @@ fn cancel_order_should_work() {
     })
 }
 
+#[test]
+fn frozen_maker_order_should_be_canceled_when_matching() {
+    with_externalities(&mut new_test_ext(), || {
+        let trading_pair = XSpot::trading_pair_of(0).unwrap();
+
+        assert_ok!(XSpot::set_handicap(0, 1_000_000, 1_100_000));
+
+        assert_ok!(XAssets::issue(&trading_pair.quote(), &1, 10));
+        assert_ok!(XAssets::pcx_issue(&2, 2000));
+
+        assert_ok!(XSpot::put_order(
+            Origin::signed(1),
+            0,
+            OrderType::Limit,
+            Side::Buy,
+            1000,
+            1_000_100,
+        ));
+        assert!(XAssets::free_balance_of(&1, &trading_pair.quote()) < 10);
+
+        assert_ok!(XAssets::freeze(
+            indices::address::Address::Id(1),
+            trading_pair.quote()
+        ));
+
+        assert_ok!(XSpot::put_order(
+            Origin::signed(2),
+            0,
+            OrderType::Limit,
+            Side::Sell,
+            500,
+            1_000_100
+        ));
+
+        // the order of the frozen maker is canceled and the reserved asset is returned.
+        assert_eq!(XSpot::order_info_of((1, 0)), None);
+        assert_eq!(XAssets::free_balance_of(&1, &trading_pair.quote()), 10);
+        assert_eq!(
+            XAssets::asset_balance_of(&1, &trading_pair.quote(), AssetType::ReservedDexSpot),
+            0
+        );
+
+        // the taker order is left unfilled.
+        let order_2_0 = XSpot::order_info_of((2, 0)).unwrap();
+        assert_eq!(order_2_0.status, OrderStatus::ZeroFill);
+        assert_eq!(order_2_0.already_filled, 0);
+        assert!(order_2_0.executed_indices.is_empty());
+        assert_eq!(XSpot::quotations_of((0, 1_000_100)), vec![(2, 0)]);
+    })
+}
+
 #[test]
 fn reap_orders_should_work() {
     with_externalities(&mut new_test_ext(), || {
@@ -7,44 +7,112 @@ mod tests;
 pub mod types;
 
 // Substrate
-use primitives::traits::{CheckedDiv, CheckedMul, CheckedSub};
+use primitives::traits::{CheckedDiv, CheckedMul, CheckedSub, Saturating, Zero};
+use rstd::cmp;
 use rstd::collections::btree_map::BTreeMap;
 use rstd::prelude::Vec;
 use rstd::result;
-use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageValue};
+use support::{decl_event, decl_module, decl_storage, dispatch::Result, StorageMap, StorageValue};
+use system::ensure_signed;
 
 // ChainX
 use chainx_primitives::Acceleration;
-use xr_primitives::XString;
+use xr_primitives::{Token, XString};
 
 use xaccounts::IntentionJackpotAccountIdFor;
+use xassets::ChainT;
 #[cfg(feature = "std")]
 use xsupport::u8array_to_string;
-use xsupport::{info, trace, warn};
+use xsupport::{debug, info, trace, warn};
 
-pub use self::types::CallSwitcher;
+pub use self::types::{CallSwitcher, Sponsorship};
+
+/// `FeeMultiplier` is in units of 1/10000, i.e. 10000 means the fee is not adjusted.
+pub const FEE_MULTIPLIER_BASE: u64 = 10_000;
+pub const MAX_FEE_MULTIPLIER: u64 = 100 * FEE_MULTIPLIER_BASE;
+/// `FeeTokenPremium` is in units of 1/10000.
+pub const FEE_TOKEN_PREMIUM_BASE: u64 = 10_000;
 
 /// Simple payment making trait, operating on a single generic `AccountId` type.
 pub trait MakePayment<AccountId> {
+    type Balance;
+
     /// Make some sort of payment concerning `who` for an extrinsic (transaction) of encoded length
-    /// `encoded_len` bytes. Return true iff the payment was successful.
-    fn make_payment(who: &AccountId, encoded_len: usize, pay: u64, acc: Acceleration) -> Result;
+    /// `encoded_len` bytes. Return the fee paid iff the payment was successful.
+    /// The fee is paid in `fee_token` instead of PCX if it's provided.
+    fn make_payment(
+        who: &AccountId,
+        encoded_len: usize,
+        pay: u64,
+        acc: Acceleration,
+        fee_token: Option<&Token>,
+    ) -> result::Result<Self::Balance, &'static str>;
 
-    fn check_payment(who: &AccountId, encoded_len: usize, pay: u64, acc: Acceleration) -> Result;
+    /// Refund the part of the `paid` fee for the weight `pay - actual_pay` which is not used,
+    /// return the actual fee paid.
+    fn refund_payment(
+        who: &AccountId,
+        encoded_len: usize,
+        pay: u64,
+        actual_pay: u64,
+        fee_token: Option<&Token>,
+        paid: Self::Balance,
+    ) -> Self::Balance;
+
+    fn check_payment(
+        who: &AccountId,
+        encoded_len: usize,
+        pay: u64,
+        acc: Acceleration,
+        fee_token: Option<&Token>,
+    ) -> Result;
+}
+
+/// The price of an asset in PCX, i.e. how much PCX(in the smallest unit) one whole asset is worth.
+pub trait AssetPrice<Balance> {
+    fn aver_asset_price(token: &Token) -> Option<Balance>;
+}
+
+impl<Balance> AssetPrice<Balance> for () {
+    fn aver_asset_price(_: &Token) -> Option<Balance> {
+        None
+    }
 }
 
 pub trait Trait: xassets::Trait + xaccounts::Trait + xsystem::Trait {
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
+    /// Used to convert the fee into other assets when the transactor pays in them.
+    type AssetPrice: AssetPrice<Self::Balance>;
 }
 
 decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
+        <T as system::Trait>::BlockNumber,
         <T as xassets::Trait>::Balance
     {
-        FeeForJackpot(AccountId, Balance),
-        FeeForProducer(AccountId, Balance),
-        FeeForCouncil(AccountId, Balance),
+        /// payer, jackpot, fee token, fee
+        FeeForJackpot(AccountId, AccountId, Token, Balance),
+        /// payer, block producer, fee token, fee
+        FeeForProducer(AccountId, AccountId, Token, Balance),
+        /// payer, council, fee token, fee
+        FeeForCouncil(AccountId, AccountId, Token, Balance),
+        /// transactor, fee token, amount of the fee token, the fee in PCX
+        PayFeeInToken(AccountId, Token, Balance, Balance),
+        /// payer, burned fee
+        FeeBurned(AccountId, Balance),
+        /// payer, fee token, refund, the actual fee paid
+        RefundFee(AccountId, Token, Balance, Balance),
+        /// sponsor, sponsored account, budget
+        Sponsor(AccountId, AccountId, Balance),
+        /// sponsor, sponsored account
+        AcceptSponsor(AccountId, AccountId),
+        /// sponsor, sponsored account
+        RevokeSponsor(AccountId, AccountId),
+        /// switcher, whether the calls are forbidden
+        SwitcherChanged(CallSwitcher, bool),
+        /// switcher, the block number the calls are allowed again
+        SwitcherReopenScheduled(CallSwitcher, BlockNumber),
     }
 );
 
@@ -52,6 +120,68 @@ decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         fn deposit_event<T>() = default;
 
+        fn on_finalize(now: T::BlockNumber) {
+            // a flag set by the calls dispatched out of the extrinsics must not leak to next block.
+            CallFailedEarly::<T>::kill();
+            for switch in SwitcherReopenQueue::<T>::take(&now) {
+                // the schedule is dropped if the switcher was modified again.
+                if Self::switcher_reopen_at(&switch) == Some(now) {
+                    Self::apply_switcher(switch, false);
+                }
+            }
+        }
+
+        /// Offer to pay the transaction fee for `who` up to `budget`, only for the methods in
+        /// `calls` if it's not empty. The sponsorship takes effect after `who` accepts it.
+        fn sponsor(origin, who: T::AccountId, budget: T::Balance, calls: Vec<XString>) -> Result {
+            let sponsor = ensure_signed(origin)?;
+            if sponsor == who {
+                return Err("can't sponsor yourself");
+            }
+
+            SponsorshipOf::<T>::insert(&(sponsor.clone(), who.clone()), Sponsorship { budget, calls });
+            Self::deposit_event(RawEvent::Sponsor(sponsor, who, budget));
+            Ok(())
+        }
+
+        /// Accept the sponsorship offered by `sponsor`, which replaces the current one if any.
+        fn accept_sponsor(origin, sponsor: T::AccountId) -> Result {
+            let who = ensure_signed(origin)?;
+            if Self::sponsorship_of(&(sponsor.clone(), who.clone())).is_none() {
+                return Err("no sponsorship offered by this account");
+            }
+
+            if let Some(old) = Self::sponsor_of(&who) {
+                if old != sponsor {
+                    SponsorshipOf::<T>::remove(&(old.clone(), who.clone()));
+                    Self::deposit_event(RawEvent::RevokeSponsor(old, who.clone()));
+                }
+            }
+            SponsorOf::<T>::insert(&who, sponsor.clone());
+            Self::deposit_event(RawEvent::AcceptSponsor(sponsor, who));
+            Ok(())
+        }
+
+        /// Stop the sponsorship between the transactor and `who`, either side could revoke it,
+        /// no matter it has been accepted or not.
+        fn revoke_sponsor(origin, who: T::AccountId) -> Result {
+            let transactor = ensure_signed(origin)?;
+            let (sponsor, sponsored) = if Self::sponsorship_of(&(transactor.clone(), who.clone())).is_some() {
+                (transactor, who)
+            } else if Self::sponsorship_of(&(who.clone(), transactor.clone())).is_some() {
+                (who, transactor)
+            } else {
+                return Err("no sponsorship between these accounts");
+            };
+
+            if Self::sponsor_of(&sponsored).as_ref() == Some(&sponsor) {
+                SponsorOf::<T>::remove(&sponsored);
+            }
+            SponsorshipOf::<T>::remove(&(sponsor.clone(), sponsored.clone()));
+            Self::deposit_event(RawEvent::RevokeSponsor(sponsor, sponsored));
+            Ok(())
+        }
+
         fn set_producer_producer_fee_proportion(proportion: (u32, u32)) -> Result {
             assert!(proportion.1 != 0, "the proportion denominator can't be Zero");
             assert!(proportion.0 < proportion.1, "the proportion numerator should less than denominator");
@@ -59,18 +189,71 @@ decl_module! {
             Ok(())
         }
 
+        /// Set the share of the PCX fee to be burned, the rest is split by `ProducerFeeProportion`.
+        fn set_fee_burn_proportion(proportion: (u32, u32)) -> Result {
+            if proportion.1 == 0 {
+                return Err("the proportion denominator can't be Zero");
+            }
+            if proportion.0 > proportion.1 {
+                return Err("the proportion numerator should not more than denominator");
+            }
+            FeeBurnProportion::<T>::put(proportion);
+            Ok(())
+        }
+
+        /// Set the proportion of the weight charged when the dispatch of a transaction fails.
+        fn set_failed_call_weight_proportion(proportion: (u32, u32)) -> Result {
+            if proportion.1 == 0 {
+                return Err("the proportion denominator can't be Zero");
+            }
+            if proportion.0 > proportion.1 {
+                return Err("the proportion numerator should not more than denominator");
+            }
+            FailedCallWeightProportion::<T>::put(proportion);
+            Ok(())
+        }
+
+        /// Set the premium charged when paying the fee in non-PCX assets, in units of 1/10000.
+        fn set_fee_token_premium(premium: u32) {
+            FeeTokenPremium::<T>::put(premium);
+        }
+
         /// set open/close for switcher
         pub fn modify_switcher(switch: CallSwitcher, open: bool) {
-            Switcher::<T>::mutate(|map| {
-                if open {
-                    map.insert(switch, true)
-                } else {
-                    map.remove(&switch)
+            Self::apply_switcher(switch, open);
+        }
+
+        /// open the switcher and close it automatically at block `until`
+        pub fn modify_switcher_until(switch: CallSwitcher, until: T::BlockNumber) -> Result {
+            if until <= system::Module::<T>::block_number() {
+                return Err("the block number to close the switcher should be in the future");
+            }
+
+            Self::apply_switcher(switch.clone(), true);
+            SwitcherReopenAt::<T>::insert(&switch, until);
+            SwitcherReopenQueue::<T>::mutate(&until, |queue| {
+                if !queue.contains(&switch) {
+                    queue.push(switch.clone());
                 }
             });
+            Self::deposit_event(RawEvent::SwitcherReopenScheduled(switch, until));
+            Ok(())
         }
 
         pub fn set_switcher(all_switcher: BTreeMap<CallSwitcher, bool>) {
+            let old = Self::switcher();
+            let is_on = |map: &BTreeMap<CallSwitcher, bool>, switch: &CallSwitcher| {
+                map.get(switch).map(|b| *b).unwrap_or(false)
+            };
+            let changed = old
+                .keys()
+                .chain(all_switcher.keys().filter(|s| !old.contains_key(*s)))
+                .filter(|s| is_on(&old, *s) != is_on(&all_switcher, *s));
+            for switch in changed {
+                SwitcherReopenAt::<T>::remove(switch);
+                let on = is_on(&all_switcher, switch);
+                Self::deposit_event(RawEvent::SwitcherChanged(switch.clone(), on));
+            }
             Switcher::<T>::put(all_switcher)
         }
 
@@ -127,6 +310,10 @@ decl_storage! {
         // deprecated in v1.0.3
         // pub Switch get(switch): SwitchStore;
         pub Switcher get(switcher): BTreeMap<CallSwitcher, bool>;
+        /// The block number a switcher will be closed automatically.
+        pub SwitcherReopenAt get(switcher_reopen_at): map CallSwitcher => Option<T::BlockNumber>;
+        /// block number => switchers to be closed at this block
+        pub SwitcherReopenQueue get(switcher_reopen_queue): map T::BlockNumber => Vec<CallSwitcher>;
         /// Emergency control
         /// Each callable method in runtime normally has a different weight.
         pub MethodCallWeight get(method_call_weight): BTreeMap<XString, u64>;
@@ -136,6 +323,25 @@ decl_storage! {
         pub TransactionBaseFee get(transaction_base_fee) config(): T::Balance;
         /// The fee to be paid for making a transaction; the per-byte portion.
         pub TransactionByteFee get(transaction_byte_fee) config(): T::Balance;
+        /// Congestion multiplier of the transaction fee, adjusted by the fullness of last block.
+        pub FeeMultiplier get(fee_multiplier): u32 = FEE_MULTIPLIER_BASE as u32;
+        /// Total fee weight of the transactions in current block.
+        pub BlockFeeWeight get(block_fee_weight): u64;
+        /// Whether the top-level call being dispatched failed in its checks, cleared before and
+        /// after each dispatch, including the nested dispatch of a call by another call.
+        pub CallFailedEarly get(call_failed_early): bool;
+        /// Extra premium charged when paying the fee in non-PCX assets, in units of 1/10000.
+        pub FeeTokenPremium get(fee_token_premium): u32 = 500;
+        /// How much of the PCX fee is burned, the rest is split by `ProducerFeeProportion`.
+        pub FeeBurnProportion get(fee_burn_proportion): (u32, u32) = (0, 1);
+        /// Total PCX fee burned.
+        pub BurnedFee get(burned_fee): T::Balance;
+        /// How much of the weight is charged when the dispatch of a transaction fails.
+        pub FailedCallWeightProportion get(failed_call_weight_proportion): (u32, u32) = (1, 10);
+        /// The sponsor accepted by an account to pay its transaction fee.
+        pub SponsorOf get(sponsor_of): map T::AccountId => Option<T::AccountId>;
+        /// (sponsor, sponsored account) => sponsorship, offered or accepted
+        pub SponsorshipOf get(sponsorship_of): map (T::AccountId, T::AccountId) => Option<Sponsorship<T::Balance>>;
     }
     add_extra_genesis {
         build(|_: &mut primitives::StorageOverlay, _: &mut primitives::ChildrenStorageOverlay, config: &GenesisConfig<T>| {
@@ -146,16 +352,87 @@ decl_storage! {
 }
 
 impl<T: Trait> MakePayment<T::AccountId> for Module<T> {
+    type Balance = T::Balance;
+
     fn make_payment(
         transactor: &T::AccountId,
         encoded_len: usize,
         power: u64,
         acc: Acceleration,
-    ) -> Result {
-        let b = Self::calc_fee_and_check(transactor, encoded_len, power, acc)?;
+        fee_token: Option<&Token>,
+    ) -> result::Result<T::Balance, &'static str> {
+        let fee = match Self::non_pcx_fee_token(fee_token) {
+            Some(token) => {
+                let (fee, pcx_fee) =
+                    Self::calc_token_fee_and_check(transactor, encoded_len, power, acc, token)?;
+
+                Self::calc_fee(transactor, token, fee)?;
+                Self::deposit_event(RawEvent::PayFeeInToken(
+                    transactor.clone(),
+                    token.clone(),
+                    fee,
+                    pcx_fee,
+                ));
+                fee
+            }
+            None => {
+                let b = Self::calc_fee_and_check(transactor, encoded_len, power, acc)?;
+
+                Self::calc_fee(
+                    transactor,
+                    &<xassets::Module<T> as ChainT>::TOKEN.to_vec(),
+                    b,
+                )?;
+                b
+            }
+        };
+        Ok(fee)
+    }
 
-        Self::calc_fee(transactor, b)?;
-        Ok(())
+    fn refund_payment(
+        transactor: &T::AccountId,
+        encoded_len: usize,
+        power: u64,
+        actual_power: u64,
+        fee_token: Option<&Token>,
+        paid: T::Balance,
+    ) -> T::Balance {
+        if actual_power >= power {
+            return paid;
+        }
+
+        // only the weight part of the fee is refunded, the bytes part is always charged.
+        // refund = paid * base_fee * (power - actual_power) / (base_fee * power + byte_fee * len)
+        let base_fee: u64 = Self::transaction_base_fee().into();
+        let byte_fee: u64 = Self::transaction_byte_fee().into();
+        let paid_u64: u64 = paid.into();
+        let numerator = u128::from(paid_u64)
+            .checked_mul(u128::from(base_fee))
+            .and_then(|x| x.checked_mul(u128::from(power - actual_power)));
+        let denominator = (u128::from(base_fee) * u128::from(power))
+            .saturating_add(u128::from(byte_fee) * encoded_len as u128);
+        let numerator = match numerator {
+            Some(n) if denominator != 0 => n,
+            _ => return paid,
+        };
+        let refund: T::Balance = ((numerator / denominator) as u64).into();
+        if refund.is_zero() {
+            return paid;
+        }
+
+        let token = Self::non_pcx_fee_token(fee_token)
+            .cloned()
+            .unwrap_or_else(|| <xassets::Module<T> as ChainT>::TOKEN.to_vec());
+        let refunded = Self::refund_fee(transactor, &token, refund);
+        let actual_fee = paid - refunded;
+
+        Self::deposit_event(RawEvent::RefundFee(
+            transactor.clone(),
+            token,
+            refunded,
+            actual_fee,
+        ));
+        actual_fee
     }
 
     fn check_payment(
@@ -163,8 +440,15 @@ impl<T: Trait> MakePayment<T::AccountId> for Module<T> {
         encoded_len: usize,
         power: u64,
         acc: Acceleration,
+        fee_token: Option<&Token>,
     ) -> Result {
-        Self::calc_fee_and_check(transactor, encoded_len, power, acc).map(|_| ())
+        match Self::non_pcx_fee_token(fee_token) {
+            Some(token) => {
+                Self::calc_token_fee_and_check(transactor, encoded_len, power, acc, token)
+                    .map(|_| ())
+            }
+            None => Self::calc_fee_and_check(transactor, encoded_len, power, acc).map(|_| ()),
+        }
     }
 }
 
@@ -173,9 +457,153 @@ impl<T: Trait> Module<T> {
         Self::switcher().get(&switcher).map(|b| *b).unwrap_or(false)
     }
 
+    /// Open or close a switcher, any schedule to close it is dropped.
+    fn apply_switcher(switch: CallSwitcher, open: bool) {
+        SwitcherReopenAt::<T>::remove(&switch);
+        let changed = Switcher::<T>::mutate(|map| {
+            if open {
+                map.insert(switch.clone(), true) != Some(true)
+            } else {
+                map.remove(&switch) == Some(true)
+            }
+        });
+        if changed {
+            Self::deposit_event(RawEvent::SwitcherChanged(switch, open));
+        }
+    }
+
     pub fn transaction_fee(power: u64, encoded_len: u64) -> T::Balance {
-        Self::transaction_base_fee() * power.into()
-            + Self::transaction_byte_fee() * encoded_len.into()
+        let fee = Self::transaction_base_fee() * power.into()
+            + Self::transaction_byte_fee() * encoded_len.into();
+        fee * u64::from(Self::fee_multiplier()).into() / FEE_MULTIPLIER_BASE.into()
+    }
+
+    /// The weight charged for a transaction whose dispatch failed, at least 1.
+    pub fn failed_call_weight(power: u64) -> u64 {
+        let proportion = Self::failed_call_weight_proportion();
+        let weight =
+            u128::from(power) * u128::from(proportion.0) / cmp::max(u128::from(proportion.1), 1);
+        cmp::min(cmp::max(weight as u64, 1), power)
+    }
+
+    /// Note the result of the checks of a call, which must be done before any storage change.
+    /// A call failed in these checks is only charged a part of the weight.
+    pub fn check_call<R>(r: result::Result<R, &'static str>) -> result::Result<R, &'static str> {
+        if r.is_err() {
+            CallFailedEarly::<T>::put(true);
+        }
+        r
+    }
+
+    /// Return whether the dispatched call failed in its checks and clear the flag.
+    pub fn take_call_failed_early() -> bool {
+        CallFailedEarly::<T>::take()
+    }
+
+    /// Clear the flag before dispatching a call, and after dispatching a nested call, since only
+    /// the checks of the top-level call decide what the extrinsic is charged.
+    pub fn clear_call_failed_early() {
+        CallFailedEarly::<T>::kill();
+    }
+
+    /// Return the sponsor and the fee in PCX if the transaction of `who` calling `method` would
+    /// be paid by the sponsor, i.e. the call is allowed and both the budget and the sponsor's
+    /// balance are enough.
+    pub fn sponsor_for(
+        who: &T::AccountId,
+        method: Option<&XString>,
+        encoded_len: usize,
+        power: u64,
+        acc: Acceleration,
+    ) -> Option<(T::AccountId, T::Balance)> {
+        let sponsor = Self::sponsor_of(who)?;
+        let sponsorship = Self::sponsorship_of(&(sponsor.clone(), who.clone()))?;
+
+        let allowed =
+            sponsorship.calls.is_empty() || method.map_or(false, |m| sponsorship.calls.contains(m));
+        if !allowed {
+            return None;
+        }
+
+        let fee = Self::calc_fee_and_check(&sponsor, encoded_len, power, acc).ok()?;
+        if sponsorship.budget < fee {
+            debug!(
+                "[sponsor_for]|sponsorship budget is not enough|sponsor:{:}|who:{:}|budget:{:}|fee:{:}",
+                sponsor, who, sponsorship.budget, fee
+            );
+            return None;
+        }
+        Some((sponsor, fee))
+    }
+
+    /// Estimate the fee of a transaction, return the payer, the fee in PCX and the fee in the
+    /// fee token if it's paid in other tokens.
+    pub fn estimate_fee(
+        who: &T::AccountId,
+        method: Option<&XString>,
+        encoded_len: usize,
+        power: u64,
+        acc: Acceleration,
+        fee_token: Option<&Token>,
+    ) -> result::Result<(T::AccountId, T::Balance, Option<(Token, T::Balance)>), &'static str> {
+        if let Some((sponsor, fee)) = Self::sponsor_for(who, method, encoded_len, power, acc) {
+            return Ok((sponsor, fee, None));
+        }
+
+        let fee = Self::transaction_fee(power, encoded_len as u64) * acc.into();
+        let token_fee = match Self::non_pcx_fee_token(fee_token) {
+            Some(token) => Some((token.clone(), Self::fee_in_token(token, fee)?)),
+            None => None,
+        };
+        Ok((who.clone(), fee, token_fee))
+    }
+
+    /// Deduct the fee paid by `sponsor` for `who` from the budget.
+    pub fn consume_sponsorship(sponsor: &T::AccountId, who: &T::AccountId, fee: T::Balance) {
+        SponsorshipOf::<T>::mutate(&(sponsor.clone(), who.clone()), |sponsorship| {
+            if let Some(s) = sponsorship {
+                s.budget = s.budget.checked_sub(&fee).unwrap_or_default();
+            }
+        });
+    }
+
+    /// Note the fee weight of a transaction applied in current block.
+    pub fn note_block_fee_weight(power: u64) {
+        BlockFeeWeight::<T>::mutate(|w| *w = w.saturating_add(power));
+    }
+
+    /// Adjust the fee multiplier by the fullness of current block, the multiplier goes up if
+    /// the block exceeds the targets, otherwise goes down, by 1/8 at most each block.
+    pub fn update_fee_multiplier(target_weight: u64, target_len: u64, block_len: u64) {
+        let block_weight = BlockFeeWeight::<T>::take();
+
+        let fullness =
+            |used: u64, target: u64| used.saturating_mul(FEE_MULTIPLIER_BASE) / cmp::max(target, 1);
+        let fullness = cmp::max(
+            fullness(block_weight, target_weight),
+            fullness(block_len, target_len),
+        );
+
+        let multiplier = u64::from(Self::fee_multiplier());
+        let new_multiplier = if fullness > FEE_MULTIPLIER_BASE {
+            let diff = cmp::min(fullness - FEE_MULTIPLIER_BASE, FEE_MULTIPLIER_BASE);
+            multiplier + multiplier * diff / FEE_MULTIPLIER_BASE / 8
+        } else {
+            let diff = FEE_MULTIPLIER_BASE - fullness;
+            multiplier - multiplier * diff / FEE_MULTIPLIER_BASE / 8
+        };
+        let new_multiplier = cmp::min(
+            cmp::max(new_multiplier, FEE_MULTIPLIER_BASE),
+            MAX_FEE_MULTIPLIER,
+        );
+
+        if new_multiplier != multiplier {
+            debug!(
+                "[update_fee_multiplier]|block weight:{:}|block len:{:}|old:{:}|new:{:}",
+                block_weight, block_len, multiplier, new_multiplier
+            );
+            FeeMultiplier::<T>::put(new_multiplier as u32);
+        }
     }
 
     fn calc_fee_and_check(
@@ -194,24 +622,114 @@ impl<T: Trait> Module<T> {
         Ok(transaction_fee)
     }
 
-    fn calc_fee(from: &T::AccountId, fee: T::Balance) -> Result {
-        let proportion = Self::producer_fee_proportion();
+    /// Paying the fee in PCX explicitly is the same as the normal case.
+    fn non_pcx_fee_token(fee_token: Option<&Token>) -> Option<&Token> {
+        fee_token.filter(|token| token.as_slice() != <xassets::Module<T> as ChainT>::TOKEN)
+    }
+
+    /// Convert the fee in PCX into `token` by the average price of the spot DEX plus the
+    /// premium, rounded up.
+    pub fn fee_in_token(
+        token: &Token,
+        fee: T::Balance,
+    ) -> result::Result<T::Balance, &'static str> {
+        if Self::get_switcher(CallSwitcher::Token(token.clone())) {
+            return Err("this token is forbidden to pay the transaction fee");
+        }
+        let price: u64 = T::AssetPrice::aver_asset_price(token)
+            .ok_or("this token has no price to pay the transaction fee")?
+            .into();
+        if price == 0 {
+            return Err("this token has no price to pay the transaction fee");
+        }
+        let asset = xassets::Module::<T>::get_asset(token)?;
+        let precision = 10_u128.pow(u32::from(asset.precision()));
+        let premium = FEE_TOKEN_PREMIUM_BASE + u64::from(Self::fee_token_premium());
+
+        // fee * 10^precision * (BASE + premium) / BASE / price
+        let fee: u64 = fee.into();
+        let numerator = u128::from(fee)
+            .checked_mul(precision)
+            .and_then(|x| x.checked_mul(u128::from(premium)))
+            .ok_or("[fee]calc fee in token mul overflow!")?;
+        let denominator = u128::from(price) * u128::from(FEE_TOKEN_PREMIUM_BASE);
+        let token_fee = (numerator + denominator - 1) / denominator;
+        if token_fee > u128::from(u64::max_value()) {
+            return Err("[fee]calc fee in token overflow!");
+        }
+        Ok((token_fee as u64).into())
+    }
+
+    /// Return the fee in `token` and the original fee in PCX.
+    fn calc_token_fee_and_check(
+        transactor: &T::AccountId,
+        encoded_len: usize,
+        power: u64,
+        acc: Acceleration,
+        token: &Token,
+    ) -> result::Result<(T::Balance, T::Balance), &'static str> {
+        let pcx_fee = Self::transaction_fee(power, encoded_len as u64) * acc.into();
+        let fee = Self::fee_in_token(token, pcx_fee)?;
+
+        if xassets::Module::<T>::free_balance_of(transactor, token) < fee {
+            return Err("not enough funds for transaction fee");
+        }
+        Ok((fee, pcx_fee))
+    }
 
-        // for_producer = fee * rate.0 / rate.1
-        let for_producer = match fee.checked_mul(&(proportion.0).into()) {
+    /// value * proportion.0 / proportion.1
+    fn proportion_of(
+        value: T::Balance,
+        proportion: (u32, u32),
+    ) -> result::Result<T::Balance, &'static str> {
+        match value.checked_mul(&(proportion.0).into()) {
             Some(r) => match r.checked_div(&(proportion.1).into()) {
-                Some(r) => r,
-                None => return Err("[fee]calc fee proportion dev overflow!"),
+                Some(r) => Ok(r),
+                None => Err("[fee]calc fee proportion dev overflow!"),
             },
-            None => return Err("[fee]calc fee proportion mul overflow!"),
+            None => Err("[fee]calc fee proportion mul overflow!"),
+        }
+    }
+
+    /// Split the fee into the parts to be burned, for the block producer and for the jackpot,
+    /// only the fee in PCX would be burned or go to the jackpot, the jackpot only distributes PCX.
+    fn split_fee(
+        token: &Token,
+        fee: T::Balance,
+    ) -> result::Result<(T::Balance, T::Balance, T::Balance), &'static str> {
+        if token.as_slice() != <xassets::Module<T> as ChainT>::TOKEN {
+            return Ok((Zero::zero(), fee, Zero::zero()));
+        }
+
+        let for_burn = Self::proportion_of(fee, Self::fee_burn_proportion())?;
+
+        // rest = fee - for_burn;
+        let rest = match fee.checked_sub(&for_burn) {
+            Some(r) => r,
+            None => return Err("[fee]sub overflow!"),
         };
 
-        // for_jackpot = fee - for_producer;
-        let for_jackpot = match fee.checked_sub(&for_producer) {
+        // for_producer = rest * rate.0 / rate.1
+        let for_producer = Self::proportion_of(rest, Self::producer_fee_proportion())?;
+
+        // for_jackpot = rest - for_producer;
+        let for_jackpot = match rest.checked_sub(&for_producer) {
             Some(r) => r,
             None => return Err("[fee]sub overflow!"),
         };
 
+        Ok((for_burn, for_producer, for_jackpot))
+    }
+
+    fn calc_fee(from: &T::AccountId, token: &Token, fee: T::Balance) -> Result {
+        let (for_burn, for_producer, for_jackpot) = Self::split_fee(token, fee)?;
+
+        if !for_burn.is_zero() {
+            xassets::Module::<T>::pcx_destroy_free(from, for_burn)?;
+            BurnedFee::<T>::mutate(|burned| *burned = burned.saturating_add(for_burn));
+            Self::deposit_event(RawEvent::FeeBurned(from.clone(), for_burn));
+        }
+
         if let Some(p) = xsystem::Module::<T>::block_producer() {
             let jackpot_addr = T::DetermineIntentionJackpotAccountId::accountid_for_unsafe(&p);
 
@@ -225,29 +743,107 @@ impl<T: Trait> Module<T> {
                 p
             );
 
-            let _ = xassets::Module::<T>::pcx_move_free_balance(from, &p, for_producer)
+            let _ = xassets::Module::<T>::move_free_balance(token, from, &p, for_producer)
                 .map_err(|e| e.info())?;
 
-            Self::deposit_event(RawEvent::FeeForProducer(p, for_producer));
-
-            let _ = xassets::Module::<T>::pcx_move_free_balance(from, &jackpot_addr, for_jackpot)
+            Self::deposit_event(RawEvent::FeeForProducer(
+                from.clone(),
+                p,
+                token.clone(),
+                for_producer,
+            ));
+
+            if !for_jackpot.is_zero() {
+                let _ = xassets::Module::<T>::move_free_balance(
+                    token,
+                    from,
+                    &jackpot_addr,
+                    for_jackpot,
+                )
                 .map_err(|e| e.info())?;
 
-            Self::deposit_event(RawEvent::FeeForJackpot(jackpot_addr, for_jackpot));
+                Self::deposit_event(RawEvent::FeeForJackpot(
+                    from.clone(),
+                    jackpot_addr,
+                    token.clone(),
+                    for_jackpot,
+                ));
+            }
         } else {
             let council = xaccounts::Module::<T>::council_account();
 
+            let fee = for_producer + for_jackpot;
             warn!(
                 "[calc_fee]|current block not set producer!|council:{:},{:?}",
                 council, fee
             );
 
-            xassets::Module::<T>::pcx_move_free_balance(from, &council, fee)
+            let _ = xassets::Module::<T>::move_free_balance(token, from, &council, fee)
                 .map_err(|e| e.info())?;
 
-            Self::deposit_event(RawEvent::FeeForJackpot(council, fee));
+            Self::deposit_event(RawEvent::FeeForCouncil(
+                from.clone(),
+                council,
+                token.clone(),
+                fee,
+            ));
         }
 
         Ok(())
     }
+
+    /// Move the refund back to `to` from the block producer and the jackpot in the same
+    /// proportion as the fee was split, and reissue the burned part, return the amount
+    /// refunded actually.
+    fn refund_fee(to: &T::AccountId, token: &Token, refund: T::Balance) -> T::Balance {
+        let (for_burn, for_producer, for_jackpot) = match Self::split_fee(token, refund) {
+            Ok(r) => r,
+            Err(_e) => {
+                warn!("[refund_fee]|split refund failed|err:{:}", _e);
+                return Zero::zero();
+            }
+        };
+
+        let refund_from = |from: &T::AccountId, value: T::Balance| -> T::Balance {
+            match xassets::Module::<T>::move_free_balance(token, from, to, value) {
+                Ok(_) => value,
+                Err(_e) => {
+                    warn!(
+                        "[refund_fee]|move refund failed|from:{:}|to:{:}|value:{:}|err:{:}",
+                        from,
+                        to,
+                        value,
+                        _e.info()
+                    );
+                    Zero::zero()
+                }
+            }
+        };
+
+        let reissued = if for_burn.is_zero() {
+            Zero::zero()
+        } else {
+            match xassets::Module::<T>::pcx_issue(to, for_burn) {
+                Ok(_) => {
+                    BurnedFee::<T>::mutate(|burned| *burned = burned.saturating_sub(for_burn));
+                    for_burn
+                }
+                Err(_e) => {
+                    warn!(
+                        "[refund_fee]|reissue burned fee failed|to:{:}|value:{:}|err:{:}",
+                        to, for_burn, _e
+                    );
+                    Zero::zero()
+                }
+            }
+        };
+
+        if let Some(p) = xsystem::Module::<T>::block_producer() {
+            let jackpot_addr = T::DetermineIntentionJackpotAccountId::accountid_for_unsafe(&p);
+            reissued + refund_from(&p, for_producer) + refund_from(&jackpot_addr, for_jackpot)
+        } else {
+            let council = xaccounts::Module::<T>::council_account();
+            reissued + refund_from(&council, for_producer + for_jackpot)
+        }
+    }
 }
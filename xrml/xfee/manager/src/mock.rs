@@ -87,6 +87,20 @@ impl xassets::Trait for Test {
 }
 impl Trait for Test {
     type Event = ();
+    type AssetPrice = MockAssetPrice;
+}
+
+pub struct MockAssetPrice;
+
+impl AssetPrice<u64> for MockAssetPrice {
+    /// 1 BTC = 2 PCX
+    fn aver_asset_price(token: &xassets::Token) -> Option<u64> {
+        if token.as_slice() == b"BTC" {
+            Some(2 * 100_000_000)
+        } else {
+            None
+        }
+    }
 }
 
 pub type XAssets = xassets::Module<Test>;
@@ -134,6 +148,17 @@ pub fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
         XAssets::pcx_issue(&1, 1000).unwrap();
         XAssets::pcx_issue(&2, 510).unwrap();
         XAssets::pcx_issue(&3, 1000).unwrap();
+
+        let btc = xassets::Asset::new(
+            b"BTC".to_vec(),
+            b"X-BTC".to_vec(),
+            xassets::Chain::Bitcoin,
+            8,
+            b"ChainX's cross-chain Bitcoin".to_vec(),
+        )
+        .unwrap();
+        XAssets::bootstrap_register_asset(btc, true, true).unwrap();
+        XAssets::issue(&b"BTC".to_vec(), &4, 100).unwrap();
     });
     let init: StorageOverlay = init.into();
     runtime_io::TestExternalities::new(init)
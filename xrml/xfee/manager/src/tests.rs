@@ -13,7 +13,7 @@ fn test_fee() {
     with_externalities(&mut new_test_ext(), || {
         xsystem::BlockProducer::<Test>::put(99);
 
-        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1));
+        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1, None), 110);
         // base fee = 10, bytes fee = 1
         let fee = 10 * 10 + 1 * 10;
         assert_eq!(XAssets::pcx_free_balance(&1), 1000 - fee);
@@ -29,7 +29,7 @@ fn test_fee() {
 #[test]
 fn test_fee_no_blockproducer() {
     with_externalities(&mut new_test_ext(), || {
-        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1));
+        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1, None), 110);
         // base fee = 10, bytes fee = 1
         let fee = 10 * 10 + 1 * 10;
         assert_eq!(XAssets::pcx_free_balance(&1), 1000 - fee);
@@ -43,7 +43,7 @@ fn test_fee_no_blockproducer() {
 fn test_fee_not_divisible() {
     with_externalities(&mut new_test_ext(), || {
         xsystem::BlockProducer::<Test>::put(99);
-        assert_ok!(XFeeManager::make_payment(&1, 11, 10, 1));
+        assert_ok!(XFeeManager::make_payment(&1, 11, 10, 1, None), 111);
         // base fee = 10, bytes fee = 1
         let fee = 10 * 10 + 1 * 11; // 111
         assert_eq!(XAssets::pcx_free_balance(&1), 1000 - fee);
@@ -53,3 +53,286 @@ fn test_fee_not_divisible() {
         assert_eq!(XAssets::pcx_free_balance(&1000), fee * 9 / 10 + 1); // 111 * 9 / 10 = 99 + 1 = 100
     });
 }
+
+#[test]
+fn test_fee_multiplier() {
+    with_externalities(&mut new_test_ext(), || {
+        xsystem::BlockProducer::<Test>::put(99);
+        assert_eq!(XFeeManager::fee_multiplier(), 10_000);
+
+        // empty block, can't be lower than base
+        XFeeManager::update_fee_multiplier(100, 1000, 0);
+        assert_eq!(XFeeManager::fee_multiplier(), 10_000);
+
+        // weight exceeds the target
+        XFeeManager::note_block_fee_weight(150);
+        XFeeManager::update_fee_multiplier(100, 1000, 0);
+        // 10000 + 10000 * 5000 / 10000 / 8
+        assert_eq!(XFeeManager::fee_multiplier(), 10_625);
+        assert_eq!(XFeeManager::block_fee_weight(), 0);
+
+        // length exceeds the target far away, increase 1/8 at most
+        XFeeManager::update_fee_multiplier(100, 1000, 5000);
+        // 10625 + 10625 / 8
+        assert_eq!(XFeeManager::fee_multiplier(), 11_953);
+
+        // fee goes up
+        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1, None), 131);
+        let fee = (10 * 10 + 1 * 10) * 11_953 / 10_000;
+        assert_eq!(XAssets::pcx_free_balance(&1), 1000 - fee);
+
+        // half of the target, goes down
+        XFeeManager::note_block_fee_weight(50);
+        XFeeManager::update_fee_multiplier(100, 1000, 0);
+        // 11953 - 11953 * 5000 / 10000 / 8
+        assert_eq!(XFeeManager::fee_multiplier(), 11_206);
+    });
+}
+
+#[test]
+fn test_fee_in_token() {
+    with_externalities(&mut new_test_ext(), || {
+        xsystem::BlockProducer::<Test>::put(99);
+        let btc = b"BTC".to_vec();
+        assert_eq!(XAssets::pcx_free_balance(&4), 0);
+
+        assert_ok!(XFeeManager::make_payment(&4, 10, 10, 1, Some(&btc)), 58);
+        // 110 PCX => 55 BTC, with 5% premium, rounded up
+        let fee = 58;
+        assert_eq!(XAssets::free_balance_of(&4, &btc), 100 - fee);
+        // block producer takes the whole fee in token, the jackpot only accepts PCX
+        assert_eq!(XAssets::free_balance_of(&99, &btc), fee);
+        assert_eq!(XAssets::free_balance_of(&1000, &btc), 0);
+
+        // without the block producer, the fee in token goes to the council
+        xsystem::BlockProducer::<Test>::kill();
+        let balance = XAssets::free_balance_of(&4, &btc);
+        let fee = XFeeManager::make_payment(&4, 1, 1, 1, Some(&btc)).unwrap();
+        assert_eq!(XAssets::free_balance_of(&4, &btc), balance - fee);
+        assert_eq!(XAssets::free_balance_of(&0, &btc), fee);
+        xsystem::BlockProducer::<Test>::put(99);
+
+        assert_eq!(
+            XFeeManager::check_payment(&4, 10, 100, 1, Some(&btc)),
+            Err("not enough funds for transaction fee")
+        );
+        // no price for this token
+        assert_eq!(
+            XFeeManager::check_payment(&4, 10, 10, 1, Some(&b"SDOT".to_vec())),
+            Err("this token has no price to pay the transaction fee")
+        );
+        // paying in PCX explicitly
+        assert_ok!(
+            XFeeManager::make_payment(
+                &1,
+                10,
+                10,
+                1,
+                Some(&<XAssets as xassets::ChainT>::TOKEN.to_vec())
+            ),
+            110
+        );
+        assert_eq!(XAssets::pcx_free_balance(&1), 1000 - 110);
+    });
+}
+
+#[test]
+fn test_sponsor() {
+    with_externalities(&mut new_test_ext(), || {
+        let transfer = b"XAssets transfer".to_vec();
+        let withdraw = b"XAssetsProcess withdraw".to_vec();
+        assert_ok!(XFeeManager::sponsor(
+            Origin::signed(1),
+            5,
+            200,
+            vec![transfer.clone()]
+        ));
+        // not accepted yet
+        assert_eq!(XFeeManager::sponsor_of(&5), None);
+        assert_eq!(
+            XFeeManager::sponsor_for(&5, Some(&transfer), 10, 10, 1),
+            None
+        );
+        assert_eq!(
+            XFeeManager::accept_sponsor(Origin::signed(5), 2),
+            Err("no sponsorship offered by this account")
+        );
+        // others could offer too, the sponsored account chooses which one to accept
+        assert_ok!(XFeeManager::sponsor(Origin::signed(2), 5, 200, vec![]));
+        assert_ok!(XFeeManager::accept_sponsor(Origin::signed(5), 2));
+        assert_eq!(XFeeManager::sponsor_of(&5), Some(2));
+        assert_ok!(XFeeManager::accept_sponsor(Origin::signed(5), 1));
+        assert_eq!(XFeeManager::sponsor_of(&5), Some(1));
+        // the replaced sponsorship is removed
+        assert_eq!(XFeeManager::sponsorship_of(&(2, 5)), None);
+
+        // the call is not sponsored
+        assert_eq!(
+            XFeeManager::sponsor_for(&5, Some(&withdraw), 10, 10, 1),
+            None
+        );
+        assert_eq!(XFeeManager::sponsor_for(&5, None, 10, 10, 1), None);
+
+        // base fee = 10, bytes fee = 1
+        let fee = 10 * 10 + 1 * 10;
+        assert_eq!(
+            XFeeManager::sponsor_for(&5, Some(&transfer), 10, 10, 1),
+            Some((1, fee))
+        );
+        XFeeManager::consume_sponsorship(&1, &5, fee);
+        assert_eq!(
+            XFeeManager::sponsorship_of(&(1, 5)).unwrap().budget,
+            200 - fee
+        );
+        // budget is not enough
+        assert_eq!(
+            XFeeManager::sponsor_for(&5, Some(&transfer), 10, 10, 1),
+            None
+        );
+
+        // the sponsored account could revoke it too
+        assert_ok!(XFeeManager::revoke_sponsor(Origin::signed(5), 1));
+        assert_eq!(XFeeManager::sponsor_of(&5), None);
+        assert_eq!(XFeeManager::sponsorship_of(&(1, 5)), None);
+        assert_eq!(
+            XFeeManager::revoke_sponsor(Origin::signed(1), 5),
+            Err("no sponsorship between these accounts")
+        );
+    });
+}
+
+#[test]
+fn test_refund_fee() {
+    with_externalities(&mut new_test_ext(), || {
+        xsystem::BlockProducer::<Test>::put(99);
+        assert_eq!(XFeeManager::failed_call_weight(100), 10);
+        assert_eq!(XFeeManager::failed_call_weight(5), 1);
+
+        // base fee = 10, bytes fee = 1
+        let fee = 10 * 100 + 1 * 10;
+        assert_ok!(XFeeManager::make_payment(&1, 10, 100, 1, None), fee);
+        // only 10 of the weight is charged, refund 1000 * 90 / 100
+        let actual_fee = XFeeManager::refund_payment(&1, 10, 100, 10, None, fee);
+        assert_eq!(actual_fee, 10 * 10 + 1 * 10);
+        assert_eq!(XAssets::pcx_free_balance(&1), 1000 - actual_fee);
+        // block producer
+        assert_eq!(XAssets::pcx_free_balance(&99), fee / 10 - 90);
+        // jackpot account
+        assert_eq!(XAssets::pcx_free_balance(&1000), fee - fee / 10 - 810);
+
+        // nothing to refund
+        assert_eq!(XFeeManager::refund_payment(&1, 10, 10, 10, None, 110), 110);
+    });
+}
+
+#[test]
+fn test_check_call() {
+    with_externalities(&mut new_test_ext(), || {
+        use primitives::traits::OnFinalize;
+
+        assert_eq!(XFeeManager::check_call(Ok(1)), Ok(1));
+        assert!(!XFeeManager::take_call_failed_early());
+
+        assert_eq!(
+            XFeeManager::check_call::<()>(Err("bad call")),
+            Err("bad call")
+        );
+        assert!(XFeeManager::take_call_failed_early());
+        // the flag is cleared
+        assert!(!XFeeManager::take_call_failed_early());
+
+        // a flag set by a nested call is cleared
+        assert!(XFeeManager::check_call::<()>(Err("bad call")).is_err());
+        XFeeManager::clear_call_failed_early();
+        assert!(!XFeeManager::take_call_failed_early());
+
+        // a flag set out of the extrinsics is cleared at the end of the block
+        assert!(XFeeManager::check_call::<()>(Err("bad call")).is_err());
+        <XFeeManager as OnFinalize<u64>>::on_finalize(1);
+        assert!(!XFeeManager::take_call_failed_early());
+    });
+}
+
+#[test]
+fn test_fee_burn() {
+    with_externalities(&mut new_test_ext(), || {
+        xsystem::BlockProducer::<Test>::put(99);
+        assert_ok!(XFeeManager::set_fee_burn_proportion((3, 10)));
+        let total = XAssets::pcx_total_balance();
+
+        // base fee = 10, bytes fee = 1
+        let fee = 10 * 10 + 1 * 10;
+        assert_ok!(XFeeManager::make_payment(&1, 10, 10, 1, None), fee);
+        assert_eq!(XAssets::pcx_free_balance(&1), 1000 - fee);
+        // burn 110 * 3 / 10 = 33, the rest 77 is split
+        assert_eq!(XFeeManager::burned_fee(), 33);
+        assert_eq!(XAssets::pcx_total_balance(), total - 33);
+        // block producer
+        assert_eq!(XAssets::pcx_free_balance(&99), 7);
+        // jackpot account
+        assert_eq!(XAssets::pcx_free_balance(&1000), 70);
+
+        // the fee in other tokens is not burned
+        let btc = b"BTC".to_vec();
+        assert_ok!(XFeeManager::make_payment(&4, 10, 10, 1, Some(&btc)), 58);
+        assert_eq!(XAssets::free_balance_of(&99, &btc), 5);
+        assert_eq!(XFeeManager::burned_fee(), 33);
+
+        // the burned part is reissued when refunding
+        let fee = 10 * 90 + 1 * 10;
+        assert_ok!(XFeeManager::make_payment(&3, 10, 90, 1, None), fee);
+        // burn 910 * 3 / 10 = 273
+        assert_eq!(XFeeManager::burned_fee(), 33 + 273);
+        // refund 800, 240 of which was burned
+        assert_eq!(
+            XFeeManager::refund_payment(&3, 10, 90, 10, None, fee),
+            fee - 800
+        );
+        assert_eq!(XFeeManager::burned_fee(), 33 + 273 - 240);
+        assert_eq!(XAssets::pcx_free_balance(&3), 1000 - fee + 800);
+        assert_eq!(XAssets::pcx_total_balance(), total - 33 - 273 + 240);
+
+        assert_eq!(
+            XFeeManager::set_fee_burn_proportion((11, 10)),
+            Err("the proportion numerator should not more than denominator")
+        );
+    });
+}
+
+#[test]
+fn test_switcher_reopen() {
+    with_externalities(&mut new_test_ext(), || {
+        use primitives::traits::OnFinalize;
+
+        let withdraw = CallSwitcher::Method(b"XAssetsProcess withdraw".to_vec());
+        let pair = CallSwitcher::SpotPair(1);
+        system::Module::<Test>::set_block_number(1);
+        assert_eq!(
+            XFeeManager::modify_switcher_until(withdraw.clone(), 1),
+            Err("the block number to close the switcher should be in the future")
+        );
+        assert_ok!(XFeeManager::modify_switcher_until(withdraw.clone(), 5));
+        assert_ok!(XFeeManager::modify_switcher_until(pair.clone(), 5));
+        assert!(XFeeManager::get_switcher(withdraw.clone()));
+        assert!(XFeeManager::get_switcher(pair.clone()));
+        assert_eq!(XFeeManager::switcher_reopen_at(&withdraw), Some(5));
+
+        // modifying the switcher again drops the schedule
+        XFeeManager::modify_switcher(pair.clone(), true);
+        assert_eq!(XFeeManager::switcher_reopen_at(&pair), None);
+
+        <XFeeManager as OnFinalize<u64>>::on_finalize(4);
+        assert!(XFeeManager::get_switcher(withdraw.clone()));
+        <XFeeManager as OnFinalize<u64>>::on_finalize(5);
+        assert!(!XFeeManager::get_switcher(withdraw.clone()));
+        assert_eq!(XFeeManager::switcher_reopen_at(&withdraw), None);
+        assert!(XFeeManager::get_switcher(pair.clone()));
+
+        let btc = CallSwitcher::Token(b"BTC".to_vec());
+        let mut all = BTreeMap::new();
+        all.insert(btc.clone(), true);
+        XFeeManager::set_switcher(all);
+        assert!(!XFeeManager::get_switcher(pair));
+        assert!(XFeeManager::get_switcher(btc));
+    });
+}
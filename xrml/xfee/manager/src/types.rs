@@ -1,6 +1,9 @@
 // Copyright 2018-2019 Chainpool.
 
 use parity_codec::{Decode, Encode};
+use rstd::prelude::Vec;
+
+use xr_primitives::{Token, XString};
 
 #[derive(PartialEq, PartialOrd, Ord, Eq, Clone, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(Debug))]
@@ -11,4 +14,21 @@ pub enum CallSwitcher {
     XBTCLockup,
     SDOT,
     XContracts,
+    XETH,
+    /// A single method, in the form of `MethodCallWeight` keys, e.g. `XAssetsProcess withdraw`.
+    Method(XString),
+    /// `put_order` of a single trading pair.
+    SpotPair(u32),
+    /// The calls operating on a certain asset.
+    Token(Token),
+}
+
+/// Pre-authorization of a sponsor to pay the transaction fee for an account.
+#[derive(PartialEq, Eq, Clone, Default, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(Debug))]
+pub struct Sponsorship<Balance> {
+    /// The fee in PCX the sponsor is still willing to pay.
+    pub budget: Balance,
+    /// Methods could be sponsored, in the form of `MethodCallWeight` keys, empty means all.
+    pub calls: Vec<XString>,
 }
@@ -44,7 +44,9 @@ pub use self::vote_weight::*;
 const DEFAULT_MINIMUM_VALIDATOR_COUNT: u32 = 4;
 const SESSIONS_PER_ROUND: u64 = 210_000;
 
-pub trait Trait: xsystem::Trait + xsession::Trait + xassets::Trait {
+pub trait Trait:
+    xsystem::Trait + xsession::Trait + xassets::Trait + xbridge_features::Trait
+{
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 
@@ -338,6 +340,11 @@ decl_module! {
             <UpperBoundFactor<T>>::put(new);
         }
 
+        /// Set the eras between automatic trustee elections, zero disables the election.
+        fn set_trustee_election_eras(new: T::BlockNumber) {
+            <TrusteeElectionEras<T>>::put(new);
+        }
+
         fn set_nomination_record(
             nominator: T::AccountId,
             nominee: T::AccountId,
@@ -519,6 +526,9 @@ decl_storage! {
         /// We are forcing a new era.
         pub ForcingNewEra get(forcing_new_era): Option<()>;
 
+        /// Elect the bitcoin trustees from intentions every these eras, zero means no automatic election.
+        pub TrusteeElectionEras get(trustee_election_eras): T::BlockNumber;
+
         pub StakeWeight get(stake_weight): map T::AccountId => T::Balance;
 
         /// All the accounts with a desire to be a validator.
@@ -4,6 +4,7 @@
 use super::*;
 
 use primitives::traits::{One, Zero};
+use xassets::Chain;
 use xsession::OnSessionChange;
 use xsupport::{debug, info, warn};
 #[cfg(feature = "std")]
@@ -21,6 +22,38 @@ impl<T: Trait> Module<T> {
         intentions
     }
 
+    /// Gather all the active intentions sorted by total nomination for trustee election.
+    fn gather_trustee_candidates() -> Vec<T::AccountId> {
+        let mut intentions = Self::intention_set()
+            .into_iter()
+            .filter(|v| Self::is_active(v))
+            .map(|v| (Self::total_nomination_of(&v), v))
+            .collect::<Vec<_>>();
+        intentions.sort_by(|&(ref b1, _), &(ref b2, _)| b2.cmp(&b1));
+        intentions.into_iter().map(|(_, v)| v).collect()
+    }
+
+    /// Elect the bitcoin trustees by stake if the current era is an election era.
+    fn try_elect_trustees() {
+        let election_eras = Self::trustee_election_eras();
+        if election_eras.is_zero() || !(Self::current_era() % election_eras).is_zero() {
+            return;
+        }
+        // the current trustees should not be retired before the funds are moved to them
+        if let Some(session_number) = <xbitcoin::Module<T>>::migrating_session() {
+            info!(
+                "[try_elect_trustees] skip the election, the fund migration into trustee session {:} is pending",
+                session_number
+            );
+            return;
+        }
+
+        let candidates = Self::gather_trustee_candidates();
+        if let Err(e) = <xbridge_features::Module<T>>::elect_trustees(Chain::Bitcoin, candidates) {
+            warn!("[try_elect_trustees] fail to elect new trustees: {:?}", e);
+        }
+    }
+
     /// A qualified candidate for validator election should be active and reach the minimum candidate threshold.
     fn is_qualified_candidate(who: &T::AccountId) -> bool {
         Self::is_active(who) && Self::meet_candidate_threshold(who)
@@ -114,6 +147,8 @@ impl<T: Trait> Module<T> {
         // Increment current era.
         <CurrentEra<T>>::put(&(<CurrentEra<T>>::get() + One::one()));
 
+        Self::try_elect_trustees();
+
         // Enact era length change.
         if let Some(next_spe) = Self::next_sessions_per_era() {
             if next_spe != Self::sessions_per_era() {
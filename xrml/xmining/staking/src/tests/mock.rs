@@ -76,6 +76,7 @@ impl xmultisig::Trait for Test {
     type GenesisMultiSig = DummyGenesisMultiSig;
     type Proposal = DummyCall;
     type TrusteeCall = TrusteeCall;
+    type ProposalHook = ();
     type Event = ();
 }
 
@@ -160,6 +161,7 @@ impl xassets::Trait for Test {
 
 impl xfee_manager::Trait for Test {
     type Event = ();
+    type AssetPrice = ();
 }
 
 impl xsystem::Trait for Test {
@@ -198,6 +200,12 @@ impl xbitcoin::Trait for Test {
     type Event = ();
 }
 
+impl xethereum::Trait for Test {
+    type AccountExtractor = DummyExtractor;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
 impl xsdot::Trait for Test {
     type AccountExtractor = DummyExtractor;
     type CrossChainProvider = XBridgeFeatures;
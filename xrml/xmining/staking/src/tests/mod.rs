@@ -707,3 +707,103 @@ fn switch_to_u128_when_overflow() {
         );
     });
 }
+
+#[test]
+fn trustee_election_should_work() {
+    use std::str::FromStr;
+    use xassets::Chain;
+    use xbridge_common::types::TrusteeInfoConfig;
+
+    with_externalities(&mut new_test_ext(), || {
+        System::set_block_number(1);
+        XSession::check_rotate_session(System::block_number());
+
+        let trustee_intentions = vec![
+            (
+                10,
+                "0384106cbc714c3a7f9a1a6cc763525d1f65e4993721f5023fc0954a185aa2fd1d",
+                "022f0fe2f0801f5dc95d93254e5e2226e919c4759f24f38e3998f695e35c967984",
+            ),
+            (
+                20,
+                "02161422b1b2da8d3f9986b0df694a9008d535fb364755858b14cb94ea41a339e7",
+                "02183ffa596f67f0445ab945933c2229c3f4f5dcb346cc8d5dfaae30d5d64e8ac4",
+            ),
+            (
+                30,
+                "026210f0c305bc8131ba929a55b3a4de504afed8cbf985c05d487e40675ba40383",
+                "02b9127e1c4f25e6b15e488006c1222dc55327b38100de7b890cd50b5e2cdb9804",
+            ),
+            (
+                40,
+                "026210f0c305bc5551ba929a55b3a4de504afed8cbf985c05d487e40675ba40383",
+                "02b9127e1c4f25e6b15e666006c1222dc55327b38100de7b890cd50b5e2cdb9804",
+            ),
+        ];
+        for (who, hot_entity, cold_entity) in trustee_intentions.into_iter() {
+            assert_ok!(XBridgeFeatures::setup_bitcoin_trustee_impl(
+                who,
+                b"trustee".to_vec(),
+                xbridge_features::H264::from_str(hot_entity).unwrap(),
+                xbridge_features::H264::from_str(cold_entity).unwrap(),
+            ));
+        }
+        assert_ok!(XBridgeFeatures::set_trustee_info_config(
+            Chain::Bitcoin,
+            TrusteeInfoConfig {
+                min_trustee_count: 3,
+                max_trustee_count: 3,
+            }
+        ));
+
+        // No automatic election by default.
+        System::set_block_number(2);
+        XSession::check_rotate_session(System::block_number());
+        assert_eq!(XBridgeFeatures::trustee_session_info_len(Chain::Bitcoin), 0);
+
+        assert_ok!(XStaking::set_trustee_election_eras(1));
+
+        // The top 3 staked intentions are elected.
+        System::set_block_number(3);
+        XSession::check_rotate_session(System::block_number());
+        assert_eq!(XBridgeFeatures::trustee_session_info_len(Chain::Bitcoin), 1);
+        let trustees = XBridgeFeatures::trustee_session_info_for(Chain::Bitcoin, None)
+            .unwrap()
+            .trustees_info
+            .into_iter()
+            .map(|(who, _)| who)
+            .collect::<Vec<_>>();
+        assert_eq!(trustees, vec![40, 30, 20]);
+
+        // Same trustees elected, no new trustee session.
+        System::set_block_number(4);
+        XSession::check_rotate_session(System::block_number());
+        assert_eq!(XBridgeFeatures::trustee_session_info_len(Chain::Bitcoin), 1);
+
+        // No election while the fund migration is pending.
+        xbitcoin::Module::<Test>::start_trustee_migration(1, &[]);
+        assert_ok!(XBridgeFeatures::set_trustee_info_config(
+            Chain::Bitcoin,
+            TrusteeInfoConfig {
+                min_trustee_count: 3,
+                max_trustee_count: 4,
+            }
+        ));
+        System::set_block_number(5);
+        XSession::check_rotate_session(System::block_number());
+        assert_eq!(XBridgeFeatures::trustee_session_info_len(Chain::Bitcoin), 1);
+
+        // The election goes on once the migration is confirmed.
+        assert_ok!(xbitcoin::Module::<Test>::confirm_trustee_migration());
+        System::set_block_number(6);
+        XSession::check_rotate_session(System::block_number());
+        assert_eq!(XBridgeFeatures::trustee_session_info_len(Chain::Bitcoin), 2);
+        assert_eq!(
+            XBridgeFeatures::trustee_session_info_for(Chain::Bitcoin, None)
+                .unwrap()
+                .trustees_info
+                .len(),
+            4
+        );
+    });
+}
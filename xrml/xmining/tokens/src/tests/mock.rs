@@ -82,6 +82,7 @@ impl xassets::Trait for Test {
 
 impl xfee_manager::Trait for Test {
     type Event = ();
+    type AssetPrice = ();
 }
 
 impl xsystem::Trait for Test {
@@ -117,6 +118,7 @@ impl xmultisig::Trait for Test {
     type GenesisMultiSig = DummyGenesisMultiSig;
     type Proposal = DummyCall;
     type TrusteeCall = TrusteeCall;
+    type ProposalHook = ();
     type Event = ();
 }
 
@@ -186,6 +188,12 @@ impl xbitcoin::Trait for Test {
     type Event = ();
 }
 
+impl xethereum::Trait for Test {
+    type AccountExtractor = DummyExtractor;
+    type CrossChainProvider = XBridgeFeatures;
+    type Event = ();
+}
+
 impl xbitcoin::lockup::Trait for Test {
     type Event = ();
 }
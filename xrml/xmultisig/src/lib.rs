@@ -1,6 +1,7 @@
 // Copyright 2018-2019 Chainpool.
 
-//! this module is for multisig, but now this is just for genesis multisig addr, not open for public.
+//! this module is for multisig, genesis and trustee multisig addrs are deployed by the chain,
+//! and any account could deploy a normal multisig addr by reserving a deposit.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 
@@ -11,20 +12,24 @@ pub mod types;
 use parity_codec::Encode;
 
 // Substrate
-use primitives::traits::Hash;
+use primitives::traits::{Hash, Zero};
 use rstd::{marker::PhantomData, prelude::*, result};
 use substrate_primitives::crypto::UncheckedFrom;
 use support::{
     decl_event, decl_module, decl_storage, dispatch::Result, Dispatchable, Parameter, StorageMap,
+    StorageValue,
 };
 use system::ensure_signed;
 
+use xassets::AssetType;
 use xsupport::{debug, error, info};
 
-pub use self::types::{AddrInfo, AddrType, MultiSigPermission, PendingState};
+pub use self::types::{
+    AddrInfo, AddrType, MultiSigPermission, PendingState, PendingStateV1, ProposalPolicy,
+    QueuedProposal,
+};
 
-// MAX_OWNERS equal PendingState.owners_done bits length
-const MAX_OWNERS: u32 = 64;
+const MAX_OWNERS: u32 = 256;
 const MAX_PENDING: u32 = 5;
 
 pub trait MultiSigFor<AccountId: Sized, Hash: Sized> {
@@ -43,11 +48,28 @@ pub trait LimitedCall<AccountId> {
     fn exec(&self, exerciser: &AccountId) -> Result;
 }
 
-pub trait Trait: xaccounts::Trait {
+/// Hooks of the runtime around the dispatch of a proposal by a multisig addr.
+pub trait ProposalHook<Proposal> {
+    /// Called before the proposal is dispatched, the proposal is rejected on error, e.g. when
+    /// it's forbidden by the call switchers.
+    fn before_dispatch(proposal: &Proposal) -> Result;
+    /// Called after the proposal is dispatched, whatever the result.
+    fn after_dispatch();
+}
+
+impl<Proposal> ProposalHook<Proposal> for () {
+    fn before_dispatch(_proposal: &Proposal) -> Result {
+        Ok(())
+    }
+    fn after_dispatch() {}
+}
+
+pub trait Trait: xaccounts::Trait + xassets::Trait {
     type MultiSig: MultiSigFor<Self::AccountId, Self::Hash>;
     type GenesisMultiSig: GenesisMultiSig<Self::AccountId>;
     type Proposal: Parameter + Dispatchable<Origin = Self::Origin>;
     type TrusteeCall: LimitedCall<Self::AccountId> + From<Self::Proposal>;
+    type ProposalHook: ProposalHook<Self::Proposal>;
     /// The overarching event type.
     type Event: From<Event<Self>> + Into<<Self as system::Trait>::Event>;
 }
@@ -95,6 +117,8 @@ decl_event!(
     pub enum Event<T> where
         <T as system::Trait>::AccountId,
         <T as system::Trait>::Hash,
+        <T as system::Trait>::BlockNumber,
+        <T as xassets::Trait>::Balance,
         <T as Trait>::Proposal
     {
         /// deploy a multisig and get address, who deploy, deploy addr, owners num, required num
@@ -102,10 +126,28 @@ decl_event!(
         /// exec. who, addr, multisigid, type
         ExecMultiSig(AccountId, AccountId, Hash, Box<Proposal>),
         /// confirm. addr, multisigid, yet_needed, owners_done
-        Confirm(AccountId, Hash, u32, u64),
+        Confirm(AccountId, Hash, u32, Vec<u32>),
+        /// set owner weights. addr, threshold weight
+        SetOwnerWeights(AccountId, u32),
 
         /// remove multisig id for a multisig addr
         RemoveMultiSigIdFor(AccountId, Hash),
+
+        /// reserve deposit for deploying a normal multisig. who, multisig addr, deposit
+        ReserveMultiSigDeposit(AccountId, AccountId, Balance),
+        /// unreserve deposit for removing a normal multisig. who, multisig addr, deposit
+        UnreserveMultiSigDeposit(AccountId, AccountId, Balance),
+        /// remove a multisig addr
+        RemoveMultiSigAddr(AccountId),
+
+        /// pending proposal expired. addr, multisigid
+        ExpireMultiSig(AccountId, Hash),
+        /// confirmed proposal queued for execution. addr, multisigid, execute at
+        QueueMultiSig(AccountId, Hash, BlockNumber),
+        /// queued proposal vetoed. who, addr, multisigid
+        VetoMultiSig(AccountId, AccountId, Hash),
+        /// queued proposal executed. addr, multisigid, success
+        ExecQueuedMultiSig(AccountId, Hash, bool),
     }
 );
 
@@ -113,13 +155,68 @@ decl_module! {
     pub struct Module<T: Trait> for enum Call where origin: T::Origin {
         fn deposit_event<T>() = default;
 
-        // not open for public
-        // fn deploy(origin, owners: Vec<(T::AccountId, bool)>, required_num: u32) -> Result {
-        //     let from = ensure_signed(origin)?;
-        //     let multisig_addr: T::AccountId = T::MultiSig::multi_sig_addr_for(&from);
-        //     debug!("[deploy]|deploy for new mutisig addr|who:{:?}|new multisig addr:{:?}|required:{:}|owners:{:?}", from, multisig_addr, required_num, owners);
-        //     Self::deploy_impl(AddrType::Normal, &multisig_addr, &from, owners, required_num)
-        // }
+        fn on_finalize(now: T::BlockNumber) {
+            Self::remove_expired(now);
+            Self::exec_queued(now);
+        }
+
+        /// deploy a normal multisig addr, the deployer must reserve `DeployDeposit` for it.
+        fn deploy(origin, owners: Vec<(T::AccountId, bool)>, required_num: u32) -> Result {
+            let from = ensure_signed(origin)?;
+            let multisig_addr: T::AccountId = T::MultiSig::multi_sig_addr_for(&from);
+            debug!("[deploy]|deploy for new mutisig addr|who:{:?}|new multisig addr:{:?}|required:{:}|owners:{:?}", from, multisig_addr, required_num, owners);
+
+            if required_num == 0 {
+                return Err("required num can't be zero");
+            }
+            if Self::multisig_addr_info(&multisig_addr).is_some() {
+                return Err("multisig addr already exists");
+            }
+
+            // the deployer is always an owner
+            let owners_len = 1 + owners.iter().filter(|(a, _)| *a != from).count() as u32;
+            Self::check_owners_len(owners_len, required_num)?;
+
+            let deposit = Self::deploy_deposit();
+            if <xassets::Module<T>>::pcx_free_balance(&from) < deposit {
+                error!("[deploy]|free balance not enough for deploy deposit|who:{:?}|deposit:{:}", from, deposit);
+                return Err("free balance not enough for deploy deposit");
+            }
+
+            let owners = owners.into_iter().map(|(a, permission)| {
+                let p = if permission {
+                    MultiSigPermission::ConfirmAndPropose
+                } else {
+                    MultiSigPermission::ConfirmOnly
+                };
+                (a, p)
+            }).collect::<Vec<_>>();
+
+            Self::reserve_deposit(&from, &multisig_addr, deposit)?;
+            Self::deploy_impl(AddrType::Normal, &multisig_addr, &from, owners, required_num)
+        }
+
+        /// remove a normal multisig addr and unreserve the deposit of the deployer.
+        /// this call can't be called from user directly, only allow call from `execute` proposal.
+        fn remove_multi_sig_addr(origin) -> Result {
+            let multi_sig_addr = ensure_signed(origin)?;
+            let (deployer, deposit) = Self::multisig_deposit_of(&multi_sig_addr)
+                .ok_or("only the multisig addr deployed with a deposit could be removed")?;
+
+            <xassets::Module<T>>::pcx_move_balance(
+                &deployer,
+                AssetType::ReservedMultiSig,
+                &deployer,
+                AssetType::Free,
+                deposit,
+            )
+            .map_err(|e| e.info())?;
+            MultiSigDepositOf::<T>::remove(&multi_sig_addr);
+            Self::deposit_event(RawEvent::UnreserveMultiSigDeposit(deployer, multi_sig_addr.clone(), deposit));
+
+            Self::remove_multi_sig_addr_impl(&multi_sig_addr);
+            Ok(())
+        }
 
         fn execute(origin, multi_sig_addr: T::AccountId, proposal: Box<T::Proposal>) -> Result {
             let from: T::AccountId = ensure_signed(origin)?;
@@ -164,6 +261,85 @@ decl_module! {
             let deploy = owners[0].0.clone();
             Self::deploy_impl(addr_info.addr_type, &multi_sig_addr, &deploy, owners, required_num)
         }
+
+        /// set the weight of owners and the threshold weight for the multisig addr, owners not in
+        /// `weights` weight 1. this call can't be called from user directly, only allow call from `execute` proposal.
+        fn set_owner_weights(origin, weights: Vec<(T::AccountId, u32)>, threshold: u32) -> Result {
+            let multi_sig_addr = ensure_signed(origin)?;
+            let mut addr_info = Self::multisig_addr_info(&multi_sig_addr).ok_or("multisig address not exist.")?;
+
+            if threshold == 0 {
+                return Err("threshold weight can't be zero");
+            }
+            for (who, weight) in weights.iter() {
+                if !addr_info.owner_list.iter().any(|(owner, _)| owner == who) {
+                    error!("[set_owner_weights]|not an owner of the multisig addr|who:{:?}|multi_sig_addr:{:?}", who, multi_sig_addr);
+                    return Err("not an owner of the multisig addr");
+                }
+                if *weight == 0 {
+                    return Err("owner weight can't be zero");
+                }
+            }
+
+            let owner_weights = addr_info.owner_list.iter().map(|(owner, _)| {
+                weights.iter().find(|(who, _)| who == owner).map(|(_, w)| *w).unwrap_or(1)
+            }).collect::<Vec<_>>();
+            let total = owner_weights.iter().fold(0_u64, |acc, w| acc + u64::from(*w));
+            if total < u64::from(threshold) {
+                error!("[set_owner_weights]|total weight can't less than threshold|total:{:}|threshold:{:}", total, threshold);
+                return Err("total weight can't less than threshold");
+            }
+
+            debug!("[set_owner_weights]|multi_sig_addr:{:?}|weights:{:?}|threshold:{:}", multi_sig_addr, owner_weights, threshold);
+            // the confirmations of pending proposals are counted by the old weights, drop them
+            for multi_sig_id in Self::pending_list_for(&multi_sig_addr) {
+                Self::remove_multi_sig_id(&multi_sig_addr, multi_sig_id);
+            }
+            addr_info.required_num = threshold;
+            MultiSigAddrInfo::<T>::insert(&multi_sig_addr, addr_info);
+            OwnerWeightsFor::<T>::insert(&multi_sig_addr, owner_weights);
+            Self::deposit_event(RawEvent::SetOwnerWeights(multi_sig_addr, threshold));
+            Ok(())
+        }
+
+        /// set the expiry and execution delay for the proposals of the multisig addr.
+        /// this call can't be called from user directly, only allow call from `execute` proposal.
+        fn set_proposal_policy(origin, expiry: Option<T::BlockNumber>, delay: Option<T::BlockNumber>) -> Result {
+            let multi_sig_addr = ensure_signed(origin)?;
+            if Self::multisig_addr_info(&multi_sig_addr).is_none() {
+                return Err("multisig address not exist.");
+            }
+            debug!("[set_proposal_policy]|multi_sig_addr:{:?}|expiry:{:?}|delay:{:?}", multi_sig_addr, expiry, delay);
+            ProposalPolicyOf::<T>::insert(&multi_sig_addr, ProposalPolicy { expiry, delay });
+            Ok(())
+        }
+
+        /// veto a queued proposal before it's executed, any owner could veto it.
+        fn veto(origin, multi_sig_addr: T::AccountId, multi_sig_id: T::Hash) -> Result {
+            let from = ensure_signed(origin)?;
+            Self::only_owner(&from, &multi_sig_addr, false)?;
+            if Self::queued_proposal_for(&(multi_sig_addr.clone(), multi_sig_id)).is_none() {
+                return Err("queued proposal not exist");
+            }
+            debug!("[veto]|veto a queued proposal|who:{:?}|for addr:{:?}|multi_sig_id:{:?}", from, multi_sig_addr, multi_sig_id);
+            Self::remove_queued(&multi_sig_addr, multi_sig_id);
+            Self::deposit_event(RawEvent::VetoMultiSig(from, multi_sig_addr, multi_sig_id));
+            Ok(())
+        }
+
+        /// set the deposit for deploying a normal multisig addr
+        fn set_deploy_deposit(value: T::Balance) {
+            <DeployDeposit<T>>::put(value);
+        }
+
+        /// index the owners of the multisig addrs deployed before the owner index exists
+        fn index_multisig_owners(multi_sig_addrs: Vec<T::AccountId>) {
+            for multi_sig_addr in multi_sig_addrs.iter() {
+                if let Some(addr_info) = Self::multisig_addr_info(multi_sig_addr) {
+                    Self::add_owner_index(multi_sig_addr, &addr_info.owner_list);
+                }
+            }
+        }
     }
 }
 
@@ -172,19 +348,51 @@ decl_storage! {
         pub RootAddrList get(root_addr_list): Vec<T::AccountId>;
 
         pub MultiSigAddrInfo get(multisig_addr_info): map T::AccountId => Option<AddrInfo<T::AccountId>>;
+        /// weights in the order of `owner_list`, empty means every owner weights 1
+        pub OwnerWeightsFor get(owner_weights_for): map T::AccountId => Vec<u32>;
 
         pub PendingListFor get(pending_list_for): map T::AccountId => Vec<T::Hash>;
         pub PendingStateFor get(pending_state_for): map (T::AccountId, T::Hash) => Option<PendingState<T::Proposal>>;
+        /// This is same with PendingStateFor with the owners_done extended from u64 bitmap to owner indexes.
+        pub PendingStateV1For get(pending_state_v1_for): map (T::AccountId, T::Hash) => Option<PendingStateV1<T::Proposal>>;
 
         // for deployer
         pub MultiSigListItemFor get(multi_sig_list_item_for): map (T::AccountId, u32) => T::AccountId;
         pub MultiSigListLenFor get(multi_sig_list_len_for): map T::AccountId => u32;
+        /// owner => multisig addrs it currently owns
+        pub MultiSigAddrsOf get(multisig_addrs_of): map T::AccountId => Vec<T::AccountId>;
+
+        /// deposit for deploying a normal multisig addr
+        pub DeployDeposit get(deploy_deposit): T::Balance = 1_000_000_000.into();
+        /// multisig addr => (deployer, reserved deposit)
+        pub MultiSigDepositOf get(multisig_deposit_of): map T::AccountId => Option<(T::AccountId, T::Balance)>;
+
+        /// multisig addr => expiry and execution delay for its proposals
+        pub ProposalPolicyOf get(proposal_policy_of): map T::AccountId => ProposalPolicy<T::BlockNumber>;
+        pub PendingExpiryOf get(pending_expiry_of): map (T::AccountId, T::Hash) => Option<T::BlockNumber>;
+        pub PendingExpireAt get(pending_expire_at): map T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
+
+        pub QueuedListFor get(queued_list_for): map T::AccountId => Vec<T::Hash>;
+        pub QueuedProposalFor get(queued_proposal_for): map (T::AccountId, T::Hash) => Option<QueuedProposal<T::Proposal, T::BlockNumber>>;
+        pub QueuedExecuteAt get(queued_execute_at): map T::BlockNumber => Vec<(T::AccountId, T::Hash)>;
     }
 }
 
 impl<T: Trait> Module<T> {
-    //    fn remove_multi_sig_addr(multi_sig_addr: &T::AccountId) {
-    //    }
+    fn remove_multi_sig_addr_impl(multi_sig_addr: &T::AccountId) {
+        for multi_sig_id in PendingListFor::<T>::take(multi_sig_addr) {
+            Self::remove_pending_for(multi_sig_addr, multi_sig_id);
+        }
+        for multi_sig_id in QueuedListFor::<T>::take(multi_sig_addr) {
+            QueuedProposalFor::<T>::remove((multi_sig_addr.clone(), multi_sig_id));
+        }
+        if let Some(addr_info) = MultiSigAddrInfo::<T>::take(multi_sig_addr) {
+            Self::remove_owner_index(multi_sig_addr, &addr_info.owner_list);
+        }
+        OwnerWeightsFor::<T>::remove(multi_sig_addr);
+        ProposalPolicyOf::<T>::remove(multi_sig_addr);
+        Self::deposit_event(RawEvent::RemoveMultiSigAddr(multi_sig_addr.clone()));
+    }
 
     fn remove_multi_sig_id(multi_sig_addr: &T::AccountId, multi_sig_id: T::Hash) {
         Self::remove_pending_for(multi_sig_addr, multi_sig_id);
@@ -199,7 +407,16 @@ impl<T: Trait> Module<T> {
     }
 
     fn remove_pending_for(multi_sig_addr: &T::AccountId, multi_sig_id: T::Hash) {
-        PendingStateFor::<T>::remove((multi_sig_addr.clone(), multi_sig_id))
+        PendingStateFor::<T>::remove((multi_sig_addr.clone(), multi_sig_id));
+        PendingStateV1For::<T>::remove((multi_sig_addr.clone(), multi_sig_id));
+        PendingExpiryOf::<T>::remove((multi_sig_addr.clone(), multi_sig_id));
+    }
+
+    fn remove_queued(multi_sig_addr: &T::AccountId, multi_sig_id: T::Hash) {
+        QueuedProposalFor::<T>::remove((multi_sig_addr.clone(), multi_sig_id));
+        QueuedListFor::<T>::mutate(multi_sig_addr, |v| {
+            v.retain(|x| x != &multi_sig_id);
+        });
     }
 
     fn is_owner(
@@ -230,8 +447,14 @@ impl<T: Trait> Module<T> {
     ) -> result::Result<bool, &'static str> {
         let index = Self::is_owner(who, multi_sig_addr, false)?;
 
+        if let Some(expiry) = Self::pending_expiry_of(&(multi_sig_addr.clone(), multi_sig_id)) {
+            if <system::Module<T>>::block_number() >= expiry {
+                return Err("this proposal has expired");
+            }
+        }
+
         let mut pending = if let Some(pending) =
-            Self::pending_state_for(&(multi_sig_addr.clone(), multi_sig_id))
+            Self::try_get_pending_state(&(multi_sig_addr.clone(), multi_sig_id))
         {
             pending
         } else {
@@ -240,16 +463,17 @@ impl<T: Trait> Module<T> {
 
         let ret: bool;
 
-        let index_bit = 1 << index; // not longer then index_bit's type
-        if pending.owners_done & index_bit == 0 {
-            if pending.yet_needed <= 1 {
+        if !pending.owners_done.contains(&index) {
+            let weight = Self::owner_weight(multi_sig_addr, index);
+            if pending.yet_needed <= weight {
                 // enough confirmations
                 ret = true;
             } else {
-                pending.yet_needed -= 1;
-                pending.owners_done |= index_bit;
-                // update pending state
-                PendingStateFor::<T>::insert(&(multi_sig_addr.clone(), multi_sig_id), pending);
+                pending.yet_needed -= weight;
+                pending.owners_done.push(index);
+                // update pending state, the old encoding is replaced by V1
+                PendingStateFor::<T>::remove(&(multi_sig_addr.clone(), multi_sig_id));
+                PendingStateV1For::<T>::insert(&(multi_sig_addr.clone(), multi_sig_id), pending);
                 ret = false;
             }
         } else {
@@ -258,6 +482,20 @@ impl<T: Trait> Module<T> {
         Ok(ret)
     }
 
+    /// Try get PendingStateV1, otherwise convert the PendingState in old encoding.
+    pub fn try_get_pending_state(
+        key: &(T::AccountId, T::Hash),
+    ) -> Option<PendingStateV1<T::Proposal>> {
+        Self::pending_state_v1_for(key).or_else(|| Self::pending_state_for(key).map(Into::into))
+    }
+
+    fn owner_weight(addr: &T::AccountId, index: u32) -> u32 {
+        Self::owner_weights_for(addr)
+            .get(index as usize)
+            .cloned()
+            .unwrap_or(1)
+    }
+
     // func alias
     fn only_owner(
         who: &T::AccountId,
@@ -296,18 +534,7 @@ impl<T: Trait> Module<T> {
         }));
 
         let owners_len = owner_list.len() as u32;
-        if owners_len > MAX_OWNERS {
-            error!("[deploy_impl]|total owners count can't more than `MAX_OWNERS`|owners_len:{:}|MAX_OWNERS:{:?}", owners_len, MAX_OWNERS);
-            return Err("total owners can't more than `MAX_OWNERS`");
-        }
-
-        if owners_len < required_num {
-            error!(
-                "[deploy_impl]|owners count can't less than required num|owners_len:{:}|required_num:{:?}",
-                owners_len, required_num
-            );
-            return Err("owners count can't less than required num");
-        }
+        Self::check_owners_len(owners_len, required_num)?;
 
         // 1, set multi_addr for current deployer. notice when transition for same deployer, the
         // multisiglist would log duplicate multisig addr. removing duplicate is meaningless
@@ -316,6 +543,12 @@ impl<T: Trait> Module<T> {
         <MultiSigListItemFor<T>>::insert(&(deployer.clone(), len), multi_addr.clone());
         <MultiSigListLenFor<T>>::insert(deployer.clone(), len + 1); // length inc
 
+        // the old owners lose the addr when transition
+        if let Some(old_info) = Self::multisig_addr_info(multi_addr) {
+            Self::remove_owner_index(multi_addr, &old_info.owner_list);
+        }
+        Self::add_owner_index(multi_addr, &owner_list);
+
         let addr_info = AddrInfo::<T::AccountId> {
             addr_type,
             required_num,
@@ -323,6 +556,8 @@ impl<T: Trait> Module<T> {
         };
         // 2
         MultiSigAddrInfo::<T>::insert(multi_addr, addr_info);
+        // owners changed, every owner weights 1 again
+        OwnerWeightsFor::<T>::remove(multi_addr);
         // event
         Self::deposit_event(RawEvent::DeployMultiSig(
             deployer.clone(),
@@ -333,6 +568,63 @@ impl<T: Trait> Module<T> {
         Ok(())
     }
 
+    fn add_owner_index(multi_addr: &T::AccountId, owners: &[(T::AccountId, MultiSigPermission)]) {
+        for (owner, _) in owners.iter() {
+            MultiSigAddrsOf::<T>::mutate(owner, |addrs| {
+                if !addrs.contains(multi_addr) {
+                    addrs.push(multi_addr.clone());
+                }
+            });
+        }
+    }
+
+    fn remove_owner_index(
+        multi_addr: &T::AccountId,
+        owners: &[(T::AccountId, MultiSigPermission)],
+    ) {
+        for (owner, _) in owners.iter() {
+            MultiSigAddrsOf::<T>::mutate(owner, |addrs| addrs.retain(|addr| addr != multi_addr));
+        }
+    }
+
+    fn check_owners_len(owners_len: u32, required_num: u32) -> Result {
+        if owners_len > MAX_OWNERS {
+            error!("[check_owners_len]|total owners count can't more than `MAX_OWNERS`|owners_len:{:}|MAX_OWNERS:{:?}", owners_len, MAX_OWNERS);
+            return Err("total owners can't more than `MAX_OWNERS`");
+        }
+        if owners_len < required_num {
+            error!(
+                "[check_owners_len]|owners count can't less than required num|owners_len:{:}|required_num:{:?}",
+                owners_len, required_num
+            );
+            return Err("owners count can't less than required num");
+        }
+        Ok(())
+    }
+
+    fn reserve_deposit(
+        who: &T::AccountId,
+        multi_addr: &T::AccountId,
+        deposit: T::Balance,
+    ) -> Result {
+        <xassets::Module<T>>::pcx_move_balance(
+            who,
+            AssetType::Free,
+            who,
+            AssetType::ReservedMultiSig,
+            deposit,
+        )
+        .map_err(|e| e.info())?;
+
+        MultiSigDepositOf::<T>::insert(multi_addr, (who.clone(), deposit));
+        Self::deposit_event(RawEvent::ReserveMultiSigDeposit(
+            who.clone(),
+            multi_addr.clone(),
+            deposit,
+        ));
+        Ok(())
+    }
+
     pub fn deploy_impl_unsafe(
         addr_type: AddrType,
         multi_addr: &T::AccountId,
@@ -351,8 +643,6 @@ impl<T: Trait> Module<T> {
         council: Vec<(T::AccountId, MultiSigPermission)>,
         council_required_num: u32,
     ) -> result::Result<T::AccountId, &'static str> {
-        use support::StorageValue;
-
         if team.len() < 1 || council.len() < 1 {
             error!(
                 "[deploy_in_genesis]|the team:{:?} and council:{:?} count can't be zero",
@@ -412,7 +702,7 @@ impl<T: Trait> Module<T> {
         multi_sig_addr: &T::AccountId,
         proposal: Box<T::Proposal>,
     ) -> Result {
-        Self::only_owner(&from, &multi_sig_addr, true)?;
+        let index = Self::only_owner(&from, &multi_sig_addr, true)?;
 
         let mut pending_list = Self::pending_list_for(multi_sig_addr);
         if pending_list.len() as u32 >= MAX_PENDING {
@@ -424,26 +714,43 @@ impl<T: Trait> Module<T> {
 
             let proposal_event = proposal.clone();
             let multi_sig_id: T::Hash;
-            if info.required_num <= 1 {
-                // real exec
-                Self::exec(&multi_sig_addr, proposal)?;
-                multi_sig_id = Default::default();
+            if info.required_num <= Self::owner_weight(multi_sig_addr, index) {
+                // real exec, or queue it if the addr has an execution delay
+                multi_sig_id = if Self::execution_delay(multi_sig_addr).is_zero() {
+                    Default::default()
+                } else {
+                    T::MultiSig::multi_sig_id_for(&from, &multi_sig_addr, &proposal.encode())
+                };
+                Self::check_queue(multi_sig_addr, multi_sig_id)?;
+                Self::exec_or_queue(multi_sig_addr, multi_sig_id, proposal)?;
             } else {
                 // determine multi sig id
                 multi_sig_id =
                     T::MultiSig::multi_sig_id_for(&from, &multi_sig_addr, &proposal.encode());
-                let pending = PendingState::<T::Proposal> {
+                let pending = PendingStateV1::<T::Proposal> {
                     yet_needed: info.required_num,
-                    owners_done: 0,
+                    owners_done: Vec::new(),
                     proposal,
                 };
                 pending_list.push(multi_sig_id);
 
                 debug!("[execute_impl]|multi_sig_addr:{:?}|new proposal multisig id:{:?}|current all multisig:{:?}", multi_sig_addr, multi_sig_id, pending_list);
 
-                PendingStateFor::<T>::insert(&(multi_sig_addr.clone(), multi_sig_id), pending);
+                PendingStateV1For::<T>::insert(&(multi_sig_addr.clone(), multi_sig_id), pending);
                 PendingListFor::<T>::insert(multi_sig_addr, pending_list);
 
+                let expiry = Self::proposal_policy_of(multi_sig_addr).expiry;
+                if let Some(expiry) = expiry.filter(|e| !e.is_zero()) {
+                    let expire_at = <system::Module<T>>::block_number() + expiry;
+                    PendingExpiryOf::<T>::insert(
+                        &(multi_sig_addr.clone(), multi_sig_id),
+                        expire_at,
+                    );
+                    PendingExpireAt::<T>::mutate(expire_at, |v| {
+                        v.push((multi_sig_addr.clone(), multi_sig_id))
+                    });
+                }
+
                 // confirm for self
                 let origin = system::RawOrigin::Signed(from.clone()).into();
                 Self::confirm(origin, multi_sig_addr.clone(), multi_sig_id)?;
@@ -469,7 +776,7 @@ impl<T: Trait> Module<T> {
         let ret = Self::only_many_owner(&from, &multi_sig_addr, multi_sig_id)?;
 
         let pending_state = if let Some(pending_state) =
-            Self::pending_state_for(&(multi_sig_addr.clone(), multi_sig_id))
+            Self::try_get_pending_state(&(multi_sig_addr.clone(), multi_sig_id))
         {
             pending_state
         } else {
@@ -479,10 +786,11 @@ impl<T: Trait> Module<T> {
         debug!("[confirm_impl]|from:{:?}|foraddr:{:?}|multisig id:{:}|ret:{:}|yet_needed:{:?} and owners_down:{:?}", from, multi_sig_addr, multi_sig_id, ret, pending_state.yet_needed, pending_state.owners_done);
 
         if ret == true {
+            Self::check_queue(&multi_sig_addr, multi_sig_id)?;
             // remove log
             Self::remove_multi_sig_id(&multi_sig_addr, multi_sig_id);
             // real exec
-            Self::exec(&multi_sig_addr, pending_state.proposal)?;
+            Self::exec_or_queue(&multi_sig_addr, multi_sig_id, pending_state.proposal)?;
         } else {
             // log event
             Self::deposit_event(RawEvent::Confirm(
@@ -498,17 +806,113 @@ impl<T: Trait> Module<T> {
 }
 
 impl<T: Trait> Module<T> {
+    fn execution_delay(addr: &T::AccountId) -> T::BlockNumber {
+        Self::proposal_policy_of(addr).delay.unwrap_or_default()
+    }
+
+    fn check_queue(addr: &T::AccountId, multi_sig_id: T::Hash) -> Result {
+        if Self::execution_delay(addr).is_zero() {
+            return Ok(());
+        }
+        let queued_list = Self::queued_list_for(addr);
+        if queued_list.len() as u32 >= MAX_PENDING {
+            return Err("queued list can't be larger than MAX_PENDING");
+        }
+        if queued_list.contains(&multi_sig_id) {
+            return Err("this proposal has been queued");
+        }
+        Ok(())
+    }
+
+    /// exec the proposal directly, or queue it until the execution delay passed.
+    fn exec_or_queue(
+        addr: &T::AccountId,
+        multi_sig_id: T::Hash,
+        proposal: Box<T::Proposal>,
+    ) -> Result {
+        let delay = Self::execution_delay(addr);
+        if delay.is_zero() {
+            return Self::exec(addr, proposal);
+        }
+
+        let execute_at = <system::Module<T>>::block_number() + delay;
+        debug!(
+            "[exec_or_queue]|queue proposal|addr:{:?}|multi_sig_id:{:?}|execute_at:{:?}",
+            addr, multi_sig_id, execute_at
+        );
+        QueuedListFor::<T>::mutate(addr, |v| v.push(multi_sig_id));
+        QueuedProposalFor::<T>::insert(
+            &(addr.clone(), multi_sig_id),
+            QueuedProposal {
+                execute_at,
+                proposal,
+            },
+        );
+        QueuedExecuteAt::<T>::mutate(execute_at, |v| v.push((addr.clone(), multi_sig_id)));
+        Self::deposit_event(RawEvent::QueueMultiSig(
+            addr.clone(),
+            multi_sig_id,
+            execute_at,
+        ));
+        Ok(())
+    }
+
+    fn remove_expired(now: T::BlockNumber) {
+        for (addr, multi_sig_id) in PendingExpireAt::<T>::take(now) {
+            // the proposal may have been finished or removed before expiry
+            if Self::pending_expiry_of(&(addr.clone(), multi_sig_id)) != Some(now) {
+                continue;
+            }
+            info!(
+                "[remove_expired]|proposal expired|addr:{:?}|multi_sig_id:{:?}",
+                addr, multi_sig_id
+            );
+            Self::remove_multi_sig_id(&addr, multi_sig_id);
+            Self::deposit_event(RawEvent::ExpireMultiSig(addr, multi_sig_id));
+        }
+    }
+
+    fn exec_queued(now: T::BlockNumber) {
+        for (addr, multi_sig_id) in QueuedExecuteAt::<T>::take(now) {
+            // the proposal may have been vetoed
+            let queued = match Self::queued_proposal_for(&(addr.clone(), multi_sig_id)) {
+                Some(queued) => queued,
+                None => continue,
+            };
+            if queued.execute_at != now {
+                continue;
+            }
+            Self::remove_queued(&addr, multi_sig_id);
+
+            let ret = Self::exec(&addr, queued.proposal);
+            if let Err(e) = ret {
+                error!(
+                    "[exec_queued]|exec queued proposal failed|addr:{:?}|multi_sig_id:{:?}|err:{:}",
+                    addr, multi_sig_id, e
+                );
+            }
+            Self::deposit_event(RawEvent::ExecQueuedMultiSig(
+                addr,
+                multi_sig_id,
+                ret.is_ok(),
+            ));
+        }
+    }
+
     fn exec(addr: &T::AccountId, proposal: Box<T::Proposal>) -> Result {
         if let Some(info) = Self::multisig_addr_info(addr) {
             debug!(
                 "[exec]|real exec|addr:{:?}|addr_type:{:?}",
                 addr, info.addr_type
             );
-            match info.addr_type {
+            T::ProposalHook::before_dispatch(&proposal)?;
+            let ret = match info.addr_type {
                 AddrType::Normal => Self::exec_tx(addr, proposal),
                 AddrType::Root => Self::exec_tx_byroot(addr, proposal),
                 AddrType::Trustee => Self::exec_tx_bytrustee(addr, proposal),
-            }
+            };
+            T::ProposalHook::after_dispatch();
+            ret
         } else {
             Err("addr info not exist")
         }
@@ -4,6 +4,8 @@
 
 use super::*;
 
+use std::cell::RefCell;
+
 use parity_codec::Decode;
 
 use primitives::testing::{Digest, DigestItem, Header, UintAuthorityId};
@@ -62,6 +64,25 @@ impl LimitedCall<AccountId> for TrusteeCall {
     }
 }
 
+thread_local! {
+    pub static FORBIDDEN: RefCell<bool> = RefCell::new(false);
+    pub static DISPATCHED: RefCell<u32> = RefCell::new(0);
+}
+
+pub struct MockProposalHook;
+impl ProposalHook<TestCall> for MockProposalHook {
+    fn before_dispatch(_proposal: &TestCall) -> Result {
+        if FORBIDDEN.with(|f| *f.borrow()) {
+            return Err("forbidden proposal");
+        }
+        Ok(())
+    }
+
+    fn after_dispatch() {
+        DISPATCHED.with(|d| *d.borrow_mut() += 1);
+    }
+}
+
 #[derive(Clone, Eq, PartialEq, Encode, Decode, Debug)]
 pub enum MyCall {
     #[allow(non_camel_case_types)]
@@ -227,6 +248,23 @@ impl xaccounts::IntentionJackpotAccountIdFor<AccountId> for MockDeterminator {
     }
 }
 
+impl xassets::Trait for Test {
+    type Balance = u64;
+    type OnNewAccount = ();
+    type Event = ();
+    type OnAssetChanged = ();
+    type OnAssetRegisterOrRevoke = ();
+    type DetermineTokenJackpotAccountId = ();
+}
+impl xassets::Trait for Test2 {
+    type Balance = u64;
+    type OnNewAccount = ();
+    type Event = ();
+    type OnAssetChanged = ();
+    type OnAssetRegisterOrRevoke = ();
+    type DetermineTokenJackpotAccountId = ();
+}
+
 impl xsession::Trait for Test {
     type ConvertAccountIdToSessionKey = ();
     type OnSessionChange = ();
@@ -238,6 +276,7 @@ impl Trait for Test {
     type GenesisMultiSig = ChainXGenesisMultisig<Test>;
     type Proposal = TestCall;
     type TrusteeCall = TrusteeCall;
+    type ProposalHook = MockProposalHook;
     type Event = ();
 }
 
@@ -246,11 +285,13 @@ impl Trait for Test2 {
     type GenesisMultiSig = ChainXGenesisMultisig<Test2>;
     type Proposal = MyCall;
     type TrusteeCall = TrusteeCall2;
+    type ProposalHook = ();
     type Event = ();
 }
 
 pub type MultiSig = Module<Test>;
 pub type MultiSig2 = Module<Test2>;
+pub type XAssets = xassets::Module<Test>;
 
 pub fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
     let t = system::GenesisConfig::<Test>::default()
@@ -258,7 +299,19 @@ pub fn new_test_ext() -> runtime_io::TestExternalities<Blake2Hasher> {
         .unwrap()
         .0;
 
-    runtime_io::TestExternalities::new(t)
+    let mut init = runtime_io::TestExternalities::new(t);
+    runtime_io::with_externalities(&mut init, || {
+        let pcx = xassets::Asset::new(
+            <XAssets as xassets::ChainT>::TOKEN.to_vec(),
+            b"PolkadotChainX".to_vec(),
+            xassets::Chain::ChainX,
+            8,
+            b"PCX onchain token".to_vec(),
+        )
+        .unwrap();
+        XAssets::bootstrap_register_asset(pcx, true, false).unwrap();
+    });
+    init
 }
 
 pub fn new_test_ext2() -> runtime_io::TestExternalities<Blake2Hasher> {
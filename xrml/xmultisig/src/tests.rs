@@ -6,6 +6,7 @@ use super::mock::*;
 use super::*;
 
 use matches::matches;
+use primitives::traits::OnFinalize;
 use runtime_io::with_externalities;
 use substrate_primitives::crypto::UncheckedInto;
 use substrate_primitives::H256;
@@ -102,10 +103,10 @@ fn test_multisig() {
         assert_eq!(MultiSig::pending_list_for(&addr).len(), 1);
         let multi_sig_id = MultiSig::pending_list_for(&addr).get(0).unwrap().clone();
         assert_eq!(
-            MultiSig::pending_state_for(&(addr.clone(), multi_sig_id)),
-            Some(PendingState::<<Test as Trait>::Proposal> {
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
                 yet_needed: 2,
-                owners_done: 1,
+                owners_done: vec![0],
                 proposal
             })
         );
@@ -128,7 +129,7 @@ fn test_multisig() {
 
         // has delete
         assert_eq!(
-            MultiSig::pending_state_for(&(addr.clone(), multi_sig_id)),
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
             None
         );
         assert_eq!(MultiSig::pending_list_for(&addr), vec![]);
@@ -267,10 +268,10 @@ fn test_remove() {
 
         // yet need 1
         assert_eq!(
-            MultiSig::pending_state_for(&(addr.clone(), multi_sig_id)),
-            Some(PendingState::<<Test as Trait>::Proposal> {
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
                 yet_needed: 1,
-                owners_done: 3,
+                owners_done: vec![0, 1],
                 proposal: proposal.clone()
             })
         );
@@ -282,10 +283,10 @@ fn test_remove() {
             "it's the owner but not required owner"
         );
         assert_eq!(
-            MultiSig::pending_state_for(&(addr.clone(), multi_sig_id)),
-            Some(PendingState::<<Test as Trait>::Proposal> {
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
                 yet_needed: 1,
-                owners_done: 3,
+                owners_done: vec![0, 1],
                 proposal: proposal.clone()
             })
         );
@@ -300,7 +301,7 @@ fn test_remove() {
 
         // has del
         assert_eq!(
-            MultiSig::pending_state_for(&(addr.clone(), multi_sig_id)),
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
             None
         );
 
@@ -592,25 +593,95 @@ fn test_deploy() {
         let a: AccountId = H256::repeat_byte(0x1).unchecked_into();
         let b: AccountId = H256::repeat_byte(0x2).unchecked_into();
         let c: AccountId = H256::repeat_byte(0x3).unchecked_into();
-        let d: AccountId = H256::repeat_byte(0x4).unchecked_into();
-        let e: AccountId = H256::repeat_byte(0x5).unchecked_into();
-        // root multisig addr
-        let owners = vec![
-            (a.clone(), MultiSigPermission::ConfirmAndPropose),
-            (b.clone(), MultiSigPermission::ConfirmAndPropose),
-            (c.clone(), MultiSigPermission::ConfirmOnly),
-        ];
-        MultiSig::deploy_in_genesis(owners.clone(), 2, owners.clone(), 2).unwrap();
-        // no multisig addr
-        let _owners = vec![
-            (a.clone(), MultiSigPermission::ConfirmAndPropose),
-            (b.clone(), MultiSigPermission::ConfirmAndPropose),
-            (c.clone(), MultiSigPermission::ConfirmOnly),
-            (d.clone(), MultiSigPermission::ConfirmOnly),
-            (e.clone(), MultiSigPermission::ConfirmOnly),
-        ];
-        // let _origin = system::RawOrigin::Signed(a.clone()).into();
-        // current deploy has been removed.
+        let owners = vec![(b.clone(), true), (c.clone(), false)];
+        let deposit = MultiSig::deploy_deposit();
+
+        // deposit not enough
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::deploy(origin, owners.clone(), 2),
+            "free balance not enough for deploy deposit"
+        );
+
+        XAssets::pcx_issue(&a, deposit + 100).unwrap();
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::deploy(origin, owners.clone(), 0),
+            "required num can't be zero"
+        );
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::deploy(origin, owners.clone(), 4),
+            "owners count can't less than required num"
+        );
+        assert_eq!(XAssets::pcx_free_balance(&a), deposit + 100);
+
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::deploy(origin, owners.clone(), 2));
+
+        let addr = MultiSig::multi_sig_list_item_for(&(a.clone(), 0));
+        let addr_info = MultiSig::multisig_addr_info(&addr).unwrap();
+        assert!(matches!(addr_info.addr_type, AddrType::Normal));
+        assert_eq!(addr_info.required_num, 2);
+        assert_eq!(
+            addr_info.owner_list,
+            vec![
+                (a.clone(), MultiSigPermission::ConfirmAndPropose),
+                (b.clone(), MultiSigPermission::ConfirmAndPropose),
+                (c.clone(), MultiSigPermission::ConfirmOnly),
+            ]
+        );
+        assert_eq!(MultiSig::multisig_addrs_of(&c), vec![addr.clone()]);
+
+        assert_eq!(XAssets::pcx_free_balance(&a), 100);
+        assert_eq!(
+            XAssets::pcx_type_balance(&a, xassets::AssetType::ReservedMultiSig),
+            deposit
+        );
+        assert_eq!(
+            MultiSig::multisig_deposit_of(&addr),
+            Some((a.clone(), deposit))
+        );
+
+        // normal proposal exec by the multisig addr itself
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        let origin = system::RawOrigin::Signed(c.clone()).into();
+        assert_err!(
+            MultiSig::confirm(origin, addr.clone(), multi_sig_id),
+            "call success"
+        );
+
+        // the multisig addr removes itself, the deposit is unreserved for the deployer
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(true))
+        ));
+        assert_eq!(MultiSig::pending_list_for(&addr).len(), 1);
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::remove_multi_sig_addr(origin),
+            "only the multisig addr deployed with a deposit could be removed"
+        );
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_ok!(MultiSig::remove_multi_sig_addr(origin));
+        assert_eq!(MultiSig::multisig_addr_info(&addr), None);
+        assert!(MultiSig::pending_list_for(&addr).is_empty());
+        assert_eq!(MultiSig::multisig_deposit_of(&addr), None);
+        assert!(MultiSig::multisig_addrs_of(&a).is_empty());
+        assert!(MultiSig::multisig_addrs_of(&c).is_empty());
+        assert_eq!(XAssets::pcx_free_balance(&a), deposit + 100);
+        assert_eq!(
+            XAssets::pcx_type_balance(&a, xassets::AssetType::ReservedMultiSig),
+            0
+        );
     })
 }
 
@@ -687,6 +758,10 @@ fn test_transition() {
         let addr2 = MultiSig2::multi_sig_list_item_for(&(a.clone(), 1));
         // trustee
         let addr3 = MultiSig2::multi_sig_list_item_for(&(a.clone(), 2));
+        assert_eq!(
+            MultiSig2::multisig_addrs_of(&c),
+            vec![addr1.clone(), addr2.clone(), addr3.clone()]
+        );
 
         // user call directly, must fail
         let origin = system::RawOrigin::Signed(a.clone()).into();
@@ -714,6 +789,12 @@ fn test_transition() {
             .collect::<Vec<_>>();
         assert_eq!(addr_info.owner_list, expect);
         assert_eq!(addr_info.required_num, 2);
+        // the removed owner loses the addr
+        assert_eq!(
+            MultiSig2::multisig_addrs_of(&c),
+            vec![addr2.clone(), addr3.clone()]
+        );
+        assert!(MultiSig2::multisig_addrs_of(&b).contains(&addr1));
 
         let mycall = MyCall::transition(vec![], 0);
         let proposal = Box::new(mycall);
@@ -747,3 +828,297 @@ fn test_transition() {
         );
     })
 }
+
+#[test]
+fn test_expiry_and_queue() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: AccountId = H256::repeat_byte(0x1).unchecked_into();
+        let b: AccountId = H256::repeat_byte(0x2).unchecked_into();
+        let c: AccountId = H256::repeat_byte(0x3).unchecked_into();
+        let owners = vec![
+            (a.clone(), MultiSigPermission::ConfirmAndPropose),
+            (b.clone(), MultiSigPermission::ConfirmAndPropose),
+            (c.clone(), MultiSigPermission::ConfirmOnly),
+        ];
+        deploy(a.clone(), owners, 2);
+        let addr = MultiSig::multi_sig_list_item_for(&(a.clone(), 0));
+
+        // user call directly, must fail
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::set_proposal_policy(origin, Some(5), Some(3)),
+            "multisig address not exist."
+        );
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_ok!(MultiSig::set_proposal_policy(origin, Some(5), Some(3)));
+
+        // expiry
+        system::Module::<Test>::set_block_number(1);
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        assert_eq!(
+            MultiSig::pending_expiry_of(&(addr.clone(), multi_sig_id)),
+            Some(6)
+        );
+        system::Module::<Test>::set_block_number(6);
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_err!(
+            MultiSig::confirm(origin, addr.clone(), multi_sig_id),
+            "this proposal has expired"
+        );
+        MultiSig::on_finalize(6);
+        assert_eq!(MultiSig::pending_list_for(&addr), vec![]);
+        assert_eq!(
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            None
+        );
+
+        // veto during the delay
+        system::Module::<Test>::set_block_number(7);
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::confirm(origin, addr.clone(), multi_sig_id));
+        assert_eq!(MultiSig::pending_list_for(&addr), vec![]);
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![multi_sig_id]);
+        assert_eq!(
+            MultiSig::queued_proposal_for(&(addr.clone(), multi_sig_id))
+                .unwrap()
+                .execute_at,
+            10
+        );
+        let origin = system::RawOrigin::Signed(c.clone()).into();
+        assert_ok!(MultiSig::veto(origin, addr.clone(), multi_sig_id));
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![]);
+        let origin = system::RawOrigin::Signed(c.clone()).into();
+        assert_err!(
+            MultiSig::veto(origin, addr.clone(), multi_sig_id),
+            "queued proposal not exist"
+        );
+
+        // exec after the delay
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::confirm(origin, addr.clone(), multi_sig_id));
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![multi_sig_id]);
+        MultiSig::on_finalize(9);
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![multi_sig_id]);
+        MultiSig::on_finalize(10);
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![]);
+        assert_eq!(
+            MultiSig::queued_proposal_for(&(addr.clone(), multi_sig_id)),
+            None
+        );
+    })
+}
+
+#[test]
+fn test_owner_weights() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: AccountId = H256::repeat_byte(0x1).unchecked_into();
+        let b: AccountId = H256::repeat_byte(0x2).unchecked_into();
+        let c: AccountId = H256::repeat_byte(0x3).unchecked_into();
+        let d: AccountId = H256::repeat_byte(0x4).unchecked_into();
+        let e: AccountId = H256::repeat_byte(0x5).unchecked_into();
+        let owners = vec![
+            (a.clone(), MultiSigPermission::ConfirmAndPropose),
+            (b.clone(), MultiSigPermission::ConfirmAndPropose),
+            (c.clone(), MultiSigPermission::ConfirmOnly),
+            (d.clone(), MultiSigPermission::ConfirmOnly),
+        ];
+        deploy(a.clone(), owners.clone(), 3);
+        let addr = MultiSig::multi_sig_list_item_for(&(a.clone(), 0));
+        assert_eq!(MultiSig::owner_weights_for(&addr), vec![]);
+
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_err!(
+            MultiSig::set_owner_weights(origin, vec![(e.clone(), 2)], 3),
+            "not an owner of the multisig addr"
+        );
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_err!(
+            MultiSig::set_owner_weights(origin, vec![(a.clone(), 2)], 6),
+            "total weight can't less than threshold"
+        );
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_ok!(MultiSig::set_owner_weights(
+            origin,
+            vec![(a.clone(), 3), (b.clone(), 2)],
+            3
+        ));
+        assert_eq!(MultiSig::owner_weights_for(&addr), vec![3, 2, 1, 1]);
+        assert_eq!(MultiSig::multisig_addr_info(&addr).unwrap().required_num, 3);
+
+        // a reaches the threshold alone
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_err!(
+            MultiSig::execute(origin, addr.clone(), Box::new(TestCall(false))),
+            "call success"
+        );
+        assert_eq!(MultiSig::pending_list_for(&addr), vec![]);
+
+        // b needs another weight
+        let proposal = Box::new(TestCall(false));
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::execute(origin, addr.clone(), proposal.clone()));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        assert_eq!(
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
+                yet_needed: 1,
+                owners_done: vec![1],
+                proposal
+            })
+        );
+        let origin = system::RawOrigin::Signed(d.clone()).into();
+        assert_err!(
+            MultiSig::confirm(origin, addr.clone(), multi_sig_id),
+            "call success"
+        );
+
+        // pending proposals are dropped when the weights change
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(true))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_ok!(MultiSig::set_owner_weights(origin, vec![(b.clone(), 3)], 3));
+        assert_eq!(MultiSig::pending_list_for(&addr), vec![]);
+        assert_eq!(
+            MultiSig::try_get_pending_state(&(addr.clone(), multi_sig_id)),
+            None
+        );
+
+        // transition resets the weights
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        let new_owners = vec![(a.clone(), true), (b.clone(), true)];
+        assert_ok!(MultiSig::transition(origin, new_owners, 2));
+        assert_eq!(MultiSig::owner_weights_for(&addr), vec![]);
+    })
+}
+
+#[test]
+fn test_pending_state_v1() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: AccountId = H256::repeat_byte(0x1).unchecked_into();
+        let b: AccountId = H256::repeat_byte(0x2).unchecked_into();
+        let c: AccountId = H256::repeat_byte(0x3).unchecked_into();
+        let d: AccountId = H256::repeat_byte(0x4).unchecked_into();
+        let owners = vec![
+            (a.clone(), MultiSigPermission::ConfirmAndPropose),
+            (b.clone(), MultiSigPermission::ConfirmAndPropose),
+            (c.clone(), MultiSigPermission::ConfirmOnly),
+            (d.clone(), MultiSigPermission::ConfirmOnly),
+        ];
+        deploy(a.clone(), owners.clone(), 4);
+        let addr = MultiSig::multi_sig_list_item_for(&(a.clone(), 0));
+
+        // a pending proposal in the old encoding, confirmed by a and c
+        let multi_sig_id = H256::repeat_byte(0x9);
+        let key = (addr.clone(), multi_sig_id);
+        let proposal = Box::new(TestCall(false));
+        PendingListFor::<Test>::insert(&addr, vec![multi_sig_id]);
+        PendingStateFor::<Test>::insert(
+            &key,
+            PendingState {
+                yet_needed: 2,
+                owners_done: 0b101,
+                proposal: proposal.clone(),
+            },
+        );
+        assert_eq!(
+            MultiSig::try_get_pending_state(&key),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
+                yet_needed: 2,
+                owners_done: vec![0, 2],
+                proposal: proposal.clone(),
+            })
+        );
+
+        let origin = system::RawOrigin::Signed(c.clone()).into();
+        assert_err!(
+            MultiSig::confirm(origin, addr.clone(), multi_sig_id),
+            "this account has confirmed for this multi sig addr and id"
+        );
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::confirm(origin, addr.clone(), multi_sig_id));
+        assert_eq!(MultiSig::pending_state_for(&key), None);
+        assert_eq!(
+            MultiSig::pending_state_v1_for(&key),
+            Some(PendingStateV1::<<Test as Trait>::Proposal> {
+                yet_needed: 1,
+                owners_done: vec![0, 2, 1],
+                proposal,
+            })
+        );
+    })
+}
+
+#[test]
+fn test_forbidden_proposal() {
+    with_externalities(&mut new_test_ext(), || {
+        let a: AccountId = H256::repeat_byte(0x1).unchecked_into();
+        let b: AccountId = H256::repeat_byte(0x2).unchecked_into();
+        let owners = vec![
+            (a.clone(), MultiSigPermission::ConfirmAndPropose),
+            (b.clone(), MultiSigPermission::ConfirmAndPropose),
+        ];
+        deploy(a.clone(), owners, 2);
+        let addr = MultiSig::multi_sig_list_item_for(&(a.clone(), 0));
+
+        // confirmed
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        FORBIDDEN.with(|f| *f.borrow_mut() = true);
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_err!(
+            MultiSig::confirm(origin, addr.clone(), multi_sig_id),
+            "forbidden proposal"
+        );
+        assert_eq!(DISPATCHED.with(|d| *d.borrow()), 0);
+
+        // queued
+        FORBIDDEN.with(|f| *f.borrow_mut() = false);
+        let origin = system::RawOrigin::Signed(addr.clone()).into();
+        assert_ok!(MultiSig::set_proposal_policy(origin, None, Some(3)));
+        system::Module::<Test>::set_block_number(1);
+        let origin = system::RawOrigin::Signed(a.clone()).into();
+        assert_ok!(MultiSig::execute(
+            origin,
+            addr.clone(),
+            Box::new(TestCall(false))
+        ));
+        let multi_sig_id = MultiSig::pending_list_for(&addr)[0];
+        let origin = system::RawOrigin::Signed(b.clone()).into();
+        assert_ok!(MultiSig::confirm(origin, addr.clone(), multi_sig_id));
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![multi_sig_id]);
+        FORBIDDEN.with(|f| *f.borrow_mut() = true);
+        MultiSig::on_finalize(4);
+        assert_eq!(MultiSig::queued_list_for(&addr), vec![]);
+        assert_eq!(DISPATCHED.with(|d| *d.borrow()), 0);
+        FORBIDDEN.with(|f| *f.borrow_mut() = false);
+    })
+}
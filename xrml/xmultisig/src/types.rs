@@ -32,6 +32,7 @@ impl Default for AddrType {
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
 pub struct AddrInfo<AccountId> {
     pub addr_type: AddrType,
+    /// threshold weight of confirmations, every owner weights 1 if not set
     pub required_num: u32,
     pub owner_list: Vec<(AccountId, MultiSigPermission)>,
 }
@@ -44,3 +45,47 @@ pub struct PendingState<Proposal> {
     pub owners_done: u64,
     pub proposal: Box<Proposal>,
 }
+
+/// This is same with PendingState with `owners_done` extended from a u64 bitmap to the owner
+/// indexes, thus the owners count is not capped by 64.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+pub struct PendingStateV1<Proposal> {
+    /// weight still needed
+    pub yet_needed: u32,
+    /// indexes of the owners who have confirmed
+    pub owners_done: Vec<u32>,
+    pub proposal: Box<Proposal>,
+}
+
+impl<Proposal> From<PendingState<Proposal>> for PendingStateV1<Proposal> {
+    fn from(pending: PendingState<Proposal>) -> Self {
+        // every owner weights 1 before the weights are introduced
+        let owners_done = (0..64_u32)
+            .filter(|index| pending.owners_done & (1 << index) != 0)
+            .collect();
+        PendingStateV1 {
+            yet_needed: pending.yet_needed,
+            owners_done,
+            proposal: pending.proposal,
+        }
+    }
+}
+
+/// optional durations applied to the proposals of a multisig addr.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+pub struct ProposalPolicy<BlockNumber> {
+    /// a pending proposal expires after these blocks if not confirmed enough.
+    pub expiry: Option<BlockNumber>,
+    /// a confirmed proposal is queued these blocks before execution, owners could veto it meanwhile.
+    pub delay: Option<BlockNumber>,
+}
+
+// struct for a confirmed proposal waiting for execution.
+#[derive(PartialEq, Eq, Clone, Encode, Decode, Default)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize, Debug))]
+pub struct QueuedProposal<Proposal, BlockNumber> {
+    pub execute_at: BlockNumber,
+    pub proposal: Box<Proposal>,
+}